@@ -24,8 +24,21 @@ pub struct AIServiceState {
 impl AIServiceState {
     /// Create a new AI service state
     pub fn new() -> Result<Self, String> {
-        let service = AITestCaseService::new()
-            .map_err(|e| format!("Failed to create AI service: {}", e))?;
+        Self::with_monitor(None, rust_automation_core::TokenBudget::default())
+    }
+
+    /// Create a new AI service state that reports provider failures to
+    /// `monitor` and charges generation to `budget`
+    pub fn with_monitor(
+        monitor: Option<rust_automation_core::CoreMonitor>,
+        budget: rust_automation_core::TokenBudget,
+    ) -> Result<Self, String> {
+        let mut service = AITestCaseService::new()
+            .map_err(|e| format!("Failed to create AI service: {}", e))?
+            .with_budget(budget);
+        if let Some(monitor) = monitor {
+            service = service.with_monitor(monitor);
+        }
         
         let config_manager = ConfigManager::new()
             .map_err(|e| format!("Failed to create config manager: {}", e))?;
@@ -421,6 +434,37 @@ pub async fn get_recent_errors(
     Ok(service.get_recent_errors(limit).await)
 }
 
+/// Get AI budget status
+/// 
+/// Retrieves daily and per-run token/cost usage, the configured limits, and
+/// any budget alerts raised so far.
+#[tauri::command]
+pub async fn get_ai_budget_status(
+    state: State<'_, AIServiceState>,
+) -> Result<rust_automation_core::BudgetStatus, String> {
+    log::debug!("[AI Test Case] Getting AI budget status");
+    
+    let service = state.service.read().await;
+    Ok(service.get_budget_status())
+}
+
+/// Update AI budget limits
+/// 
+/// Replaces the daily and per-run token/cost limits and the action taken
+/// when they are exceeded, and saves them for the next start. Usage recorded
+/// so far is kept.
+#[tauri::command]
+pub async fn update_ai_budget_config(
+    config: rust_automation_core::TokenBudgetConfig,
+    state: State<'_, AIServiceState>,
+) -> Result<rust_automation_core::BudgetStatus, String> {
+    log::info!("[AI Test Case] Updating AI budget configuration");
+    
+    let service = state.service.read().await;
+    service.update_budget_config(config)?;
+    Ok(service.get_budget_status())
+}
+
 // ============================================================================
 // Integration Commands
 // Requirements: 6.4
@@ -64,6 +64,10 @@ pub enum AITestCaseError {
     #[error("Maximum retry attempts ({max_attempts}) exceeded")]
     MaxRetriesExceeded { max_attempts: u32 },
 
+    /// Daily or per-run AI token/cost budget exhausted
+    #[error("AI budget exceeded: {message}")]
+    BudgetExceeded { message: String },
+
     /// Generic internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -16,6 +16,7 @@ pub mod models;
 pub mod monitoring;
 pub mod service;
 pub mod validation;
+pub mod vision;
 
 // Re-export main types for convenience
 pub use commands::AIServiceState;
@@ -32,3 +33,4 @@ pub use monitoring::{
 };
 pub use service::AITestCaseService;
 pub use validation::{TestCaseValidator, ValidationResult};
+pub use vision::GeminiVisionProvider;
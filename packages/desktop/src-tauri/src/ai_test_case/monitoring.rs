@@ -7,6 +7,7 @@
 use crate::ai_test_case::error::AITestCaseError;
 use crate::ai_test_case::models::TokenUsage;
 use chrono::{DateTime, Utc};
+use rust_automation_core::{AITokenUsage, BudgetDecision, BudgetStatus, TokenBudget, TokenBudgetConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -128,6 +129,8 @@ pub struct MonitoringService {
     usage_patterns: Arc<RwLock<UsagePattern>>,
     /// Maximum entries to keep in memory
     max_entries: usize,
+    /// Daily and per-run token/cost budget
+    budget: TokenBudget,
 }
 
 impl MonitoringService {
@@ -139,9 +142,39 @@ impl MonitoringService {
             token_stats: Arc::new(RwLock::new(TokenUsageStats::default())),
             usage_patterns: Arc::new(RwLock::new(UsagePattern::default())),
             max_entries: 1000, // Keep last 1000 entries
+            budget: TokenBudget::default(),
         }
     }
 
+    /// Create a monitoring service that enforces the given budget
+    pub fn with_budget(budget: TokenBudget) -> Self {
+        MonitoringService {
+            budget,
+            ..Self::new()
+        }
+    }
+
+    /// Shared handle to the token budget, e.g. for AI vision playback
+    pub fn budget(&self) -> TokenBudget {
+        self.budget.clone()
+    }
+
+    /// Check whether the next AI call fits in the budget
+    pub fn check_budget(&self) -> BudgetDecision {
+        self.budget.check()
+    }
+
+    /// Get budget usage, limits and alerts
+    pub fn get_budget_status(&self) -> BudgetStatus {
+        self.budget.status()
+    }
+
+    /// Replace the budget limits and save them for the next start
+    pub fn update_budget_config(&self, config: TokenBudgetConfig) -> Result<(), String> {
+        self.budget.set_config(config);
+        self.budget.save_config().map_err(|e| e.user_message())
+    }
+
     /// Log performance metrics for an operation
     /// Requirements: 8.5
     pub async fn log_performance(
@@ -227,6 +260,12 @@ impl MonitoringService {
         stats.total_tokens += token_usage.total_tokens as u64;
         stats.request_count += 1;
         stats.last_updated = Utc::now();
+        drop(stats);
+
+        let usage = AITokenUsage::new(token_usage.prompt_tokens as u64, token_usage.completion_tokens as u64);
+        for alert in self.budget.record_usage(usage) {
+            log::warn!("[AI Test Case Monitor] {}", alert.message);
+        }
     }
     /// Update usage patterns
     /// Requirements: 10.5
@@ -335,6 +374,7 @@ impl MonitoringService {
             AITestCaseError::InputError { .. } => "InputError".to_string(),
             AITestCaseError::KeyringError { .. } => "KeyringError".to_string(),
             AITestCaseError::MaxRetriesExceeded { .. } => "MaxRetriesExceeded".to_string(),
+            AITestCaseError::BudgetExceeded { .. } => "BudgetExceeded".to_string(),
             AITestCaseError::Internal(_) => "Internal".to_string(),
         }
     }
@@ -439,6 +479,26 @@ mod tests {
         assert!((cost_estimation.estimated_cost_usd - 0.000225).abs() < 0.000001);
     }
 
+    #[tokio::test]
+    async fn test_token_usage_counts_against_budget() {
+        let monitor = MonitoringService::with_budget(TokenBudget::new(TokenBudgetConfig {
+            daily: rust_automation_core::BudgetLimits { max_tokens: Some(1000), max_cost_usd: None },
+            ..Default::default()
+        }));
+
+        let token_usage = TokenUsage {
+            prompt_tokens: 800,
+            completion_tokens: 300,
+            total_tokens: 1100,
+        };
+        monitor.log_performance("test_operation", Duration::from_millis(200), true, Some(token_usage)).await;
+
+        assert!(matches!(monitor.check_budget(), BudgetDecision::Degraded { .. }));
+        let status = monitor.get_budget_status();
+        assert_eq!(status.daily_usage.total_tokens(), 1100);
+        assert_eq!(status.alerts.len(), 1);
+    }
+
     // Property test generators
     prop_compose! {
         fn arb_token_usage()(
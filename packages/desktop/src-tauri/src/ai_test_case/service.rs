@@ -13,6 +13,7 @@ use crate::ai_test_case::models::{
 use crate::ai_test_case::monitoring::MonitoringService;
 use crate::ai_test_case::validation::TestCaseValidator;
 use reqwest::Client;
+use rust_automation_core::BudgetDecision;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -63,6 +64,8 @@ pub struct AITestCaseService {
     /// Monitoring service
     /// Requirements: 8.1, 8.3, 8.5, 10.1, 10.3, 10.5
     monitoring: MonitoringService,
+    /// Core monitor told about provider failures for crash-loop protection
+    core_monitor: Option<rust_automation_core::CoreMonitor>,
 }
 
 impl AITestCaseService {
@@ -81,9 +84,22 @@ impl AITestCaseService {
             config_manager: Arc::new(RwLock::new(config_manager)),
             validator: TestCaseValidator::new(),
             monitoring: MonitoringService::new(),
+            core_monitor: None,
         })
     }
 
+    /// Report provider failures to the monitor for crash-loop protection
+    pub fn with_monitor(mut self, monitor: rust_automation_core::CoreMonitor) -> Self {
+        self.core_monitor = Some(monitor);
+        self
+    }
+
+    /// Charge generation to `budget`, e.g. the budget AI vision playback uses
+    pub fn with_budget(mut self, budget: rust_automation_core::TokenBudget) -> Self {
+        self.monitoring = MonitoringService::with_budget(budget);
+        self
+    }
+
     /// Get the API key from configuration
     async fn get_api_key(&self) -> Result<String> {
         let config_manager = self.config_manager.read().await;
@@ -110,6 +126,15 @@ impl AITestCaseService {
         // Requirements: 2.1
         self.validator.validate_requirements_input(requirements)?;
 
+        // Test cases cannot be produced without the AI, so any budget
+        // restriction refuses the request
+        match self.monitoring.check_budget() {
+            BudgetDecision::Allowed => {}
+            BudgetDecision::Degraded { reason } | BudgetDecision::Blocked { reason } => {
+                return Err(AITestCaseError::BudgetExceeded { message: reason });
+            }
+        }
+
         // Get API key
         let api_key = self.get_api_key().await?;
 
@@ -173,6 +198,17 @@ impl AITestCaseService {
             return Err(AITestCaseError::input_error("No actions provided"));
         }
 
+        match self.monitoring.check_budget() {
+            BudgetDecision::Allowed => {}
+            BudgetDecision::Degraded { reason } => {
+                log::warn!("[AI Test Case] {}; generating template documentation", reason);
+                return Ok(self.build_template_documentation(actions, &context, &reason, start_time));
+            }
+            BudgetDecision::Blocked { reason } => {
+                return Err(AITestCaseError::BudgetExceeded { message: reason });
+            }
+        }
+
         // Get API key
         let api_key = self.get_api_key().await?;
 
@@ -273,6 +309,14 @@ impl AITestCaseService {
         let url = format!("{}?key={}", self.build_api_url(&config.model), api_key);
         drop(config);
 
+        if let Some(monitor) = &self.core_monitor {
+            if !monitor.can_restart_subsystem(rust_automation_core::Subsystem::AiProvider) {
+                return Err(AITestCaseError::Internal(
+                    "AI provider is failing repeatedly and requests are paused. Reset its circuit breaker after fixing the cause.".to_string(),
+                ));
+            }
+        }
+
         let mut last_error = None;
         let mut attempt = 0;
 
@@ -299,6 +343,19 @@ impl AITestCaseService {
                         return Err(e);
                     }
 
+                    // Each retry restarts the provider request, so a provider that
+                    // keeps failing trips the AI provider circuit breaker
+                    if attempt < max_retries {
+                        if let Some(monitor) = &self.core_monitor {
+                            let decision = monitor
+                                .record_subsystem_restart(rust_automation_core::Subsystem::AiProvider, &e.to_string())
+                                .await;
+                            if !decision.allowed {
+                                return Err(e);
+                            }
+                        }
+                    }
+
                     last_error = Some(e);
 
                     // Wait before retry with exponential backoff
@@ -402,6 +459,16 @@ impl AITestCaseService {
         self.monitoring.get_recent_errors(limit).await
     }
 
+    /// Get AI token/cost budget usage, limits and alerts
+    pub fn get_budget_status(&self) -> rust_automation_core::BudgetStatus {
+        self.monitoring.get_budget_status()
+    }
+
+    /// Replace the AI token/cost budget limits
+    pub fn update_budget_config(&self, config: rust_automation_core::TokenBudgetConfig) -> Result<(), String> {
+        self.monitoring.update_budget_config(config)
+    }
+
     /// Add test case to project with proper metadata preservation
     /// Requirements: 6.4
     pub async fn add_test_case_to_project(
@@ -900,6 +967,52 @@ Return ONLY the JSON object, no additional text."#,
         )
     }
 
+    /// Build documentation directly from the recorded actions without calling the AI
+    ///
+    /// Used when the token budget is exhausted and configured to degrade.
+    fn build_template_documentation(
+        &self,
+        actions: &[RecordedAction],
+        context: &DocumentationContext,
+        reason: &str,
+        start_time: Instant,
+    ) -> DocumentationResponse {
+        let steps = actions
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let mut description = action.action_type.clone();
+                if let Some(ref target) = action.target {
+                    description.push_str(&format!(" on '{}'", target));
+                }
+                if let Some(ref value) = action.value {
+                    description.push_str(&format!(" with value '{}'", value));
+                }
+                TestStep {
+                    order: (i + 1) as u32,
+                    action: description,
+                    expected_outcome: None,
+                    notes: None,
+                }
+            })
+            .collect();
+
+        DocumentationResponse {
+            success: true,
+            title: format!("Test Case for {}", context.script_name),
+            description: format!("Steps recorded in {}", context.script_name),
+            preconditions: context.additional_context.clone(),
+            steps,
+            message: format!("{}. Documentation was generated from a template without AI.", reason),
+            metadata: ResponseMetadata {
+                processing_time_ms: start_time.elapsed().as_millis() as u64,
+                token_usage: None,
+                api_version: "template".to_string(),
+                generation_id: uuid::Uuid::new_v4().to_string(),
+            },
+        }
+    }
+
     /// Format recorded actions for prompt
     fn format_actions_for_prompt(&self, actions: &[RecordedAction]) -> String {
         actions
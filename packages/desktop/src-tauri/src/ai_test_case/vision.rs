@@ -0,0 +1,195 @@
+//! Gemini provider for AI Vision Capture playback
+//!
+//! The Rust player calls this provider for dynamic AI Vision Capture steps.
+//! It uses the Gemini API key stored for test case generation and reports the
+//! tokens each call spent, so playback is charged to the shared AI budget.
+
+use crate::ai_test_case::config::ConfigManager;
+use crate::ai_test_case::models::GeminiResponse;
+use reqwest::Client;
+use rust_automation_core::{AITokenUsage, AIVisionAnalysisRequest, AIVisionAnalysisResponse, AIVisionProvider, SearchScope};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Gemini API base URL
+const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+/// Model used to locate UI elements, matching the frontend AI vision service
+const VISION_MODEL: &str = "gemini-2.0-flash";
+
+/// Instructions sent ahead of every request
+const VISION_SYSTEM_PROMPT: &str = "You are a UI element locator assistant. Analyze the screenshot, \
+find the UI element described by the user and return the pixel coordinates of its center. \
+Reference images, if provided, show what the element looks like. \
+Respond with ONLY a JSON object: {\"found\": true, \"x\": <integer>, \"y\": <integer>, \"confidence\": <0..1>} \
+or, if the element cannot be found, {\"found\": false, \"error\": \"<reason>\", \"confidence\": 0}.";
+
+/// Element location returned by the model
+#[derive(Debug, Deserialize)]
+struct VisionResult {
+    found: bool,
+    x: Option<i32>,
+    y: Option<i32>,
+    #[serde(default)]
+    confidence: f64,
+    error: Option<String>,
+}
+
+/// Locates UI elements with the Gemini vision API
+pub struct GeminiVisionProvider {
+    client: Client,
+}
+
+impl GeminiVisionProvider {
+    pub fn new() -> Self {
+        Self { client: Client::new() }
+    }
+
+    fn api_key() -> Result<String, String> {
+        ConfigManager::new()
+            .and_then(|config| config.retrieve_api_key())
+            .map_err(|e| format!("Failed to read Gemini API key: {}", e))?
+            .ok_or_else(|| "Gemini API key not configured".to_string())
+    }
+
+    fn request_body(request: &AIVisionAnalysisRequest) -> serde_json::Value {
+        let mut parts = vec![serde_json::json!({
+            "inline_data": { "mime_type": "image/png", "data": request.screenshot }
+        })];
+        for reference in &request.reference_images {
+            parts.push(serde_json::json!({
+                "inline_data": { "mime_type": "image/png", "data": reference }
+            }));
+        }
+
+        let mut prompt = format!("Find the UI element described below in the screenshot.\n\nTarget: {}", request.prompt);
+        if !request.reference_images.is_empty() {
+            prompt.push_str(&format!(
+                "\n\nThe {} reference image(s) show what the target element looks like.",
+                request.reference_images.len()
+            ));
+        }
+        if let (SearchScope::Regional, Some(roi)) = (&request.search_scope, &request.roi) {
+            prompt.push_str(&format!(
+                "\n\nOnly consider the region x={}, y={}, width={}, height={}. Return coordinates of the full screenshot.",
+                roi.x, roi.y, roi.width, roi.height
+            ));
+        }
+        parts.push(serde_json::json!({ "text": prompt }));
+
+        serde_json::json!({
+            "contents": [
+                { "role": "user", "parts": [{ "text": VISION_SYSTEM_PROMPT }] },
+                { "role": "user", "parts": parts }
+            ],
+            "generationConfig": {
+                "temperature": 0.1,
+                "topK": 1,
+                "topP": 0.95,
+                "maxOutputTokens": 256,
+                "responseMimeType": "application/json"
+            }
+        })
+    }
+
+    async fn call_api(&self, request: AIVisionAnalysisRequest) -> Result<AIVisionAnalysisResponse, String> {
+        let url = format!("{}/{}:generateContent?key={}", GEMINI_API_BASE, VISION_MODEL, Self::api_key()?);
+        let response = self
+            .client
+            .post(&url)
+            .timeout(Duration::from_millis(request.timeout_ms))
+            .json(&Self::request_body(&request))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    format!("AI analysis timed out after {}ms", request.timeout_ms)
+                } else {
+                    format!("AI vision request failed: {}", e)
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("AI vision request failed: {} - {}", status, body));
+        }
+
+        let gemini_response: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse AI vision response: {}", e))?;
+        if let Some(error) = gemini_response.error {
+            return Err(error.message);
+        }
+
+        let token_usage = gemini_response.usage_metadata.map(|usage| {
+            AITokenUsage::new(
+                usage.prompt_token_count as u64,
+                usage.candidates_token_count.unwrap_or(0) as u64,
+            )
+        });
+
+        let text = gemini_response
+            .candidates
+            .and_then(|candidates| candidates.into_iter().next())
+            .map(|candidate| candidate.content.parts.into_iter().map(|part| part.text).collect::<String>())
+            .unwrap_or_default();
+
+        let result = match parse_vision_result(&text) {
+            Some(VisionResult { found: true, x: Some(x), y: Some(y), confidence, .. }) => {
+                AIVisionAnalysisResponse::success(x, y, confidence)
+            }
+            Some(result) => AIVisionAnalysisResponse::failure(
+                result.error.unwrap_or_else(|| "Element not found in screenshot".to_string()),
+            ),
+            None => AIVisionAnalysisResponse::failure("AI response was not in the expected format".to_string()),
+        };
+
+        // Tokens are spent whether or not the element was found
+        Ok(match token_usage {
+            Some(usage) => result.with_token_usage(usage),
+            None => result,
+        })
+    }
+}
+
+impl Default for GeminiVisionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AIVisionProvider for GeminiVisionProvider {
+    /// Called from the player thread, so the request runs on the Tauri runtime
+    fn analyze(&self, request: AIVisionAnalysisRequest) -> Result<AIVisionAnalysisResponse, String> {
+        tauri::async_runtime::block_on(self.call_api(request))
+    }
+}
+
+/// Parse the model's JSON answer, which may be wrapped in a code block
+fn parse_vision_result(text: &str) -> Option<VisionResult> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    serde_json::from_str(&text[start..=end]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vision_result_from_code_block() {
+        let text = "```json\n{\"found\": true, \"x\": 120, \"y\": 48, \"confidence\": 0.92}\n```";
+        let result = parse_vision_result(text).unwrap();
+        assert!(result.found);
+        assert_eq!((result.x, result.y), (Some(120), Some(48)));
+
+        let missing = parse_vision_result("{\"found\": false, \"error\": \"No such button\"}").unwrap();
+        assert!(!missing.found);
+        assert_eq!(missing.error.as_deref(), Some("No such button"));
+        assert!(parse_vision_result("not json").is_none());
+    }
+}
@@ -12,6 +12,7 @@ use crate::application_focused_automation::{
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use rust_automation_core::{named_event_channel, EventSendError, EventSender};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -109,6 +110,13 @@ pub struct NotificationConfig {
     pub enable_system_notifications: bool,
     /// Whether to show in-app notifications
     pub enable_in_app_notifications: bool,
+    /// Maximum queued events before new events are dropped
+    #[serde(default = "default_event_queue_capacity")]
+    pub event_queue_capacity: usize,
+}
+
+fn default_event_queue_capacity() -> usize {
+    256
 }
 
 impl Default for NotificationConfig {
@@ -118,6 +126,7 @@ impl Default for NotificationConfig {
             max_active_notifications: 10,
             enable_system_notifications: true,
             enable_in_app_notifications: true,
+            event_queue_capacity: default_event_queue_capacity(),
         }
     }
 }
@@ -126,15 +135,16 @@ impl Default for NotificationConfig {
 pub struct NotificationService {
     config: NotificationConfig,
     active_notifications: Arc<Mutex<HashMap<String, Notification>>>,
-    event_sender: mpsc::UnboundedSender<NotificationEvent>,
-    event_receiver: Arc<Mutex<Option<mpsc::UnboundedReceiver<NotificationEvent>>>>,
+    /// Bounded queue, reported in the monitor's buffer stats
+    event_sender: EventSender<NotificationEvent>,
+    event_receiver: Arc<Mutex<Option<mpsc::Receiver<NotificationEvent>>>>,
     is_running: Arc<Mutex<bool>>,
 }
 
 impl NotificationService {
     /// Creates a new notification service with the given configuration
     pub fn new(config: NotificationConfig) -> Self {
-        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, event_receiver) = named_event_channel("notification_events", config.event_queue_capacity);
         
         Self {
             config,
@@ -338,7 +348,7 @@ impl NotificationService {
                 // Remove the oldest notification
                 if let Some((oldest_id, _)) = active.iter().min_by_key(|(_, n)| n.created_at).map(|(id, n)| (id.clone(), n.clone())) {
                     active.remove(&oldest_id);
-                    let _ = self.event_sender.send(NotificationEvent::NotificationExpired {
+                    let _ = self.emit_event(NotificationEvent::NotificationExpired {
                         notification_id: oldest_id,
                     });
                 }
@@ -348,7 +358,7 @@ impl NotificationService {
         }
 
         // Send notification displayed event
-        self.event_sender.send(NotificationEvent::NotificationDisplayed {
+        self.emit_event(NotificationEvent::NotificationDisplayed {
             notification_id: notification.id.clone(),
             notification_type: notification.notification_type.clone(),
         }).map_err(|e| PlaybackError::AutomationEngineError(format!("Failed to send notification event: {}", e)))?;
@@ -370,7 +380,7 @@ impl NotificationService {
                 {
                     let mut active = active_notifications.lock().unwrap();
                     if active.remove(&notification_id).is_some() {
-                        let _ = event_sender.send(NotificationEvent::NotificationExpired {
+                        let _ = try_emit(&event_sender, NotificationEvent::NotificationExpired {
                             notification_id: notification_id.clone(),
                         });
                     }
@@ -398,7 +408,7 @@ impl NotificationService {
             .unwrap_or(NotificationActionType::Dismiss);
 
         // Send notification clicked event
-        self.event_sender.send(NotificationEvent::NotificationClicked {
+        self.emit_event(NotificationEvent::NotificationClicked {
             notification_id: notification_id.to_string(),
             action_type: action_type.clone(),
         }).map_err(|e| PlaybackError::AutomationEngineError(format!("Failed to send click event: {}", e)))?;
@@ -419,7 +429,7 @@ impl NotificationService {
         };
 
         if removed {
-            self.event_sender.send(NotificationEvent::NotificationDismissed {
+            self.emit_event(NotificationEvent::NotificationDismissed {
                 notification_id: notification_id.to_string(),
             }).map_err(|e| PlaybackError::AutomationEngineError(format!("Failed to send dismiss event: {}", e)))?;
         }
@@ -454,11 +464,21 @@ impl NotificationService {
     }
 
     /// Gets the event receiver for listening to notification events
-    pub fn take_event_receiver(&self) -> Option<mpsc::UnboundedReceiver<NotificationEvent>> {
+    pub fn take_event_receiver(&self) -> Option<mpsc::Receiver<NotificationEvent>> {
         let mut receiver = self.event_receiver.lock().unwrap();
         receiver.take()
     }
 
+    /// Number of events dropped because the event queue was full
+    pub fn dropped_event_count(&self) -> u64 {
+        self.event_sender.dropped()
+    }
+
+    /// Queues an event without blocking, dropping it if the queue is full
+    fn emit_event(&self, event: NotificationEvent) -> Result<(), String> {
+        try_emit(&self.event_sender, event)
+    }
+
     /// Updates the notification configuration
     pub fn update_config(&mut self, config: NotificationConfig) {
         self.config = config;
@@ -470,6 +490,17 @@ impl NotificationService {
     }
 }
 
+/// Sends an event unless the queue is full, counting dropped events
+///
+/// Only a closed channel is reported as an error; a full queue means nobody
+/// is draining events, which must not block or fail notification handling.
+fn try_emit(sender: &EventSender<NotificationEvent>, event: NotificationEvent) -> Result<(), String> {
+    match sender.send(event) {
+        Ok(()) | Err(EventSendError::Full) => Ok(()),
+        Err(EventSendError::Closed) => Err("event channel closed".to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -630,4 +661,28 @@ mod tests {
         service.clear_all_notifications().await.unwrap();
         assert_eq!(service.get_active_notifications().len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_event_queue_drops_when_full() {
+        let config = NotificationConfig {
+            event_queue_capacity: 2,
+            ..Default::default()
+        };
+        let service = NotificationService::new(config);
+        let app = create_test_app();
+
+        // Nobody drains the receiver, so events beyond capacity are dropped
+        for _ in 0..4 {
+            service.notify_automation_resumed(&app).await.unwrap();
+        }
+
+        assert_eq!(service.dropped_event_count(), 2);
+        let queue = rust_automation_core::buffer_registry()
+            .stats()
+            .into_iter()
+            .find(|stats| stats.name == "notification_events" && stats.dropped == 2);
+        assert!(queue.is_some());
+        let mut receiver = service.take_event_receiver().unwrap();
+        assert!(receiver.try_recv().is_ok());
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::RwLock;
 use std::collections::HashMap;
 
 use crate::python_process::PythonProcessManager;
@@ -9,7 +9,10 @@ use crate::python_process::PythonProcessManager;
 // Import preference types from rust-core
 use rust_automation_core::preferences::{PreferenceManager, UserSettings, CoreType as RustCoreType};
 // Import automation types from rust-core
-use rust_automation_core::{AutomationConfig, ScriptData};
+use rust_automation_core::{AutomationConfig, ScriptData, localized_error};
+use rust_automation_core::{CapabilityMatrix, CaptureMode, SupportLevel};
+use rust_automation_core::health::CoreType as HealthCoreType;
+use rust_automation_core::validation::{CompatibilityIssue, IssueSeverity};
 use rust_automation_core::recorder::Recorder;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -29,6 +32,15 @@ impl From<RustCoreType> for CoreType {
     }
 }
 
+impl From<CoreType> for HealthCoreType {
+    fn from(core_type: CoreType) -> Self {
+        match core_type {
+            CoreType::Python => HealthCoreType::Python,
+            CoreType::Rust => HealthCoreType::Rust,
+        }
+    }
+}
+
 impl From<CoreType> for RustCoreType {
     fn from(core_type: CoreType) -> Self {
         match core_type {
@@ -60,6 +72,9 @@ pub enum AutomationCommand {
         script_path: Option<String>,
         speed: Option<f64>,
         loop_count: Option<i32>,
+        /// Capture an annotated frame after every click (Rust core only)
+        #[serde(default)]
+        annotate_clicks: Option<bool>,
     },
     StopPlayback,
     PausePlayback,
@@ -74,6 +89,7 @@ pub enum AutomationCommand {
 pub struct CoreRouter {
     active_core: Arc<Mutex<CoreType>>,
     python_manager: Arc<PythonProcessManager>,
+    monitor: Option<rust_automation_core::CoreMonitor>,
     // Enhanced error handling and fallback
     startup_health_check_completed: Arc<RwLock<bool>>,
     last_health_check: Arc<RwLock<Option<std::time::Instant>>>,
@@ -84,7 +100,13 @@ pub struct CoreRouter {
     preference_manager: Arc<Mutex<Option<PreferenceManager>>>,
     // Rust automation core components
     rust_recorder: Arc<Mutex<Option<Recorder>>>,
+    // Script path of the Rust recording in progress, chosen up front so click
+    // screenshots can be written next to it while recording
+    rust_recording_path: Arc<Mutex<Option<String>>>,
     rust_player: Arc<Mutex<Option<rust_automation_core::player::Player>>>,
+    // AI Vision Capture playback
+    ai_vision_provider: Option<Arc<dyn rust_automation_core::AIVisionProvider>>,
+    token_budget: Option<rust_automation_core::TokenBudget>,
 }
 
 /// Error record for cross-core error tracking
@@ -147,6 +169,7 @@ impl CoreRouter {
         Self {
             active_core: Arc::new(Mutex::new(CoreType::Rust)), // Default to Rust
             python_manager,
+            monitor: None,
             startup_health_check_completed: Arc::new(RwLock::new(false)),
             last_health_check: Arc::new(RwLock::new(None)),
             failure_count: Arc::new(RwLock::new(HashMap::new())),
@@ -154,10 +177,72 @@ impl CoreRouter {
             performance_metrics: Arc::new(RwLock::new(HashMap::new())),
             preference_manager: Arc::new(Mutex::new(None)),
             rust_recorder: Arc::new(Mutex::new(None)),
+            rust_recording_path: Arc::new(Mutex::new(None)),
             rust_player: Arc::new(Mutex::new(None)),
+            ai_vision_provider: None,
+            token_budget: None,
         }
     }
     
+    /// Report subsystem restarts to the monitor for crash-loop protection
+    pub fn with_monitor(mut self, monitor: rust_automation_core::CoreMonitor) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Let Rust playback run dynamic AI Vision Capture steps with `provider`,
+    /// charged to `budget`
+    pub fn with_ai_vision(
+        mut self,
+        provider: Arc<dyn rust_automation_core::AIVisionProvider>,
+        budget: rust_automation_core::TokenBudget,
+    ) -> Self {
+        self.ai_vision_provider = Some(provider);
+        self.token_budget = Some(budget);
+        self
+    }
+
+    /// Suite runner using the same AI provider and budget as Rust playback
+    pub fn suite_runner(&self) -> rust_automation_core::SuiteRunner {
+        let mut runner = rust_automation_core::SuiteRunner::new(AutomationConfig::default());
+        if let Some(ref provider) = self.ai_vision_provider {
+            runner.set_ai_vision_provider(Arc::clone(provider));
+        }
+        if let Some(ref budget) = self.token_budget {
+            runner.set_token_budget(budget.clone());
+        }
+        runner
+    }
+
+    /// Check whether the Python process may be (re)started
+    ///
+    /// A Python process that exited since the last command counts as a crash.
+    /// Once it crashes too often the circuit breaker refuses further restarts.
+    /// Commands for a process that is still running are always let through,
+    /// so recording and playback can be stopped while the breaker is open.
+    async fn guard_python_restart(&self) -> Result<(), String> {
+        let monitor = match &self.monitor {
+            Some(monitor) => monitor,
+            None => return Ok(()),
+        };
+
+        if let Some(reason) = self.python_manager.reap_exited_process() {
+            eprintln!("[Python] {}", reason);
+            monitor.record_subsystem_restart(rust_automation_core::Subsystem::PythonCore, &reason).await;
+        }
+
+        // Only a command that would spawn a new process is a restart
+        if self.python_manager.is_healthy() {
+            return Ok(());
+        }
+
+        if monitor.can_restart_subsystem(rust_automation_core::Subsystem::PythonCore) {
+            Ok(())
+        } else {
+            Err("Python core is crash looping and automatic restarts are disabled. Reset its circuit breaker after fixing the cause.".to_string())
+        }
+    }
+
     /// Initialize preference manager
     pub fn initialize_preferences(&self) -> Result<(), String> {
         let mut pref_manager = self.preference_manager.lock().unwrap();
@@ -226,7 +311,7 @@ impl CoreRouter {
     }
 
     /// Comprehensive Python core health check
-    async fn check_python_core_health(&self) -> Result<(), String> {
+    pub async fn check_python_core_health(&self) -> Result<(), String> {
         // Check if Python is installed
         let python_cmd = if cfg!(target_os = "windows") { "python" } else { "python3" };
         
@@ -787,9 +872,11 @@ impl CoreRouter {
     pub fn update_user_settings(&self, settings: UserSettings) -> Result<(), String> {
         let mut pref_manager_guard = self.preference_manager.lock().unwrap();
         if let Some(ref mut pref_manager) = *pref_manager_guard {
+            let locale = settings.locale;
             pref_manager.update_user_settings(settings).map_err(|e| {
-                format!("Failed to update user settings: {:?}", e)
+                localized_error("ui-failed-update-settings", &e)
             })?;
+            rust_automation_core::set_preferred_locale(locale);
         }
         Ok(())
     }
@@ -809,7 +896,7 @@ impl CoreRouter {
         let mut pref_manager_guard = self.preference_manager.lock().unwrap();
         if let Some(ref mut pref_manager) = *pref_manager_guard {
             pref_manager.set_playback_speed(speed).map_err(|e| {
-                format!("Failed to set playback speed: {:?}", e)
+                localized_error("ui-failed-update-settings", &e)
             })?;
         }
         Ok(())
@@ -820,7 +907,7 @@ impl CoreRouter {
         let mut pref_manager_guard = self.preference_manager.lock().unwrap();
         if let Some(ref mut pref_manager) = *pref_manager_guard {
             pref_manager.set_loop_count(count).map_err(|e| {
-                format!("Failed to set loop count: {:?}", e)
+                localized_error("ui-failed-update-settings", &e)
             })?;
         }
         Ok(())
@@ -831,7 +918,7 @@ impl CoreRouter {
         let mut pref_manager_guard = self.preference_manager.lock().unwrap();
         if let Some(ref mut pref_manager) = *pref_manager_guard {
             pref_manager.set_selected_script_path(path).map_err(|e| {
-                format!("Failed to set selected script path: {:?}", e)
+                localized_error("ui-failed-update-settings", &e)
             })?;
         }
         Ok(())
@@ -842,7 +929,7 @@ impl CoreRouter {
         let mut pref_manager_guard = self.preference_manager.lock().unwrap();
         if let Some(ref mut pref_manager) = *pref_manager_guard {
             pref_manager.set_show_preview(show_preview).map_err(|e| {
-                format!("Failed to set show preview: {:?}", e)
+                localized_error("ui-failed-update-settings", &e)
             })?;
         }
         Ok(())
@@ -853,9 +940,32 @@ impl CoreRouter {
         let mut pref_manager_guard = self.preference_manager.lock().unwrap();
         if let Some(ref mut pref_manager) = *pref_manager_guard {
             pref_manager.set_preview_opacity(opacity).map_err(|e| {
-                format!("Failed to set preview opacity: {:?}", e)
+                localized_error("ui-failed-update-settings", &e)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Update the resource mode; takes effect on the next start
+    pub fn set_resource_mode(&self, mode: rust_automation_core::ResourceMode) -> Result<(), String> {
+        let mut pref_manager_guard = self.preference_manager.lock().unwrap();
+        if let Some(ref mut pref_manager) = *pref_manager_guard {
+            pref_manager.set_resource_mode(mode).map_err(|e| {
+                localized_error("ui-failed-update-settings", &e)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Update the language errors are shown in; takes effect immediately
+    pub fn set_locale(&self, locale: rust_automation_core::Locale) -> Result<(), String> {
+        let mut pref_manager_guard = self.preference_manager.lock().unwrap();
+        if let Some(ref mut pref_manager) = *pref_manager_guard {
+            pref_manager.set_locale(locale).map_err(|e| {
+                localized_error("ui-failed-set-locale", &e)
             })?;
         }
+        rust_automation_core::set_preferred_locale(locale);
         Ok(())
     }
 
@@ -946,6 +1056,69 @@ impl CoreRouter {
         vec![CoreType::Rust]
     }
 
+    /// Capability matrix for the current platform, marking the cores that can be selected
+    pub fn get_capability_matrix(&self) -> CapabilityMatrix {
+        let available_cores: Vec<HealthCoreType> = self
+            .get_available_cores()
+            .into_iter()
+            .map(HealthCoreType::from)
+            .collect();
+        CapabilityMatrix::current(&available_cores)
+    }
+
+    /// Warn about script steps the active core cannot execute on this platform
+    pub fn check_script_capabilities(&self, script: &ScriptData) -> Vec<CompatibilityIssue> {
+        let active_core = HealthCoreType::from(self.active_core.lock().unwrap().clone());
+        let issues = self.get_capability_matrix().check_script(script, &active_core);
+        
+        for issue in &issues {
+            log::warn!("[Capabilities] {}", issue.message);
+        }
+        
+        issues
+    }
+
+    /// Check a script against the default validator, its signed rule sets and
+    /// the active core's capabilities
+    ///
+    /// Errors block playback; warnings are logged.
+    fn validate_script_for_playback(&self, script: &ScriptData) -> Result<(), String> {
+        let active_core = HealthCoreType::from(self.active_core.lock().unwrap().clone());
+        let result = rust_automation_core::default_validator()
+            .validate_script_for_core(script, &active_core)
+            .map_err(|e| localized_error("ui-failed-validate-script", &e))?;
+
+        for warning in &result.warnings {
+            log::warn!("[Validation] {}", warning);
+        }
+        for issue in &result.issues {
+            log::warn!("[Validation] {}: {}", issue.field, issue.message);
+        }
+
+        if result.is_compatible {
+            Ok(())
+        } else {
+            let errors: Vec<String> = result.issues
+                .iter()
+                .filter(|issue| matches!(issue.severity, IssueSeverity::Error))
+                .map(|issue| format!("{}: {}", issue.field, issue.message))
+                .collect();
+            Err(format!("Script failed validation: {}", errors.join("; ")))
+        }
+    }
+
+    /// Validate the script a playback command will run, whichever core runs it
+    fn validate_playback_command(&self, command: &AutomationCommand) -> Result<(), String> {
+        match command {
+            AutomationCommand::StartPlayback { script_path, .. } => {
+                let path = resolve_playback_script_path(script_path.clone())?;
+                let script = load_playback_script(&path)?;
+                self.validate_script_for_playback(&script)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Get current core status including health information
     pub fn get_core_status(&self) -> CoreStatus {
         let active_core = self.active_core.lock().unwrap().clone();
@@ -968,12 +1141,17 @@ impl CoreRouter {
         app_handle: &AppHandle,
         capture_screenshot_on_click: bool,
     ) -> Result<serde_json::Value, String> {
+        self.validate_playback_command(&command)?;
+
         let active_core = self.active_core.lock().unwrap().clone();
         let operation = format!("{:?}", command);
         let start_time = std::time::Instant::now();
 
         let result = match active_core.clone() {
-            CoreType::Python => self.route_to_python_with_options(command.clone(), app_handle, capture_screenshot_on_click),
+            CoreType::Python => match self.guard_python_restart().await {
+                Ok(()) => self.route_to_python_with_options(command.clone(), app_handle, capture_screenshot_on_click),
+                Err(e) => Err(e),
+            },
             CoreType::Rust => self.route_to_rust_with_options(command.clone(), app_handle, capture_screenshot_on_click),
         };
 
@@ -1025,12 +1203,18 @@ impl CoreRouter {
         command: AutomationCommand,
         app_handle: &AppHandle,
     ) -> Result<serde_json::Value, String> {
+        // An invalid script is not a core failure, so reject it before routing
+        self.validate_playback_command(&command)?;
+
         let active_core = self.active_core.lock().unwrap().clone();
         let operation = format!("{:?}", command);
         let start_time = std::time::Instant::now();
 
         let result = match active_core.clone() {
-            CoreType::Python => self.route_to_python(command.clone(), app_handle),
+            CoreType::Python => match self.guard_python_restart().await {
+                Ok(()) => self.route_to_python(command.clone(), app_handle),
+                Err(e) => Err(e),
+            },
             CoreType::Rust => self.route_to_rust(command.clone(), app_handle),
         };
 
@@ -1091,7 +1275,10 @@ impl CoreRouter {
                         eprintln!("Retrying command with fallback core: {:?}", fallback_core);
                         let retry_start = std::time::Instant::now();
                         let retry_result = match fallback_core.clone() {
-                            CoreType::Python => self.route_to_python(command, app_handle),
+                            CoreType::Python => match self.guard_python_restart().await {
+                                Ok(()) => self.route_to_python(command, app_handle),
+                                Err(e) => Err(e),
+                            },
                             CoreType::Rust => self.route_to_rust(command, app_handle),
                         };
 
@@ -1191,7 +1378,7 @@ impl CoreRouter {
                             *recorder_lock = Some(recorder);
                         }
                         Err(e) => {
-                            return Err(format!("Failed to initialize Rust recorder: {:?}", e));
+                            return Err(localized_error("ui-failed-initialize-recorder", &e));
                         }
                     }
                 } else if let Some(recorder) = recorder_lock.as_mut() {
@@ -1199,7 +1386,7 @@ impl CoreRouter {
                 }
 
                 let app_handle_clone = app_handle.clone();
-                let (event_tx, mut event_rx) = mpsc::unbounded_channel::<rust_automation_core::recorder::RecordingEvent>();
+                let (event_tx, mut event_rx) = rust_automation_core::named_event_channel::<rust_automation_core::recorder::RecordingEvent>("recording_events", rust_automation_core::DEFAULT_EVENT_CAPACITY);
                 
                 tauri::async_runtime::spawn(async move {
                     while let Some(event) = event_rx.recv().await {
@@ -1230,6 +1417,8 @@ impl CoreRouter {
 
                 if let Some(recorder) = recorder_lock.as_mut() {
                     recorder.set_event_sender(event_tx);
+                    let script_path = self.start_rust_recording_path(recorder);
+                    eprintln!("[Rust Recorder] Recording to {}", script_path);
                     
                     match recorder.start_recording() {
                         Ok(_) => {
@@ -1244,7 +1433,7 @@ impl CoreRouter {
                         }
                         Err(e) => {
                             eprintln!("[Rust Recorder] Failed to start recording: {:?}", e);
-                            Err(format!("Failed to start recording: {:?}", e))
+                            Err(localized_error("ui-failed-start-recording", &e))
                         }
                     }
                 } else {
@@ -1255,6 +1444,17 @@ impl CoreRouter {
         }
     }
 
+    /// Choose the script path for a Rust recording about to start
+    ///
+    /// Click screenshots go to its `_screenshots` directory, as the Python
+    /// core stores them.
+    fn start_rust_recording_path(&self, recorder: &mut Recorder) -> String {
+        let script_path = new_recording_path();
+        recorder.set_screenshot_directory(script_path.trim_end_matches(".json").to_string() + "_screenshots");
+        *self.rust_recording_path.lock().unwrap() = Some(script_path.clone());
+        script_path
+    }
+
     /// Route command to Python core
     fn route_to_python(
         &self,
@@ -1273,7 +1473,21 @@ impl CoreRouter {
                 let response = self.python_manager.send_command("stop_recording", serde_json::json!({}), app_handle)?;
                 Ok(response)
             }
-            AutomationCommand::StartPlayback { script_path, speed, loop_count } => {
+            AutomationCommand::StartPlayback { script_path, speed, loop_count, annotate_clicks } => {
+                // The Python player has no annotation option, so refuse rather than run without them
+                if annotate_clicks.unwrap_or(false) {
+                    let support = self
+                        .get_capability_matrix()
+                        .core(&HealthCoreType::Python)
+                        .and_then(|capabilities| capabilities.capture_modes.get(&CaptureMode::ClickAnnotation).cloned());
+                    if let Some(SupportLevel::Unsupported { reason }) = support {
+                        return Err(format!(
+                            "Click annotations are not supported on the Python core ({}). Switch to the Rust core or turn annotations off.",
+                            reason
+                        ));
+                    }
+                }
+
                 let mut params = serde_json::Map::new();
                 if let Some(path) = script_path {
                     params.insert("scriptPath".to_string(), serde_json::Value::String(path));
@@ -1349,14 +1563,14 @@ impl CoreRouter {
                             *recorder_lock = Some(recorder);
                         }
                         Err(e) => {
-                            return Err(format!("Failed to initialize Rust recorder: {:?}", e));
+                            return Err(localized_error("ui-failed-initialize-recorder", &e));
                         }
                     }
                 }
 
                 // Set up event streaming to Tauri for recording events
                 let app_handle_clone = app_handle.clone();
-                let (event_tx, mut event_rx) = mpsc::unbounded_channel::<rust_automation_core::recorder::RecordingEvent>();
+                let (event_tx, mut event_rx) = rust_automation_core::named_event_channel::<rust_automation_core::recorder::RecordingEvent>("recording_events", rust_automation_core::DEFAULT_EVENT_CAPACITY);
                 
                 eprintln!("[Rust Recorder] Event streaming channel created");
                 
@@ -1396,6 +1610,7 @@ impl CoreRouter {
                     // Set event sender for real-time UI updates
                     recorder.set_event_sender(event_tx);
                     eprintln!("[Rust Recorder] Event sender configured");
+                    self.start_rust_recording_path(recorder);
                     
                     match recorder.start_recording() {
                         Ok(_) => {
@@ -1408,7 +1623,7 @@ impl CoreRouter {
                             }))
                         }
                         Err(e) => {
-                            Err(format!("Failed to start recording: {:?}", e))
+                            Err(localized_error("ui-failed-start-recording", &e))
                         }
                     }
                 } else {
@@ -1420,12 +1635,9 @@ impl CoreRouter {
                 if let Some(recorder) = recorder_lock.as_mut() {
                     match recorder.stop_recording() {
                         Ok(script_data) => {
-                            // Save script to file
-                            let script_path = format!(
-                                "{}/GeniusQA/recordings/recording_{}.json",
-                                std::env::var("HOME").unwrap_or_else(|_| ".".to_string()),
-                                chrono::Utc::now().timestamp()
-                            );
+                            // Save script to the path chosen when recording started
+                            let script_path = self.rust_recording_path.lock().unwrap().take()
+                                .unwrap_or_else(new_recording_path);
 
                             // Create directory if it doesn't exist
                             if let Some(parent) = std::path::Path::new(&script_path).parent() {
@@ -1439,130 +1651,67 @@ impl CoreRouter {
                             std::fs::write(&script_path, json_data)
                                 .map_err(|e| format!("Failed to write script file: {}", e))?;
 
+                            // Click screenshots were already written next to the script while recording
+                            let screenshot_count = recorder.screenshot_count();
+
+                            // Score the recording so the UI can suggest cleanups before the user keeps it
+                            let quality = rust_automation_core::RecordingQualityAnalyzer::default()
+                                .analyze(&script_data, Some(std::path::Path::new(&script_path)));
+
                             Ok(serde_json::json!({
                                 "success": true,
                                 "data": {
                                     "scriptPath": script_path,
                                     "actionCount": script_data.actions.len(),
                                     "duration": script_data.metadata.duration,
-                                    "screenshotCount": 0
+                                    "screenshotCount": screenshot_count,
+                                    "quality": quality
                                 }
                             }))
                         }
                         Err(e) => {
-                            Err(format!("Failed to stop recording: {:?}", e))
+                            Err(localized_error("ui-failed-stop-recording", &e))
                         }
                     }
                 } else {
                     Err("No active recording session".to_string())
                 }
             }
-            AutomationCommand::StartPlayback { script_path, speed, loop_count } => {
+            AutomationCommand::StartPlayback { script_path, speed, loop_count, annotate_clicks } => {
                 // Initialize player if not already created
                 let mut player_lock = self.rust_player.lock().unwrap();
                 if player_lock.is_none() {
                     let config = AutomationConfig::default();
                     match rust_automation_core::player::Player::new(config) {
-                        Ok(player) => {
+                        Ok(mut player) => {
+                            if let Some(ref provider) = self.ai_vision_provider {
+                                player.set_ai_vision_provider(Arc::clone(provider));
+                            }
+                            if let Some(ref budget) = self.token_budget {
+                                player.set_token_budget(budget.clone());
+                            }
                             eprintln!("[Rust Player] Player instance created successfully");
                             *player_lock = Some(player);
                         }
                         Err(e) => {
                             eprintln!("[Rust Player] Failed to initialize player: {:?}", e);
-                            return Err(format!("Failed to initialize Rust player. Please check system permissions and ensure your platform is supported. Error: {:?}", e));
+                            return Err(localized_error("ui-failed-initialize-player", &e));
                         }
                     }
                 }
 
-                // Get the script path to load
-                let path_to_load = if let Some(path) = script_path {
-                    path
-                } else {
-                    // Get the latest recording
-                    let recordings_dir = format!(
-                        "{}/GeniusQA/recordings",
-                        std::env::var("HOME").unwrap_or_else(|_| ".".to_string())
-                    );
-                    
-                    std::fs::read_dir(&recordings_dir)
-                        .ok()
-                        .and_then(|entries| {
-                            entries
-                                .filter_map(|e| e.ok())
-                                .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("json"))
-                                .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
-                                .map(|e| e.path().to_string_lossy().to_string())
-                        })
-                        .ok_or_else(|| "No recordings found. Please record a script first before attempting playback.".to_string())?
-                };
+                // Get the script path to load; route_command has already validated it
+                let path_to_load = resolve_playback_script_path(script_path)?;
 
                 eprintln!("[Rust Player] Loading script from: {}", path_to_load);
 
-                // Load the script file
-                let script_content = std::fs::read_to_string(&path_to_load)
-                    .map_err(|e| format!("Failed to read script file '{}'. Please ensure the file exists and is readable. Error: {}", path_to_load, e))?;
-
-                // Preprocess script JSON to support extra action fields (e.g., visual_assert)
-                // Rust core ScriptData::Action only has `additional_data`, so we move known
-                // extra keys into `additional_data` for compatibility.
-                let mut script_json: serde_json::Value = serde_json::from_str(&script_content)
-                    .map_err(|e| format!("Failed to parse script file '{}'. The file may be corrupted or in an invalid format. Error: {}", path_to_load, e))?;
-
-                if let Some(actions) = script_json.get_mut("actions").and_then(|v| v.as_array_mut()) {
-                    for action in actions.iter_mut() {
-                        let action_type = action.get("type").and_then(|v| v.as_str()).unwrap_or("");
-                        if action_type == "visual_assert" {
-                            // Ensure additional_data is an object
-                            let has_additional_data = action.get("additional_data").and_then(|v| v.as_object()).is_some();
-                            if !has_additional_data {
-                                action["additional_data"] = serde_json::Value::Object(serde_json::Map::new());
-                            }
-
-                            // Provide action_id for Rust core (Action struct doesn't have id field)
-                            if let Some(action_id) = action.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()) {
-                                if action.get("additional_data").and_then(|v| v.as_object()).is_some() {
-                                    action["additional_data"]["action_id"] = serde_json::Value::String(action_id);
-                                }
-                            }
-
-                            // Move known keys into additional_data (but keep top-level keys as-is)
-                            let keys = ["config", "regions", "assets", "context"];
-                            for key in keys {
-                                if let Some(value) = action.get(key).cloned() {
-                                    if action.get("additional_data").and_then(|v| v.as_object()).is_some() {
-                                        action["additional_data"][key] = value;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Inject script directory for resolving relative asset paths (e.g. assets/*.png)
-                let script_dir = std::path::Path::new(&path_to_load)
-                    .parent()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "".to_string());
-                if let Some(metadata) = script_json.get_mut("metadata").and_then(|v| v.as_object_mut()) {
-                    // Ensure metadata.additional_data is an object
-                    let has_additional_data = metadata.get("additional_data").and_then(|v| v.as_object()).is_some();
-                    if !has_additional_data {
-                        metadata.insert("additional_data".to_string(), serde_json::Value::Object(serde_json::Map::new()));
-                    }
-
-                    if let Some(additional_data) = metadata.get_mut("additional_data").and_then(|v| v.as_object_mut()) {
-                        additional_data.insert("script_dir".to_string(), serde_json::Value::String(script_dir));
-                    }
-                }
-
-                let script_data: ScriptData = serde_json::from_value(script_json)
-                    .map_err(|e| format!("Failed to deserialize script file '{}'. The file may be corrupted or in an invalid format. Error: {}", path_to_load, e))?;
+                let script_data = load_playback_script(&path_to_load)?;
 
                 eprintln!("[Rust Player] Script loaded successfully: {} actions", script_data.actions.len());
 
                 // Set up event streaming to Tauri
                 let app_handle_clone = app_handle.clone();
-                let (event_tx, mut event_rx) = mpsc::unbounded_channel::<rust_automation_core::player::PlaybackEvent>();
+                let (event_tx, mut event_rx) = rust_automation_core::named_event_channel::<rust_automation_core::player::PlaybackEvent>("playback_events", rust_automation_core::DEFAULT_EVENT_CAPACITY);
                 
                 eprintln!("[Rust Player] Event streaming channel created");
                 
@@ -1582,9 +1731,19 @@ impl CoreRouter {
                     player.set_event_sender(event_tx);
                     eprintln!("[Rust Player] Event sender configured");
                     
+                    // Annotations are chosen per run and stored next to the script
+                    let report_directory = std::path::Path::new(&path_to_load)
+                        .parent()
+                        .map(|dir| dir.join("reports"));
+                    player.set_click_annotations(rust_automation_core::ClickAnnotationConfig {
+                        enabled: annotate_clicks.unwrap_or(false),
+                        output_directory: report_directory,
+                        ..Default::default()
+                    });
+                    
                     // Load the script
                     player.load_script(script_data)
-                        .map_err(|e| format!("Failed to load script into player: {:?}", e))?;
+                        .map_err(|e| localized_error("ui-failed-load-script", &e))?;
                     
                     eprintln!("[Rust Player] Script loaded into player");
                     
@@ -1597,7 +1756,7 @@ impl CoreRouter {
                     player.start_playback(playback_speed, loops)
                         .map_err(|e| {
                             eprintln!("[Rust Player] Failed to start playback: {:?}", e);
-                            format!("Failed to start playback: {:?}", e)
+                            localized_error("ui-failed-start-playback", &e)
                         })?;
                     
                     eprintln!("[Rust Player] Playback started successfully");
@@ -1622,7 +1781,7 @@ impl CoreRouter {
                     player.stop_playback()
                         .map_err(|e| {
                             eprintln!("[Rust Player] Failed to stop playback: {:?}", e);
-                            format!("Failed to stop playback: {:?}", e)
+                            localized_error("ui-failed-stop-playback", &e)
                         })?;
                     
                     eprintln!("[Rust Player] Playback stopped successfully");
@@ -1644,7 +1803,7 @@ impl CoreRouter {
                     let is_paused = player.pause_playback()
                         .map_err(|e| {
                             eprintln!("[Rust Player] Failed to pause/resume playback: {:?}", e);
-                            format!("Failed to pause/resume playback: {:?}", e)
+                            localized_error("ui-failed-pause-playback", &e)
                         })?;
                     
                     eprintln!("[Rust Player] Playback {} successfully", if is_paused { "paused" } else { "resumed" });
@@ -1797,6 +1956,53 @@ impl CoreRouter {
     }
 }
 
+/// A new script path in the recordings directory
+fn new_recording_path() -> String {
+    format!(
+        "{}/GeniusQA/recordings/recording_{}.json",
+        std::env::var("HOME").unwrap_or_else(|_| ".".to_string()),
+        chrono::Utc::now().timestamp()
+    )
+}
+
+/// Script a playback command runs: `script_path`, or the latest recording
+fn resolve_playback_script_path(script_path: Option<String>) -> Result<String, String> {
+    if let Some(path) = script_path {
+        return Ok(path);
+    }
+
+    let recordings_dir = format!(
+        "{}/GeniusQA/recordings",
+        std::env::var("HOME").unwrap_or_else(|_| ".".to_string())
+    );
+
+    std::fs::read_dir(&recordings_dir)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("json"))
+                .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+                .map(|e| e.path().to_string_lossy().to_string())
+        })
+        .ok_or_else(|| "No recordings found. Please record a script first before attempting playback.".to_string())
+}
+
+/// Read a script file and prepare it for playback
+fn load_playback_script(path: &str) -> Result<ScriptData, String> {
+    let script_content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read script file '{}'. Please ensure the file exists and is readable. Error: {}", path, e))?;
+
+    // Move editor-only action fields into additional_data and record the
+    // script's location for asset paths and AI Vision Capture cache updates
+    let mut script_json: serde_json::Value = serde_json::from_str(&script_content)
+        .map_err(|e| format!("Failed to parse script file '{}'. The file may be corrupted or in an invalid format. Error: {}", path, e))?;
+    rust_automation_core::prepare_playback_json(&mut script_json, std::path::Path::new(path));
+
+    serde_json::from_value(script_json)
+        .map_err(|e| format!("Failed to deserialize script file '{}'. The file may be corrupted or in an invalid format. Error: {}", path, e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2083,6 +2289,24 @@ mod tests {
         assert!(is_completed, "Health check should be marked as completed");
     }
 
+    #[tokio::test]
+    async fn test_python_restart_guard_refuses_spawn_once_breaker_is_open() {
+        let mut config = rust_automation_core::MonitoringConfig::default();
+        config.crash_loop.max_restarts = 1;
+        let monitor = rust_automation_core::CoreMonitor::new(config);
+        let router = create_test_router().with_monitor(monitor.clone());
+
+        // No process is running yet, so the next command would spawn one
+        assert!(router.guard_python_restart().await.is_ok());
+
+        monitor.record_subsystem_restart(rust_automation_core::Subsystem::PythonCore, "exit code 1").await;
+        monitor.record_subsystem_restart(rust_automation_core::Subsystem::PythonCore, "exit code 1").await;
+        assert!(router.guard_python_restart().await.is_err());
+
+        monitor.reset_circuit_breaker(rust_automation_core::Subsystem::PythonCore).await;
+        assert!(router.guard_python_restart().await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_failure_count_tracking() {
         let router = create_test_router();
@@ -6,6 +6,7 @@
 pub mod ai_test_case;
 pub mod application_focused_automation;
 pub mod core_router;
+pub mod onboarding;
 pub mod python_process;
 
 // Re-export commonly used types for convenience
@@ -4,6 +4,7 @@
 mod ai_test_case;
 mod application_focused_automation;
 mod core_router;
+mod onboarding;
 mod python_process;
 
 use core_router::{AutomationCommand, CoreRouter, CoreStatus, CoreType, PerformanceComparison};
@@ -35,6 +36,11 @@ struct MonitorState {
     monitor: rust_automation_core::CoreMonitor,
 }
 
+// Control API token store wrapper
+struct ControlApiState {
+    tokens: rust_automation_core::ApiTokenStore,
+}
+
 // Application-focused automation state wrapper
 struct ApplicationFocusedAutomationState {
     service: Arc<ApplicationFocusedAutomationService>,
@@ -50,6 +56,7 @@ struct RecordingResult {
     duration: Option<f64>,
     #[serde(rename = "screenshotCount")]
     screenshot_count: Option<i32>,
+    quality: Option<rust_automation_core::RecordingQualityReport>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -114,6 +121,26 @@ async fn get_core_status(
     Ok(core_router.router.get_core_status())
 }
 
+#[tauri::command]
+async fn get_capability_matrix(
+    core_router: State<'_, CoreRouterState>,
+) -> Result<rust_automation_core::CapabilityMatrix, String> {
+    Ok(core_router.router.get_capability_matrix())
+}
+
+#[tauri::command]
+async fn check_script_capabilities(
+    core_router: State<'_, CoreRouterState>,
+    script_path: String,
+) -> Result<Vec<rust_automation_core::validation::CompatibilityIssue>, String> {
+    let content = std::fs::read_to_string(&script_path)
+        .map_err(|e| format!("Failed to read script file: {}", e))?;
+    let script: rust_automation_core::ScriptData = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse script file: {}", e))?;
+    
+    Ok(core_router.router.check_script_capabilities(&script))
+}
+
 #[tauri::command]
 async fn get_core_performance_metrics(
     core_router: State<'_, CoreRouterState>,
@@ -201,6 +228,36 @@ async fn set_preview_opacity(
     core_router.router.set_preview_opacity(opacity)
 }
 
+/// Get the resource profile in effect for this session
+#[tauri::command]
+async fn get_resource_profile() -> Result<rust_automation_core::ResourceProfile, String> {
+    Ok(rust_automation_core::resource_profile().clone())
+}
+
+/// Persist the resource mode; it is applied on the next start
+#[tauri::command]
+async fn set_resource_mode(
+    core_router: State<'_, CoreRouterState>,
+    mode: rust_automation_core::ResourceMode,
+) -> Result<(), String> {
+    core_router.router.set_resource_mode(mode)
+}
+
+/// Get the language errors are shown in
+#[tauri::command]
+async fn get_locale() -> Result<rust_automation_core::Locale, String> {
+    Ok(rust_automation_core::preferred_locale())
+}
+
+/// Persist the language errors are shown in; it applies immediately
+#[tauri::command]
+async fn set_locale(
+    core_router: State<'_, CoreRouterState>,
+    locale: rust_automation_core::Locale,
+) -> Result<(), String> {
+    core_router.router.set_locale(locale)
+}
+
 // Automation commands (now routed through CoreRouter)
 #[tauri::command]
 async fn start_recording(
@@ -275,11 +332,25 @@ async fn stop_recording(
             if resp.get("success").and_then(|s| s.as_bool()) == Some(true) {
                 let data = resp.get("data").ok_or("Missing data in response")?;
                 
+                let script_path = data.get("scriptPath").and_then(|s| s.as_str()).map(String::from);
+                // The Python core does not score recordings, so score its saved file here
+                let quality = data
+                    .get("quality")
+                    .and_then(|q| serde_json::from_value(q.clone()).ok())
+                    .or_else(|| {
+                        script_path.as_ref().and_then(|path| {
+                            rust_automation_core::RecordingQualityAnalyzer::default()
+                                .analyze_file(path)
+                                .ok()
+                        })
+                    });
+
                 let result = RecordingResult {
-                    script_path: data.get("scriptPath").and_then(|s| s.as_str()).map(String::from),
+                    script_path,
                     action_count: data.get("actionCount").and_then(|n| n.as_i64()).map(|n| n as i32),
                     duration: data.get("duration").and_then(|n| n.as_f64()),
                     screenshot_count: data.get("screenshotCount").and_then(|n| n.as_i64()).map(|n| n as i32),
+                    quality,
                 };
                 
                 Ok(result)
@@ -302,6 +373,7 @@ async fn start_playback(
     script_path: Option<String>,
     speed: Option<f64>,
     loop_count: Option<i32>,
+    annotate_clicks: Option<bool>,
 ) -> Result<(), String> {
     let start_time = std::time::Instant::now();
     let active_core = core_router.router.get_core_status().active_core;
@@ -311,6 +383,7 @@ async fn start_playback(
             script_path,
             speed,
             loop_count,
+            annotate_clicks,
         },
         &app_handle,
     ).await;
@@ -706,6 +779,45 @@ async fn get_monitoring_stats(
     Ok(monitor.monitor.get_monitoring_stats().await)
 }
 
+// Control API token commands
+#[tauri::command]
+async fn issue_api_token(
+    control_api: State<'_, ControlApiState>,
+    name: String,
+    scopes: Vec<rust_automation_core::ApiScope>,
+    ttl_days: Option<i64>,
+) -> Result<serde_json::Value, String> {
+    let ttl = ttl_days.map(chrono::Duration::days);
+    let (token, secret) = control_api.tokens.issue(&name, scopes, ttl)
+        .map_err(|e| e.user_message())?;
+    
+    // The secret is shown once and never stored
+    Ok(serde_json::json!({ "token": token, "secret": secret }))
+}
+
+#[tauri::command]
+async fn revoke_api_token(
+    control_api: State<'_, ControlApiState>,
+    token_id: String,
+) -> Result<bool, String> {
+    control_api.tokens.revoke(&token_id).map_err(|e| e.user_message())
+}
+
+#[tauri::command]
+async fn list_api_tokens(
+    control_api: State<'_, ControlApiState>,
+) -> Result<Vec<rust_automation_core::ApiToken>, String> {
+    Ok(control_api.tokens.list())
+}
+
+#[tauri::command]
+async fn get_control_audit_log(
+    control_api: State<'_, ControlApiState>,
+    limit: Option<usize>,
+) -> Result<Vec<rust_automation_core::AuditRecord>, String> {
+    Ok(control_api.tokens.audit_log(limit))
+}
+
 // ============================================================================
 // Application-Focused Automation Commands
 // Requirements: 1.1, 1.2, 2.1, 2.2, 4.1, 5.5
@@ -1403,8 +1515,27 @@ async fn get_service_stats(
 #[tauri::command]
 async fn restart_service(
     service_state: State<'_, ApplicationFocusedAutomationState>,
+    monitor: State<'_, MonitorState>,
 ) -> Result<(), String> {
     log::info!("[App Focus] Restarting Application-Focused Automation service");
+
+    // Only restarts after the service failed count towards the crash-loop breaker;
+    // restarting a healthy service is a user action, not a crash
+    let crash_reason = match service_state.service.get_stats() {
+        Ok(stats) => match stats.state {
+            ServiceState::Error(error) => Some(error),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+    if let Some(reason) = crash_reason {
+        let decision = monitor.monitor
+            .record_subsystem_restart(rust_automation_core::Subsystem::FocusService, &reason)
+            .await;
+        if !decision.allowed {
+            return Err("Focus service is crash looping and automatic restarts are disabled. Reset its circuit breaker after fixing the cause.".to_string());
+        }
+    }
     
     // Stop the service
     service_state.service.stop().await.map_err(|e| format!("Failed to stop service: {}", e))?;
@@ -1416,6 +1547,25 @@ async fn restart_service(
     Ok(())
 }
 
+/// Get circuit breaker status for restartable subsystems
+#[tauri::command]
+async fn get_circuit_breakers(
+    monitor: State<'_, MonitorState>,
+) -> Result<Vec<rust_automation_core::CircuitBreakerStatus>, String> {
+    Ok(monitor.monitor.get_circuit_breaker_status())
+}
+
+/// Manually reset a subsystem's circuit breaker so it may restart again
+#[tauri::command]
+async fn reset_circuit_breaker(
+    subsystem: rust_automation_core::Subsystem,
+    monitor: State<'_, MonitorState>,
+) -> Result<(), String> {
+    log::info!("[Monitoring] Resetting circuit breaker for {}", subsystem);
+    monitor.monitor.reset_circuit_breaker(subsystem).await;
+    Ok(())
+}
+
 /// Update application focus configuration
 /// 
 /// Requirements: Configuration management for onboarding
@@ -1707,13 +1857,10 @@ async fn update_vision_cache(
 /// Requirements: 4.4, 4.5
 #[tauri::command]
 async fn get_screen_dimensions() -> Result<[i32; 2], String> {
-    // Use platform automation to get screen dimensions
-    use rust_automation_core::platform::create_platform_automation;
+    // Dimensions are cached until the display configuration changes
+    use rust_automation_core::platform::platform_cache;
     
-    let platform = create_platform_automation()
-        .map_err(|e| format!("Failed to create platform automation: {}", e))?;
-    
-    match platform.get_screen_size() {
+    match platform_cache().screen_size() {
         Ok((width, height)) => {
             log::info!("[AI Vision] Screen dimensions: {}x{}", width, height);
             Ok([width as i32, height as i32])
@@ -1725,6 +1872,25 @@ async fn get_screen_dimensions() -> Result<[i32; 2], String> {
     }
 }
 
+/// Get the current display layout
+/// 
+/// Returns every connected display with its origin, size and scale factor.
+#[tauri::command]
+async fn get_display_layout() -> Result<Vec<rust_automation_core::DisplayInfo>, String> {
+    use rust_automation_core::platform::shared_platform;
+    
+    shared_platform()
+        .and_then(|platform| platform.get_displays())
+        .map_err(|e| rust_automation_core::localized_error("ui-failed-display-layout", &e))
+}
+
+/// Load the annotated click screenshots stored with a run report
+#[tauri::command]
+async fn get_click_annotations(report_directory: String) -> Result<rust_automation_core::ClickAnnotationReport, String> {
+    rust_automation_core::load_annotation_report(Path::new(&report_directory))
+        .map_err(|e| format!("Failed to load click annotations: {}", e))
+}
+
 /// Capture current screen screenshot
 /// 
 /// Captures a screenshot of the current screen and returns it as base64.
@@ -1732,12 +1898,12 @@ async fn get_screen_dimensions() -> Result<[i32; 2], String> {
 /// Requirements: 4.6
 #[tauri::command]
 async fn capture_screenshot() -> Result<String, String> {
-    // Use platform automation to capture screenshot
-    use rust_automation_core::platform::create_platform_automation;
+    // Use the shared platform handle to capture screenshot
+    use rust_automation_core::platform::shared_platform;
     use base64::{Engine as _, engine::general_purpose::STANDARD};
     
-    let platform = create_platform_automation()
-        .map_err(|e| format!("Failed to create platform automation: {}", e))?;
+    let platform = shared_platform()
+        .map_err(|e| rust_automation_core::localized_error("ui-failed-create-platform", &e))?;
     
     match platform.take_screenshot() {
         Ok(screenshot_data) => {
@@ -1992,6 +2158,100 @@ async fn get_platform_info() -> Result<String, String> {
     Ok(std::env::consts::OS.to_string())
 }
 
+// Environment gate commands
+#[tauri::command]
+async fn check_environment_gates(
+    gates: Vec<rust_automation_core::EnvironmentGate>,
+) -> Result<rust_automation_core::EnvironmentGateReport, String> {
+    tokio::task::spawn_blocking(move || rust_automation_core::EnvironmentGateChecker::check(&gates))
+        .await
+        .map_err(|e| format!("Environment gate check failed: {}", e))
+}
+
+/// Check a suite's environment gates before running it
+///
+/// Returns a skipped suite result (with the reason) when any gate fails,
+/// or `None` when the suite can run.
+#[tauri::command]
+async fn preflight_suite(
+    suite: rust_automation_core::SuiteDefinition,
+) -> Result<Option<rust_automation_core::SuiteRunResult>, String> {
+    let result = tokio::task::spawn_blocking(move || suite.preflight())
+        .await
+        .map_err(|e| format!("Suite preflight failed: {}", e))?;
+
+    if let Some(ref skipped) = result {
+        log::info!(
+            "[Suite] Skipping suite '{}': {}",
+            skipped.suite_name,
+            skipped.skip_reason.clone().unwrap_or_default()
+        );
+    }
+
+    Ok(result)
+}
+
+/// Run a suite file on the Rust core
+///
+/// Script paths are resolved against the suite file's directory. A suite
+/// whose environment gates fail comes back skipped without playing anything.
+#[tauri::command]
+async fn run_suite(
+    core_router: State<'_, CoreRouterState>,
+    suite_path: String,
+) -> Result<rust_automation_core::SuiteRunResult, String> {
+    let runner = core_router.router.suite_runner();
+    let result = tokio::task::spawn_blocking(move || runner.run_file(&suite_path))
+        .await
+        .map_err(|e| format!("Suite run failed: {}", e))?
+        .map_err(|e| e.user_message())?;
+
+    log::info!("[Suite] Suite '{}' finished: {:?}", result.suite_name, result.status);
+    Ok(result)
+}
+
+// Onboarding commands
+
+/// Report what a new installation still needs, step by step
+///
+/// When `create_script_directory` is true the default script directory is
+/// created as part of the check.
+#[tauri::command]
+async fn get_onboarding_status(
+    create_script_directory: Option<bool>,
+    core_router: State<'_, CoreRouterState>,
+    ai_state: State<'_, ai_test_case::AIServiceState>,
+) -> Result<onboarding::OnboardingReport, String> {
+    let mut steps = tokio::task::spawn_blocking(onboarding::check_current_permissions)
+        .await
+        .map_err(|e| format!("Failed to collect environment snapshot: {}", e))?;
+
+    let has_api_key = ai_state.config_manager.read().await.retrieve_api_key().ok().map(|key| key.is_some());
+
+    steps.push(onboarding::check_python_core(core_router.router.check_python_core_health().await));
+    steps.push(onboarding::check_ai_api_key(has_api_key));
+    steps.push(onboarding::check_script_directory(
+        &onboarding::default_script_directory(),
+        create_script_directory.unwrap_or(false),
+    ));
+
+    let report = onboarding::OnboardingReport::new(steps);
+    log::info!(
+        "[Onboarding] {} of {} steps satisfied",
+        report.steps.iter().filter(|step| step.is_satisfied()).count(),
+        report.steps.len()
+    );
+    Ok(report)
+}
+
+/// Create the default script directory
+#[tauri::command]
+async fn create_script_directory() -> Result<onboarding::OnboardingStep, String> {
+    let path = onboarding::default_script_directory();
+    onboarding::ensure_script_directory(&path)?;
+    Ok(onboarding::check_script_directory(&path, false))
+}
+
 #[cfg(target_os = "macos")]
 #[tauri::command]
 async fn check_accessibility_permissions() -> Result<bool, String> {
@@ -2036,17 +2296,53 @@ async fn request_accessibility_permissions() -> Result<(), String> {
 }
 
 fn main() {
+    // Select the resource profile and language before any subsystem reads them
+    let (resource_mode, locale) = rust_automation_core::PreferenceManager::with_default_path()
+        .map(|preferences| {
+            let preferences = preferences.get_preferences();
+            (preferences.resource_mode, preferences.user_settings.locale)
+        })
+        .unwrap_or_default();
+    rust_automation_core::set_preferred_locale(locale);
+    let resource_profile = rust_automation_core::init_resource_profile(resource_mode);
+
     // Initialize logging system
     if let Err(e) = init_logging() {
         eprintln!("Failed to initialize logging: {}", e);
     }
+    if resource_profile.low_resource {
+        log::warn!("Low-resource mode enabled: {}", resource_profile.reason);
+    }
 
     // Initialize monitoring system
-    let monitoring_config = rust_automation_core::MonitoringConfig::default();
+    let mut monitoring_config = rust_automation_core::MonitoringConfig::default();
+    resource_profile.apply_to_monitoring(&mut monitoring_config);
     let core_monitor = rust_automation_core::CoreMonitor::new(monitoring_config);
 
+    // One AI budget for test case generation and AI vision playback
+    let budget_path = rust_automation_core::TokenBudget::default_usage_path();
+    let token_budget = rust_automation_core::TokenBudget::open_with_saved_config(
+        rust_automation_core::TokenBudgetConfig::default(),
+        &budget_path,
+        rust_automation_core::TokenBudget::default_config_path(),
+    )
+        .unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to load AI budget from {}: {}", budget_path.display(), e);
+            rust_automation_core::TokenBudget::default()
+        });
+    let budget_monitor = core_monitor.clone();
+    token_budget.set_alert_handler(move |alert| {
+        let monitor = budget_monitor.clone();
+        let alert = alert.clone();
+        tauri::async_runtime::spawn(async move {
+            monitor.raise_budget_alert(&alert).await;
+        });
+    });
+
     let python_manager = Arc::new(PythonProcessManager::new());
-    let core_router = CoreRouter::new(python_manager);
+    let core_router = CoreRouter::new(python_manager)
+        .with_monitor(core_monitor.clone())
+        .with_ai_vision(Arc::new(ai_test_case::GeminiVisionProvider::new()), token_budget.clone());
     
     // Initialize preferences
     if let Err(e) = core_router.initialize_preferences() {
@@ -2057,7 +2353,7 @@ fn main() {
     let monitor_state = MonitorState { monitor: core_monitor.clone() };
 
     // Initialize AI Test Case service
-    let ai_service_state = match ai_test_case::AIServiceState::new() {
+    let ai_service_state = match ai_test_case::AIServiceState::with_monitor(Some(core_monitor.clone()), token_budget.clone()) {
         Ok(state) => state,
         Err(e) => {
             eprintln!("Warning: Failed to initialize AI Test Case service: {}", e);
@@ -2082,9 +2378,18 @@ fn main() {
         service: Arc::new(application_focused_automation_service),
     };
 
+    let token_path = rust_automation_core::ApiTokenStore::default_path();
+    let control_api_state = ControlApiState {
+        tokens: rust_automation_core::ApiTokenStore::open(&token_path).unwrap_or_else(|e| {
+            eprintln!("Warning: Failed to load API tokens from {}: {}", token_path.display(), e);
+            rust_automation_core::ApiTokenStore::in_memory()
+        }),
+    };
+
     tauri::Builder::default()
         .manage(core_router_state)
         .manage(monitor_state)
+        .manage(control_api_state)
         .manage(ai_service_state)
         .manage(application_focused_automation_state)
         .setup(move |app| {
@@ -2097,6 +2402,27 @@ fn main() {
                 }
             });
             
+            // Watch for display changes and forward them to the UI
+            rust_automation_core::DisplayMonitor::default().start();
+            let display_app_handle = app_handle.clone();
+            let mut core_events = rust_automation_core::event_bus().subscribe();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    match core_events.recv().await {
+                        Ok(event) => {
+                            if let Some(change) = event.as_display_change() {
+                                log::info!("Display configuration changed: {}", change.description());
+                                let _ = display_app_handle.emit_all("display_configuration_changed", change);
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                            log::warn!("Missed {} core events", missed);
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+            
             // Start Application-Focused Automation service
             let app_handle_clone = app_handle.clone();
             tauri::async_runtime::spawn(async move {
@@ -2122,6 +2448,8 @@ fn main() {
             select_core,
             get_available_cores,
             get_core_status,
+            get_capability_matrix,
+            check_script_capabilities,
             get_core_performance_metrics,
             get_performance_comparison,
             // Settings management commands
@@ -2132,12 +2460,23 @@ fn main() {
             set_selected_script_path,
             set_show_preview,
             set_preview_opacity,
+            get_resource_profile,
+            set_resource_mode,
+            get_locale,
+            set_locale,
             // Monitoring commands
             get_health_status,
             get_active_alerts,
             get_alert_history,
             resolve_alert,
             get_monitoring_stats,
+            // Control API token commands
+            issue_api_token,
+            revoke_api_token,
+            list_api_tokens,
+            get_control_audit_log,
+            get_circuit_breakers,
+            reset_circuit_breaker,
             // Automation commands (routed through CoreRouter)
             start_recording,
             stop_recording,
@@ -2189,6 +2528,8 @@ fn main() {
             analyze_vision,
             update_vision_cache,
             get_screen_dimensions,
+            get_display_layout,
+            get_click_annotations,
             capture_screenshot,
             // Asset management commands
             save_asset,
@@ -2214,10 +2555,19 @@ fn main() {
             ai_test_case::commands::get_usage_patterns,
             ai_test_case::commands::calculate_cost_estimation,
             ai_test_case::commands::get_recent_errors,
+            ai_test_case::commands::get_ai_budget_status,
+            ai_test_case::commands::update_ai_budget_config,
             // Platform and permission commands
             get_platform_info,
             check_accessibility_permissions,
             request_accessibility_permissions,
+            // Environment gate commands
+            check_environment_gates,
+            preflight_suite,
+            run_suite,
+            // Onboarding commands
+            get_onboarding_status,
+            create_script_directory,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -2225,11 +2575,14 @@ fn main() {
 
 /// Initialize the logging system
 fn init_logging() -> Result<(), Box<dyn std::error::Error>> {
+    let resource_profile = rust_automation_core::resource_profile();
+    let default_filter = if resource_profile.low_resource { "warn" } else { "info" };
+
     // Initialize tracing for Tauri backend (only once)
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"))
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(default_filter))
         )
         .with_target(true)
         .with_thread_ids(true)
@@ -2239,7 +2592,8 @@ fn init_logging() -> Result<(), Box<dyn std::error::Error>> {
         .ok(); // Ignore error if already initialized
 
     // Initialize Rust core logging (without tracing subscriber)
-    let logging_config = rust_automation_core::LoggingConfig::default();
+    let mut logging_config = rust_automation_core::LoggingConfig::default();
+    resource_profile.apply_to_logging(&mut logging_config);
     rust_automation_core::init_logger(logging_config)?;
 
     log::info!("Logging system initialized successfully");
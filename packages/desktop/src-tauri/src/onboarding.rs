@@ -0,0 +1,312 @@
+//! First-run onboarding checks
+//!
+//! Reports, step by step, what a new installation still needs before it can
+//! record and play back scripts. Each step carries a machine-readable status
+//! and, when the user has to do something, the command the UI wizard should
+//! offer to resolve it.
+
+use rust_automation_core::{EnvironmentSnapshot, RequiredPermission};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Identifier of an onboarding step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStepId {
+    InputControlPermission,
+    ScreenCapturePermission,
+    PythonCore,
+    AiApiKey,
+    ScriptDirectory,
+}
+
+/// Machine-readable status of an onboarding step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStepStatus {
+    /// Nothing left to do
+    Complete,
+    /// The user must act before the app is usable
+    ActionRequired,
+    /// Not needed for core functionality; the user may set it up later
+    Optional,
+    /// The check itself could not be completed
+    Failed,
+}
+
+/// Result of a single onboarding step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStep {
+    pub id: OnboardingStepId,
+    pub status: OnboardingStepStatus,
+    /// Whether the step blocks onboarding completion
+    pub required: bool,
+    pub message: String,
+    /// Tauri command the wizard can invoke to resolve the step
+    pub resolve_command: Option<String>,
+}
+
+/// Consolidated onboarding status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingReport {
+    /// True when every required step is complete
+    pub complete: bool,
+    pub steps: Vec<OnboardingStep>,
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl OnboardingStep {
+    fn new(id: OnboardingStepId, status: OnboardingStepStatus, required: bool, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            status,
+            required,
+            message: message.into(),
+            resolve_command: None,
+        }
+    }
+
+    fn with_resolve_command(mut self, command: &str) -> Self {
+        self.resolve_command = Some(command.to_string());
+        self
+    }
+
+    /// Whether the step is satisfied for the purpose of completing onboarding
+    pub fn is_satisfied(&self) -> bool {
+        !self.required || self.status == OnboardingStepStatus::Complete
+    }
+}
+
+impl OnboardingReport {
+    /// Build a report from evaluated steps
+    pub fn new(steps: Vec<OnboardingStep>) -> Self {
+        Self {
+            complete: steps.iter().all(OnboardingStep::is_satisfied),
+            steps,
+            checked_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Look up a step by id
+    pub fn step(&self, id: OnboardingStepId) -> Option<&OnboardingStep> {
+        self.steps.iter().find(|step| step.id == id)
+    }
+}
+
+/// Default directory recordings are saved to
+pub fn default_script_directory() -> PathBuf {
+    PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".to_string()))
+        .join("GeniusQA")
+        .join("recordings")
+}
+
+/// Create the default script directory if it does not exist yet
+pub fn ensure_script_directory(path: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(path)
+        .map_err(|e| format!("Failed to create script directory {}: {}", path.display(), e))
+}
+
+/// Evaluate the permission steps against an environment snapshot
+pub fn check_permissions(snapshot: &EnvironmentSnapshot) -> Vec<OnboardingStep> {
+    let input_control = if snapshot.granted_permissions.contains(&RequiredPermission::InputControl) {
+        OnboardingStep::new(
+            OnboardingStepId::InputControlPermission,
+            OnboardingStepStatus::Complete,
+            true,
+            "Mouse and keyboard control is permitted",
+        )
+    } else {
+        OnboardingStep::new(
+            OnboardingStepId::InputControlPermission,
+            OnboardingStepStatus::ActionRequired,
+            true,
+            "Grant accessibility permission so GeniusQA can control the mouse and keyboard",
+        )
+        .with_resolve_command("request_accessibility_permissions")
+    };
+
+    let screen_capture = if snapshot.granted_permissions.contains(&RequiredPermission::ScreenCapture) {
+        OnboardingStep::new(
+            OnboardingStepId::ScreenCapturePermission,
+            OnboardingStepStatus::Complete,
+            true,
+            "Screen capture is permitted",
+        )
+    } else {
+        OnboardingStep::new(
+            OnboardingStepId::ScreenCapturePermission,
+            OnboardingStepStatus::ActionRequired,
+            true,
+            "Grant screen recording permission so GeniusQA can capture screenshots",
+        )
+        .with_resolve_command("open_system_settings")
+    };
+
+    vec![input_control, screen_capture]
+}
+
+/// Evaluate the permission steps against the live environment
+pub fn check_current_permissions() -> Vec<OnboardingStep> {
+    check_permissions(&EnvironmentSnapshot::collect())
+}
+
+/// Evaluate the Python core step from the result of its health check
+///
+/// The Rust core is the default, so a missing Python core does not block onboarding.
+pub fn check_python_core(health: Result<(), String>) -> OnboardingStep {
+    match health {
+        Ok(()) => OnboardingStep::new(
+            OnboardingStepId::PythonCore,
+            OnboardingStepStatus::Complete,
+            false,
+            "Python core is available",
+        ),
+        Err(reason) => OnboardingStep::new(
+            OnboardingStepId::PythonCore,
+            OnboardingStepStatus::Optional,
+            false,
+            format!("Python core is not available: {}", reason),
+        ),
+    }
+}
+
+/// Evaluate the AI API key step
+pub fn check_ai_api_key(has_api_key: Option<bool>) -> OnboardingStep {
+    match has_api_key {
+        Some(true) => OnboardingStep::new(
+            OnboardingStepId::AiApiKey,
+            OnboardingStepStatus::Complete,
+            false,
+            "AI API key is configured",
+        ),
+        Some(false) => OnboardingStep::new(
+            OnboardingStepId::AiApiKey,
+            OnboardingStepStatus::Optional,
+            false,
+            "Add a Gemini API key to enable AI test case generation and AI vision",
+        )
+        .with_resolve_command("configure_api_key"),
+        None => OnboardingStep::new(
+            OnboardingStepId::AiApiKey,
+            OnboardingStepStatus::Failed,
+            false,
+            "Could not access the system keyring to check for an AI API key",
+        ),
+    }
+}
+
+/// Evaluate the script directory step, creating the directory when requested
+pub fn check_script_directory(path: &Path, create_if_missing: bool) -> OnboardingStep {
+    if !path.is_dir() && create_if_missing {
+        if let Err(e) = ensure_script_directory(path) {
+            return OnboardingStep::new(OnboardingStepId::ScriptDirectory, OnboardingStepStatus::Failed, true, e);
+        }
+        log::info!("[Onboarding] Created script directory {}", path.display());
+    }
+
+    if path.is_dir() {
+        OnboardingStep::new(
+            OnboardingStepId::ScriptDirectory,
+            OnboardingStepStatus::Complete,
+            true,
+            format!("Scripts are saved to {}", path.display()),
+        )
+    } else {
+        OnboardingStep::new(
+            OnboardingStepId::ScriptDirectory,
+            OnboardingStepStatus::ActionRequired,
+            true,
+            format!("Script directory {} does not exist yet", path.display()),
+        )
+        .with_resolve_command("create_script_directory")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(granted_permissions: Vec<RequiredPermission>) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            os: "macos".to_string(),
+            os_version: None,
+            screen_resolution: Some((1920, 1080)),
+            granted_permissions,
+            captured_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_optional_steps_do_not_block_completion() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut steps = check_permissions(&snapshot(vec![
+            RequiredPermission::InputControl,
+            RequiredPermission::ScreenCapture,
+        ]));
+        steps.push(check_python_core(Err("python3 not found".to_string())));
+        steps.push(check_ai_api_key(Some(false)));
+        steps.push(check_script_directory(dir.path(), false));
+
+        let report = OnboardingReport::new(steps);
+        assert!(report.complete);
+        assert_eq!(report.step(OnboardingStepId::PythonCore).unwrap().status, OnboardingStepStatus::Optional);
+        assert_eq!(
+            report.step(OnboardingStepId::AiApiKey).unwrap().resolve_command.as_deref(),
+            Some("configure_api_key")
+        );
+    }
+
+    #[test]
+    fn test_missing_permission_requires_action() {
+        let report = OnboardingReport::new(check_permissions(&snapshot(vec![RequiredPermission::ScreenCapture])));
+        let step = report.step(OnboardingStepId::InputControlPermission).unwrap();
+
+        assert!(!report.complete);
+        assert_eq!(step.status, OnboardingStepStatus::ActionRequired);
+        assert_eq!(step.resolve_command.as_deref(), Some("request_accessibility_permissions"));
+    }
+
+    #[test]
+    fn test_script_directory_creation() {
+        let dir = tempfile::tempdir().unwrap();
+        let scripts = dir.path().join("GeniusQA").join("recordings");
+
+        assert_eq!(check_script_directory(&scripts, false).status, OnboardingStepStatus::ActionRequired);
+        assert_eq!(check_script_directory(&scripts, true).status, OnboardingStepStatus::Complete);
+        assert!(scripts.is_dir());
+    }
+
+    #[test]
+    fn test_step_serialization_is_machine_readable() {
+        let json = serde_json::to_value(check_ai_api_key(None)).unwrap();
+        assert_eq!(json["id"], "ai_api_key");
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["resolveCommand"], serde_json::Value::Null);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn test_screen_capture_step_follows_macos_permission() {
+        #[link(name = "CoreGraphics", kind = "framework")]
+        extern "C" {
+            fn CGPreflightScreenCaptureAccess() -> bool;
+        }
+        let granted = unsafe { CGPreflightScreenCaptureAccess() };
+
+        let steps = check_current_permissions();
+        let step = steps
+            .iter()
+            .find(|step| step.id == OnboardingStepId::ScreenCapturePermission)
+            .unwrap();
+
+        // Whatever this machine has granted, the step must agree with the OS
+        let expected = if granted {
+            OnboardingStepStatus::Complete
+        } else {
+            OnboardingStepStatus::ActionRequired
+        };
+        assert_eq!(step.status, expected);
+    }
+}
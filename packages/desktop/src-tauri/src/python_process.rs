@@ -171,6 +171,27 @@ impl PythonProcessManager {
         }
     }
 
+    /// Detect a Python process that has exited on its own
+    ///
+    /// Clears the dead process so the next `ensure_process_running` respawns it
+    /// and returns the exit reason.
+    pub fn reap_exited_process(&self) -> Option<String> {
+        let mut process_guard = self.process.lock().unwrap();
+        let exit_status = match process_guard.as_mut() {
+            Some(process) => process._child.try_wait(),
+            None => return None,
+        };
+
+        let reason = match exit_status {
+            Ok(Some(status)) => format!("Python process exited with {}", status),
+            Ok(None) => return None,
+            Err(e) => format!("Failed to query Python process status: {}", e),
+        };
+
+        *process_guard = None;
+        Some(reason)
+    }
+
     /// Check if the Python process is healthy and responsive
     pub fn is_healthy(&self) -> bool {
         let process_guard = self.process.lock().unwrap();
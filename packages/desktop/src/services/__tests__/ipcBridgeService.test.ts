@@ -100,6 +100,19 @@ describe('IPCBridgeService', () => {
       });
     });
 
+    it('should pass the click annotation flag when given', async () => {
+      mockInvoke.mockResolvedValueOnce(undefined);
+
+      await service.startPlayback('/custom/path.json', 1.0, 1, true);
+
+      expect(mockInvoke).toHaveBeenCalledWith('start_playback', {
+        scriptPath: '/custom/path.json',
+        speed: 1.0,
+        loopCount: 1,
+        annotateClicks: true,
+      });
+    });
+
     it('should handle optional parameters', async () => {
       mockInvoke.mockResolvedValueOnce(undefined);
 
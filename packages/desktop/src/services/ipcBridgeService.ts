@@ -231,6 +231,8 @@ export class IPCBridgeService {
    *   Default is 1.0. Valid range: 0.1x to 10x
    * @param {number} [loopCount] - Optional number of times to repeat playback (1 = play once, 0 = infinite loop).
    *   Default is 1
+   * @param {boolean} [annotateClicks] - Optional flag to save an annotated screenshot after every click
+   *   for post-run review (Rust core only). Default is false
    * 
    * @throws {Error} If Python Core is unavailable or playback fails to start
    * @throws {Error} If no recordings exist (when scriptPath not provided)
@@ -254,7 +256,12 @@ export class IPCBridgeService {
    * Validates: Property 3 (Playback executes actions in order)
    * Validates: Property 4 (Timing preservation during playback)
    */
-  public async startPlayback(scriptPath?: string, speed?: number, loopCount?: number): Promise<void> {
+  public async startPlayback(
+    scriptPath?: string,
+    speed?: number,
+    loopCount?: number,
+    annotateClicks?: boolean
+  ): Promise<void> {
     try {
       // Get current core status for logging (gracefully handle failures)
       let coreType = 'unknown';
@@ -271,6 +278,7 @@ export class IPCBridgeService {
         scriptPath: scriptPath || null,
         speed: speed || 1.0,
         loopCount: loopCount || 1,
+        ...(annotateClicks !== undefined ? { annotateClicks } : {}),
       });
       console.log(`[IPC Bridge] start_playback command successful with ${coreType} core`);
     } catch (error) {
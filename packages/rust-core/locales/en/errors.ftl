@@ -0,0 +1,76 @@
+## Error messages for AutomationError variants
+
+error-unsupported-platform = Platform not supported: { $platform }
+error-permission-denied = Permission denied: { $operation }
+error-recording = Recording error: { $message }
+error-playback = Playback error: { $message }
+error-script = Script error: { $message }
+error-io = IO error: { $message }
+error-serialization = Serialization error: { $message }
+error-config = Configuration error: { $message }
+error-system = System error: { $message }
+error-invalid-input = Invalid input: { $message }
+error-timeout = Operation timeout: { $operation }
+error-core-unavailable = Core unavailable: { $core_type }
+error-core-health-check-failed = Core health check failed: { $core_type } - { $reason }
+error-fallback-failed = Fallback failed: { $reason }
+error-runtime-failure = Runtime failure: { $operation } - { $reason }
+error-performance-degradation = Performance degradation detected: { $metric } - { $details }
+error-dependency-missing = Dependency missing: { $dependency } - { $suggestion }
+
+## Recovery hints attached to ErrorInfo
+
+recovery-unsupported-platform = This platform is not supported. Please use a supported operating system.
+recovery-permission-denied = Permission denied for { $operation }. Please grant the required permissions and try again.
+recovery-core-unavailable = The selected automation core is unavailable. Try switching to an alternative core.
+recovery-core-health-check-failed = Core health check failed. Try restarting the core or switching to an alternative.
+recovery-fallback-failed = All automation cores are unavailable. Please check your system configuration.
+recovery-runtime-failure = Runtime failure detected. Try restarting the operation or switching cores.
+recovery-performance-degradation = Performance issues detected. Consider switching to a different automation core.
+recovery-recording = Recording failed. Try stopping and restarting the recording, or switch cores.
+recovery-playback = Playback failed. Try restarting playback or switch to a different core.
+recovery-script = Script error detected. Please check the script format and try again.
+recovery-io = File system error. Please check file permissions and disk space.
+recovery-serialization = Data format error. The script file may be corrupted.
+recovery-config = Configuration error. Please check your settings and try again.
+recovery-system = System error detected. Try restarting the operation or switching cores.
+recovery-invalid-input = Invalid input provided. Please check your input and try again.
+recovery-timeout = Operation timed out. Try increasing timeout settings or switching cores.
+
+## Suggested actions in error reports
+
+action-python-check-permissions = Check and grant permissions for: { $operation }
+action-python-switch-for-permissions = Try using Rust core which may have different permission requirements
+action-install-dependency = Install missing dependency: { $dependency } - { $suggestion }
+action-python-switch-for-dependencies = Switch to Rust core to avoid Python dependencies
+action-retry-after-delay = Retry the operation after a brief delay
+action-restart-python = Restart Python automation process
+action-python-switch-for-performance = Switch to Rust core for potentially better performance
+action-retry = Retry the operation
+action-rust-grant-permissions = Grant system permissions for: { $operation }
+action-rust-switch-for-permissions = Try Python core which may work with current permissions
+action-rust-unsupported-platform = Platform { $platform } not supported by Rust core, switch to Python core
+action-retry-with-rust = Retry the operation with Rust core
+action-rust-switch-fallback = Switch to Python core as fallback
+action-response-time-critical = Response time ({ $actual }) exceeds critical threshold ({ $threshold }). Consider switching cores.
+action-response-time-elevated = Response time ({ $actual }) is elevated. Switching cores may improve performance.
+action-success-rate-critical = Success rate ({ $rate }%) is critically low. Switching cores is recommended.
+action-success-rate-low = Success rate ({ $rate }%) is below optimal. Consider switching cores.
+action-memory-critical = Memory usage ({ $memory_mb } MB) is critically high. Switching to a more efficient core is recommended.
+
+## Errors returned to the desktop app
+
+ui-error-with-context = { $context }: { $error }
+ui-failed-update-settings = Failed to update settings
+ui-failed-set-locale = Failed to set language
+ui-failed-validate-script = Failed to validate script
+ui-failed-initialize-recorder = Failed to initialize the recorder
+ui-failed-start-recording = Failed to start recording
+ui-failed-stop-recording = Failed to stop recording
+ui-failed-initialize-player = Failed to initialize the player. Please check system permissions and ensure your platform is supported
+ui-failed-load-script = Failed to load script into player
+ui-failed-start-playback = Failed to start playback
+ui-failed-stop-playback = Failed to stop playback
+ui-failed-pause-playback = Failed to pause/resume playback
+ui-failed-display-layout = Failed to get display layout
+ui-failed-create-platform = Failed to create platform automation
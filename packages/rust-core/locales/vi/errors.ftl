@@ -0,0 +1,76 @@
+## Thông báo lỗi cho các biến thể AutomationError
+
+error-unsupported-platform = Nền tảng không được hỗ trợ: { $platform }
+error-permission-denied = Không có quyền thực hiện: { $operation }
+error-recording = Lỗi ghi kịch bản: { $message }
+error-playback = Lỗi phát lại: { $message }
+error-script = Lỗi kịch bản: { $message }
+error-io = Lỗi nhập/xuất: { $message }
+error-serialization = Lỗi tuần tự hóa dữ liệu: { $message }
+error-config = Lỗi cấu hình: { $message }
+error-system = Lỗi hệ thống: { $message }
+error-invalid-input = Dữ liệu đầu vào không hợp lệ: { $message }
+error-timeout = Hết thời gian chờ thao tác: { $operation }
+error-core-unavailable = Lõi tự động hóa không khả dụng: { $core_type }
+error-core-health-check-failed = Kiểm tra tình trạng lõi thất bại: { $core_type } - { $reason }
+error-fallback-failed = Chuyển sang lõi dự phòng thất bại: { $reason }
+error-runtime-failure = Lỗi khi chạy: { $operation } - { $reason }
+error-performance-degradation = Phát hiện suy giảm hiệu năng: { $metric } - { $details }
+error-dependency-missing = Thiếu thành phần phụ thuộc: { $dependency } - { $suggestion }
+
+## Gợi ý khắc phục gắn với ErrorInfo
+
+recovery-unsupported-platform = Nền tảng này không được hỗ trợ. Vui lòng sử dụng hệ điều hành được hỗ trợ.
+recovery-permission-denied = Không có quyền cho { $operation }. Vui lòng cấp các quyền cần thiết rồi thử lại.
+recovery-core-unavailable = Lõi tự động hóa đã chọn không khả dụng. Hãy thử chuyển sang lõi khác.
+recovery-core-health-check-failed = Kiểm tra tình trạng lõi thất bại. Hãy thử khởi động lại lõi hoặc chuyển sang lõi khác.
+recovery-fallback-failed = Tất cả lõi tự động hóa đều không khả dụng. Vui lòng kiểm tra cấu hình hệ thống.
+recovery-runtime-failure = Phát hiện lỗi khi chạy. Hãy thử chạy lại thao tác hoặc chuyển lõi.
+recovery-performance-degradation = Phát hiện vấn đề hiệu năng. Hãy cân nhắc chuyển sang lõi tự động hóa khác.
+recovery-recording = Ghi kịch bản thất bại. Hãy thử dừng rồi bắt đầu ghi lại, hoặc chuyển lõi.
+recovery-playback = Phát lại thất bại. Hãy thử phát lại lần nữa hoặc chuyển sang lõi khác.
+recovery-script = Phát hiện lỗi kịch bản. Vui lòng kiểm tra định dạng kịch bản rồi thử lại.
+recovery-io = Lỗi hệ thống tệp. Vui lòng kiểm tra quyền truy cập tệp và dung lượng ổ đĩa.
+recovery-serialization = Lỗi định dạng dữ liệu. Tệp kịch bản có thể đã bị hỏng.
+recovery-config = Lỗi cấu hình. Vui lòng kiểm tra phần cài đặt rồi thử lại.
+recovery-system = Phát hiện lỗi hệ thống. Hãy thử chạy lại thao tác hoặc chuyển lõi.
+recovery-invalid-input = Dữ liệu đầu vào không hợp lệ. Vui lòng kiểm tra lại dữ liệu rồi thử lại.
+recovery-timeout = Thao tác đã hết thời gian chờ. Hãy thử tăng thời gian chờ hoặc chuyển lõi.
+
+## Hành động gợi ý trong báo cáo lỗi
+
+action-python-check-permissions = Kiểm tra và cấp quyền cho: { $operation }
+action-python-switch-for-permissions = Thử dùng lõi Rust, vốn có thể có yêu cầu quyền khác
+action-install-dependency = Cài đặt thành phần phụ thuộc còn thiếu: { $dependency } - { $suggestion }
+action-python-switch-for-dependencies = Chuyển sang lõi Rust để không phụ thuộc vào Python
+action-retry-after-delay = Thử lại thao tác sau một lúc
+action-restart-python = Khởi động lại tiến trình tự động hóa Python
+action-python-switch-for-performance = Chuyển sang lõi Rust để có thể đạt hiệu năng tốt hơn
+action-retry = Thử lại thao tác
+action-rust-grant-permissions = Cấp quyền hệ thống cho: { $operation }
+action-rust-switch-for-permissions = Thử dùng lõi Python, có thể hoạt động với quyền hiện tại
+action-rust-unsupported-platform = Lõi Rust không hỗ trợ nền tảng { $platform }, hãy chuyển sang lõi Python
+action-retry-with-rust = Thử lại thao tác với lõi Rust
+action-rust-switch-fallback = Chuyển sang lõi Python làm phương án dự phòng
+action-response-time-critical = Thời gian phản hồi ({ $actual }) vượt ngưỡng nghiêm trọng ({ $threshold }). Hãy cân nhắc chuyển lõi.
+action-response-time-elevated = Thời gian phản hồi ({ $actual }) đang cao. Chuyển lõi có thể cải thiện hiệu năng.
+action-success-rate-critical = Tỷ lệ thành công ({ $rate }%) rất thấp. Nên chuyển lõi.
+action-success-rate-low = Tỷ lệ thành công ({ $rate }%) thấp hơn mức tối ưu. Hãy cân nhắc chuyển lõi.
+action-memory-critical = Mức sử dụng bộ nhớ ({ $memory_mb } MB) rất cao. Nên chuyển sang lõi hiệu quả hơn.
+
+## Lỗi trả về cho ứng dụng desktop
+
+ui-error-with-context = { $context }: { $error }
+ui-failed-update-settings = Không thể cập nhật cài đặt
+ui-failed-set-locale = Không thể đổi ngôn ngữ
+ui-failed-validate-script = Không thể kiểm tra kịch bản
+ui-failed-initialize-recorder = Không thể khởi tạo trình ghi
+ui-failed-start-recording = Không thể bắt đầu ghi
+ui-failed-stop-recording = Không thể dừng ghi
+ui-failed-initialize-player = Không thể khởi tạo trình phát. Vui lòng kiểm tra quyền hệ thống và đảm bảo nền tảng của bạn được hỗ trợ
+ui-failed-load-script = Không thể tải kịch bản vào trình phát
+ui-failed-start-playback = Không thể bắt đầu phát lại
+ui-failed-stop-playback = Không thể dừng phát lại
+ui-failed-pause-playback = Không thể tạm dừng/tiếp tục phát lại
+ui-failed-display-layout = Không thể lấy bố cục màn hình
+ui-failed-create-platform = Không thể tạo trình tự động hóa nền tảng
@@ -0,0 +1,675 @@
+//! Token and cost budgets for AI features
+//!
+//! AI test case generation and AI vision (dynamic mode) both spend provider
+//! tokens. A `TokenBudget` tracks that spend per day and per run (e.g. one
+//! suite execution) and decides whether the next AI call may go ahead. Once a
+//! limit is reached, calls are either blocked or degraded to cached/template
+//! modes, and an alert is raised once per exceeded limit.
+//!
+//! No limits are set by default; users opt in through `set_config`. A budget
+//! opened with `open` keeps the day's usage in a file so restarting the app
+//! does not reset the daily counter.
+
+use crate::bounded_buffer::{BoundedBuffer, OverflowPolicy};
+use crate::logging::{get_logger, CoreType, LogLevel, OperationType};
+use crate::{AutomationError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Number of budget alerts kept in memory
+const MAX_BUDGET_ALERTS: usize = 100;
+
+/// Token counts reported by an AI provider for a single call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AITokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl AITokenUsage {
+    pub fn new(prompt_tokens: u64, completion_tokens: u64) -> Self {
+        Self { prompt_tokens, completion_tokens }
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Token and cost limits for one budget scope; `None` means unlimited
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BudgetLimits {
+    pub max_tokens: Option<u64>,
+    pub max_cost_usd: Option<f64>,
+}
+
+/// What happens to AI calls once a budget is exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetExceededAction {
+    /// Refuse the AI call
+    Block,
+    /// Skip the AI call and fall back to cached or template results
+    Degrade,
+}
+
+/// Budget configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBudgetConfig {
+    /// Limits per calendar day (UTC)
+    pub daily: BudgetLimits,
+    /// Limits per run, between `begin_run` and `end_run`
+    pub per_run: BudgetLimits,
+    /// Cost per 1K prompt tokens in USD
+    pub prompt_token_cost_per_1k: f64,
+    /// Cost per 1K completion tokens in USD
+    pub completion_token_cost_per_1k: f64,
+    pub on_exceeded: BudgetExceededAction,
+}
+
+impl Default for TokenBudgetConfig {
+    fn default() -> Self {
+        Self {
+            daily: BudgetLimits::default(),
+            per_run: BudgetLimits::default(),
+            // Gemini 1.5 Flash pricing, matching the test case generator's estimate
+            prompt_token_cost_per_1k: 0.000075,
+            completion_token_cost_per_1k: 0.0003,
+            on_exceeded: BudgetExceededAction::Degrade,
+        }
+    }
+}
+
+/// Scope a budget limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetScope {
+    Daily,
+    Run,
+}
+
+/// Kind of limit that was exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLimitKind {
+    Tokens,
+    Cost,
+}
+
+/// Accumulated usage within a scope
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub requests: u64,
+    pub cost_usd: f64,
+}
+
+impl BudgetUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// Raised the first time a limit is exceeded within its day or run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetAlert {
+    pub scope: BudgetScope,
+    pub kind: BudgetLimitKind,
+    pub limit: f64,
+    pub used: f64,
+    pub run_id: Option<String>,
+    pub message: String,
+    pub triggered_at: DateTime<Utc>,
+}
+
+/// Whether an AI call may proceed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum BudgetDecision {
+    Allowed,
+    /// Skip the AI call and use cached or template results instead
+    Degraded { reason: String },
+    Blocked { reason: String },
+}
+
+impl BudgetDecision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, BudgetDecision::Allowed)
+    }
+}
+
+/// Snapshot of budget usage and alerts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetStatus {
+    /// Day the daily usage applies to (YYYY-MM-DD, UTC)
+    pub day: String,
+    pub daily_usage: BudgetUsage,
+    pub run_id: Option<String>,
+    pub run_usage: BudgetUsage,
+    pub config: TokenBudgetConfig,
+    /// Limits currently exceeded
+    pub exceeded: Vec<BudgetAlert>,
+    /// Recent alerts, oldest first
+    pub alerts: Vec<BudgetAlert>,
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    day: String,
+    daily_usage: BudgetUsage,
+    run_id: Option<String>,
+    run_usage: BudgetUsage,
+    exceeded: HashMap<(BudgetScope, BudgetLimitKind), BudgetAlert>,
+    alerts: BoundedBuffer<BudgetAlert>,
+}
+
+/// Daily usage as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedUsage {
+    day: String,
+    daily_usage: BudgetUsage,
+}
+
+/// Called with each newly raised budget alert
+pub type BudgetAlertHandler = Arc<dyn Fn(&BudgetAlert) + Send + Sync>;
+
+/// Token and cost budget shared between clones
+#[derive(Clone)]
+pub struct TokenBudget {
+    config: Arc<Mutex<TokenBudgetConfig>>,
+    state: Arc<Mutex<BudgetState>>,
+    /// File the daily usage is saved to after every recorded call
+    usage_path: Option<PathBuf>,
+    /// File the limits are saved to by `save_config`
+    config_path: Option<PathBuf>,
+    alert_handler: Arc<Mutex<Option<BudgetAlertHandler>>>,
+}
+
+impl fmt::Debug for TokenBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenBudget")
+            .field("config", &self.config)
+            .field("state", &self.state)
+            .field("usage_path", &self.usage_path)
+            .field("config_path", &self.config_path)
+            .finish()
+    }
+}
+
+impl Default for TokenBudget {
+    fn default() -> Self {
+        Self::new(TokenBudgetConfig::default())
+    }
+}
+
+impl TokenBudget {
+    /// Create a budget with the given limits
+    pub fn new(config: TokenBudgetConfig) -> Self {
+        Self {
+            config: Arc::new(Mutex::new(config)),
+            state: Arc::new(Mutex::new(BudgetState {
+                day: day_key(Utc::now()),
+                daily_usage: BudgetUsage::default(),
+                run_id: None,
+                run_usage: BudgetUsage::default(),
+                exceeded: HashMap::new(),
+                alerts: BoundedBuffer::new("budget_alerts", MAX_BUDGET_ALERTS, OverflowPolicy::DropOldest),
+            })),
+            usage_path: None,
+            config_path: None,
+            alert_handler: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Create a budget that keeps the day's usage in `path`
+    ///
+    /// Usage saved earlier the same day counts against today's limits; usage
+    /// from a previous day is discarded.
+    pub fn open(config: TokenBudgetConfig, path: impl AsRef<Path>) -> Result<Self> {
+        let mut budget = Self::new(config);
+        budget.usage_path = Some(path.as_ref().to_path_buf());
+
+        if path.as_ref().exists() {
+            let persisted: PersistedUsage = serde_json::from_str(&fs::read_to_string(path.as_ref())?)?;
+            let now = Utc::now();
+            if persisted.day == day_key(now) {
+                let mut state = budget.state.lock().unwrap();
+                state.daily_usage = persisted.daily_usage;
+                // Restore exceeded daily limits without raising the alerts again
+                budget.evaluate(&mut state, now);
+            }
+        }
+        Ok(budget)
+    }
+
+    /// Create a budget that keeps the day's usage in `usage_path` and its
+    /// limits in `config_path`
+    ///
+    /// Limits saved earlier with `save_config` replace `fallback`, which only
+    /// applies until limits are first saved.
+    pub fn open_with_saved_config(
+        fallback: TokenBudgetConfig,
+        usage_path: impl AsRef<Path>,
+        config_path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let config = if config_path.as_ref().exists() {
+            serde_json::from_str(&fs::read_to_string(config_path.as_ref())?)?
+        } else {
+            fallback
+        };
+
+        let mut budget = Self::open(config, usage_path)?;
+        budget.config_path = Some(config_path.as_ref().to_path_buf());
+        Ok(budget)
+    }
+
+    /// Default file for the daily usage, under the user's config directory
+    pub fn default_usage_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("GeniusQA")
+            .join("ai_budget_usage.json")
+    }
+
+    /// Default file for the limits, next to the daily usage
+    pub fn default_config_path() -> PathBuf {
+        Self::default_usage_path().with_file_name("ai_budget_config.json")
+    }
+
+    /// Call `handler` with every alert raised from now on, e.g. to surface it
+    /// in the monitoring dashboard
+    pub fn set_alert_handler(&self, handler: impl Fn(&BudgetAlert) + Send + Sync + 'static) {
+        *self.alert_handler.lock().unwrap() = Some(Arc::new(handler));
+    }
+
+    /// Whether a run is in progress
+    pub fn run_active(&self) -> bool {
+        self.state.lock().unwrap().run_id.is_some()
+    }
+
+    pub fn config(&self) -> TokenBudgetConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Replace the limits; usage recorded so far is kept
+    ///
+    /// The new limits are only kept across restarts once `save_config` is called.
+    pub fn set_config(&self, config: TokenBudgetConfig) {
+        let mut state = self.state.lock().unwrap();
+        // Lift restrictions whose limits were raised; newly exceeded limits
+        // are reported on the next recorded call
+        let daily_usage = state.daily_usage.clone();
+        let run_usage = state.run_usage.clone();
+        state.exceeded.retain(|(scope, kind), _| match scope {
+            BudgetScope::Daily => exceeded_limit(&config.daily, *kind, &daily_usage).is_some(),
+            BudgetScope::Run => exceeded_limit(&config.per_run, *kind, &run_usage).is_some(),
+        });
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// Start a new run, resetting the per-run usage
+    pub fn begin_run(&self, run_id: impl Into<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.run_id = Some(run_id.into());
+        state.run_usage = BudgetUsage::default();
+        state.exceeded.retain(|(scope, _), _| *scope != BudgetScope::Run);
+    }
+
+    /// End the current run; per-run limits no longer apply until the next run
+    pub fn end_run(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.run_id = None;
+        state.run_usage = BudgetUsage::default();
+        state.exceeded.retain(|(scope, _), _| *scope != BudgetScope::Run);
+    }
+
+    /// Cost in USD of the given usage
+    pub fn cost_of(&self, usage: &AITokenUsage) -> f64 {
+        let config = self.config.lock().unwrap();
+        (usage.prompt_tokens as f64 / 1000.0) * config.prompt_token_cost_per_1k
+            + (usage.completion_tokens as f64 / 1000.0) * config.completion_token_cost_per_1k
+    }
+
+    /// Decide whether the next AI call may go ahead
+    pub fn check(&self) -> BudgetDecision {
+        self.check_at(Utc::now())
+    }
+
+    fn check_at(&self, now: DateTime<Utc>) -> BudgetDecision {
+        let on_exceeded = self.config.lock().unwrap().on_exceeded;
+        let mut state = self.state.lock().unwrap();
+        Self::roll_over(&mut state, now);
+
+        let reason = match state.exceeded.values().next() {
+            Some(alert) => alert.message.clone(),
+            None => return BudgetDecision::Allowed,
+        };
+
+        match on_exceeded {
+            BudgetExceededAction::Block => BudgetDecision::Blocked { reason },
+            BudgetExceededAction::Degrade => BudgetDecision::Degraded { reason },
+        }
+    }
+
+    /// Record the tokens spent by an AI call
+    ///
+    /// Returns the alerts raised by this call, if it pushed usage over a limit.
+    pub fn record_usage(&self, usage: AITokenUsage) -> Vec<BudgetAlert> {
+        self.record_usage_at(usage, Utc::now())
+    }
+
+    fn record_usage_at(&self, usage: AITokenUsage, now: DateTime<Utc>) -> Vec<BudgetAlert> {
+        let cost = self.cost_of(&usage);
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        Self::roll_over(state, now);
+
+        for scope_usage in [&mut state.daily_usage, &mut state.run_usage] {
+            scope_usage.prompt_tokens += usage.prompt_tokens;
+            scope_usage.completion_tokens += usage.completion_tokens;
+            scope_usage.requests += 1;
+            scope_usage.cost_usd += cost;
+        }
+
+        let raised = self.evaluate(state, now);
+        let persisted = PersistedUsage {
+            day: state.day.clone(),
+            daily_usage: state.daily_usage.clone(),
+        };
+        drop(guard);
+
+        if let Err(e) = self.save_usage(&persisted) {
+            log_budget_error(&format!("Failed to save AI budget usage: {}", e));
+        }
+
+        let handler = self.alert_handler.lock().unwrap().clone();
+        for alert in &raised {
+            log_budget_alert(alert);
+            if let Some(ref handler) = handler {
+                handler(alert);
+            }
+        }
+        raised
+    }
+
+    /// Save the current limits to the config file the budget was opened with
+    ///
+    /// Does nothing for a budget without a config file.
+    pub fn save_config(&self) -> Result<()> {
+        let path = match self.config_path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(&self.config()).map_err(|e| AutomationError::SystemError {
+            message: format!("Failed to serialize AI budget config: {}", e),
+        })?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    fn save_usage(&self, usage: &PersistedUsage) -> Result<()> {
+        let path = match self.usage_path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(usage).map_err(|e| AutomationError::SystemError {
+            message: format!("Failed to serialize AI budget usage: {}", e),
+        })?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn status(&self) -> BudgetStatus {
+        let config = self.config();
+        let mut state = self.state.lock().unwrap();
+        Self::roll_over(&mut state, Utc::now());
+
+        BudgetStatus {
+            day: state.day.clone(),
+            daily_usage: state.daily_usage.clone(),
+            run_id: state.run_id.clone(),
+            run_usage: state.run_usage.clone(),
+            config,
+            exceeded: state.exceeded.values().cloned().collect(),
+            alerts: state.alerts.to_vec(),
+        }
+    }
+
+    /// Reset daily usage when the UTC day changes
+    fn roll_over(state: &mut BudgetState, now: DateTime<Utc>) {
+        let today = day_key(now);
+        if state.day != today {
+            state.day = today;
+            state.daily_usage = BudgetUsage::default();
+            state.exceeded.retain(|(scope, _), _| *scope != BudgetScope::Daily);
+        }
+    }
+
+    /// Compare usage with the limits and raise alerts for newly exceeded ones
+    fn evaluate(&self, state: &mut BudgetState, now: DateTime<Utc>) -> Vec<BudgetAlert> {
+        let config = self.config.lock().unwrap().clone();
+        let mut scopes = vec![(BudgetScope::Daily, &config.daily, state.daily_usage.clone())];
+        if state.run_id.is_some() {
+            scopes.push((BudgetScope::Run, &config.per_run, state.run_usage.clone()));
+        }
+
+        let mut raised = Vec::new();
+        for (scope, limits, usage) in scopes {
+            for kind in [BudgetLimitKind::Tokens, BudgetLimitKind::Cost] {
+                let (limit, used) = match exceeded_limit(limits, kind, &usage) {
+                    Some(exceeded) => exceeded,
+                    None => continue,
+                };
+                if state.exceeded.contains_key(&(scope, kind)) {
+                    continue;
+                }
+
+                let alert = BudgetAlert {
+                    scope,
+                    kind,
+                    limit,
+                    used,
+                    run_id: if scope == BudgetScope::Run { state.run_id.clone() } else { None },
+                    message: budget_message(scope, kind, limit, used),
+                    triggered_at: now,
+                };
+                state.exceeded.insert((scope, kind), alert.clone());
+                state.alerts.push(alert.clone());
+                raised.push(alert);
+            }
+        }
+        raised
+    }
+}
+
+/// The limit and usage if `usage` has reached the limit of the given kind
+fn exceeded_limit(limits: &BudgetLimits, kind: BudgetLimitKind, usage: &BudgetUsage) -> Option<(f64, f64)> {
+    let (limit, used) = match kind {
+        BudgetLimitKind::Tokens => (limits.max_tokens.map(|l| l as f64), usage.total_tokens() as f64),
+        BudgetLimitKind::Cost => (limits.max_cost_usd, usage.cost_usd),
+    };
+    limit.filter(|limit| used >= *limit).map(|limit| (limit, used))
+}
+
+fn day_key(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m-%d").to_string()
+}
+
+fn budget_message(scope: BudgetScope, kind: BudgetLimitKind, limit: f64, used: f64) -> String {
+    let scope = match scope {
+        BudgetScope::Daily => "Daily",
+        BudgetScope::Run => "Per-run",
+    };
+    match kind {
+        BudgetLimitKind::Tokens => format!("{} AI token budget exceeded: {:.0} of {:.0} tokens used", scope, used, limit),
+        BudgetLimitKind::Cost => format!("{} AI cost budget exceeded: ${:.4} of ${:.4} spent", scope, used, limit),
+    }
+}
+
+fn log_budget_alert(alert: &BudgetAlert) {
+    if let Some(logger) = get_logger() {
+        let mut metadata = HashMap::new();
+        metadata.insert("scope".to_string(), serde_json::json!(alert.scope));
+        metadata.insert("kind".to_string(), serde_json::json!(alert.kind));
+        metadata.insert("limit".to_string(), serde_json::json!(alert.limit));
+        metadata.insert("used".to_string(), serde_json::json!(alert.used));
+        if let Some(ref run_id) = alert.run_id {
+            metadata.insert("run_id".to_string(), serde_json::json!(run_id));
+        }
+
+        logger.log_operation(
+            LogLevel::Warn,
+            CoreType::Rust,
+            OperationType::PerformanceMonitoring,
+            format!("ai_budget_{}", alert.triggered_at.timestamp_millis()),
+            alert.message.clone(),
+            Some(metadata),
+        );
+    }
+}
+
+fn log_budget_error(message: &str) {
+    if let Some(logger) = get_logger() {
+        logger.log_operation(
+            LogLevel::Error,
+            CoreType::Rust,
+            OperationType::PerformanceMonitoring,
+            format!("ai_budget_error_{}", Utc::now().timestamp_millis()),
+            message.to_string(),
+            None,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_limited(daily: Option<u64>, per_run: Option<u64>, on_exceeded: BudgetExceededAction) -> TokenBudget {
+        TokenBudget::new(TokenBudgetConfig {
+            daily: BudgetLimits { max_tokens: daily, max_cost_usd: None },
+            per_run: BudgetLimits { max_tokens: per_run, max_cost_usd: None },
+            on_exceeded,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_run_budget_degrades_and_alerts_once() {
+        let budget = token_limited(None, Some(1000), BudgetExceededAction::Degrade);
+        budget.begin_run("suite-1");
+
+        assert!(budget.record_usage(AITokenUsage::new(400, 100)).is_empty());
+        assert!(budget.check().is_allowed());
+
+        let alerts = budget.record_usage(AITokenUsage::new(400, 200));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].scope, BudgetScope::Run);
+        assert_eq!(alerts[0].run_id.as_deref(), Some("suite-1"));
+        assert!(matches!(budget.check(), BudgetDecision::Degraded { .. }));
+
+        // Already exceeded limits do not raise new alerts
+        assert!(budget.record_usage(AITokenUsage::new(10, 10)).is_empty());
+
+        // A new run starts with a fresh per-run budget
+        budget.begin_run("suite-2");
+        assert!(budget.check().is_allowed());
+        assert_eq!(budget.status().alerts.len(), 1);
+    }
+
+    #[test]
+    fn test_daily_budget_blocks_until_next_day() {
+        let budget = token_limited(Some(500), None, BudgetExceededAction::Block);
+        let now = Utc::now();
+
+        budget.record_usage_at(AITokenUsage::new(500, 0), now);
+        assert!(matches!(budget.check_at(now), BudgetDecision::Blocked { .. }));
+
+        let tomorrow = now + chrono::Duration::days(1);
+        assert!(budget.check_at(tomorrow).is_allowed());
+        assert_eq!(budget.status().daily_usage.total_tokens(), 0);
+    }
+
+    #[test]
+    fn test_cost_budget_and_raised_limits() {
+        let budget = TokenBudget::new(TokenBudgetConfig {
+            daily: BudgetLimits { max_tokens: None, max_cost_usd: Some(0.001) },
+            per_run: BudgetLimits::default(),
+            prompt_token_cost_per_1k: 0.001,
+            completion_token_cost_per_1k: 0.001,
+            on_exceeded: BudgetExceededAction::Block,
+        });
+
+        let alerts = budget.record_usage(AITokenUsage::new(600, 600));
+        assert_eq!(alerts[0].kind, BudgetLimitKind::Cost);
+        assert!(!budget.check().is_allowed());
+
+        let mut config = budget.config();
+        config.daily.max_cost_usd = Some(1.0);
+        budget.set_config(config);
+        assert!(budget.check().is_allowed());
+    }
+
+    #[test]
+    fn test_daily_usage_survives_reopen_and_alerts_reach_handler() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.json");
+        let config = TokenBudgetConfig {
+            daily: BudgetLimits { max_tokens: Some(1000), max_cost_usd: None },
+            on_exceeded: BudgetExceededAction::Block,
+            ..Default::default()
+        };
+
+        let budget = TokenBudget::open(config.clone(), &path).unwrap();
+        let raised = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&raised);
+        budget.set_alert_handler(move |alert| sink.lock().unwrap().push(alert.scope));
+        budget.record_usage(AITokenUsage::new(600, 500));
+        assert_eq!(*raised.lock().unwrap(), vec![BudgetScope::Daily]);
+
+        let reopened = TokenBudget::open(config, &path).unwrap();
+        assert_eq!(reopened.status().daily_usage.total_tokens(), 1100);
+        assert!(matches!(reopened.check(), BudgetDecision::Blocked { .. }));
+    }
+
+    #[test]
+    fn test_saved_config_replaces_fallback_on_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let usage_path = dir.path().join("usage.json");
+        let config_path = dir.path().join("config.json");
+
+        let budget = TokenBudget::open_with_saved_config(TokenBudgetConfig::default(), &usage_path, &config_path).unwrap();
+        budget.set_config(TokenBudgetConfig {
+            daily: BudgetLimits { max_tokens: Some(1000), max_cost_usd: None },
+            on_exceeded: BudgetExceededAction::Block,
+            ..Default::default()
+        });
+        budget.save_config().unwrap();
+        budget.record_usage(AITokenUsage::new(600, 500));
+
+        let reopened = TokenBudget::open_with_saved_config(TokenBudgetConfig::default(), &usage_path, &config_path).unwrap();
+        assert_eq!(reopened.config().daily.max_tokens, Some(1000));
+        assert_eq!(reopened.config().on_exceeded, BudgetExceededAction::Block);
+        assert!(matches!(reopened.check(), BudgetDecision::Blocked { .. }));
+    }
+
+    #[test]
+    fn test_default_config_has_no_limits() {
+        let budget = TokenBudget::default();
+        budget.begin_run("run-1");
+        assert!(budget.record_usage(AITokenUsage::new(10_000_000, 10_000_000)).is_empty());
+        assert!(budget.check().is_allowed());
+    }
+}
@@ -2431,3 +2431,48 @@ fn test_cache_invalidation_on_ai_timeout() {
         "Error should mention timeout"
     );
 }
+
+/// Test that an exhausted token budget stops AI calls and degrades to saved coordinates
+#[test]
+fn test_dynamic_mode_respects_token_budget() {
+    use crate::ai_budget::{AITokenUsage, BudgetLimits, TokenBudget, TokenBudgetConfig};
+    use crate::player::execute_dynamic_mode_with_budget;
+
+    let mut action = AIVisionCaptureAction::new(
+        "test-id".to_string(),
+        0.0,
+        "screenshots/test.png".to_string(),
+        (1920, 1080),
+    );
+    action.is_dynamic = true;
+    action.dynamic_config.prompt = "Find the button".to_string();
+    action.static_data.saved_x = Some(100);
+    action.static_data.saved_y = Some(200);
+
+    let mock_provider = MockAIVisionProvider::new(
+        AIVisionAnalysisResponse::success(500, 300, 0.9).with_token_usage(AITokenUsage::new(800, 400)),
+    );
+    let platform = MockPlatform::new((1920, 1080));
+    let budget = TokenBudget::new(TokenBudgetConfig {
+        per_run: BudgetLimits { max_tokens: Some(1000), max_cost_usd: None },
+        ..Default::default()
+    });
+    budget.begin_run("suite-run");
+
+    // First call is within budget and spends 1200 tokens
+    let result = execute_dynamic_mode_with_budget(
+        &platform, &action, 0, &mock_provider, &budget, "screenshot".to_string(), vec![],
+    );
+    assert!(result.execution_result.ai_called);
+    assert_eq!(budget.status().run_usage.total_tokens(), 1200);
+
+    // Second call exceeds the budget and falls back to the saved coordinates
+    let result = execute_dynamic_mode_with_budget(
+        &platform, &action, 0, &mock_provider, &budget, "screenshot".to_string(), vec![],
+    );
+    assert_eq!(mock_provider.get_call_count(), 1);
+    assert!(!result.execution_result.ai_called);
+    assert_eq!(result.execution_result.mode, AIVisionExecutionMode::Static);
+    assert_eq!(result.execution_result.coordinates, Some((100, 200)));
+    assert!(result.cache_update.is_none());
+}
@@ -8,6 +8,7 @@
 //!
 //! Requirements: 4.6, 4.7, 4.8, 4.9, 4.10, 4.11
 
+use crate::ai_budget::AITokenUsage;
 use crate::script::{AIVisionCaptureAction, VisionROI, SearchScope};
 use serde::{Deserialize, Serialize};
 
@@ -52,6 +53,9 @@ pub struct AIVisionAnalysisResponse {
     /// Error message if analysis failed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Tokens spent on the analysis, if the provider reports them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<AITokenUsage>,
 }
 
 impl AIVisionAnalysisResponse {
@@ -63,6 +67,7 @@ impl AIVisionAnalysisResponse {
             y: Some(y),
             confidence: Some(confidence),
             error: None,
+            token_usage: None,
         }
     }
 
@@ -74,6 +79,7 @@ impl AIVisionAnalysisResponse {
             y: None,
             confidence: None,
             error: Some(error),
+            token_usage: None,
         }
     }
 
@@ -85,8 +91,15 @@ impl AIVisionAnalysisResponse {
             y: None,
             confidence: None,
             error: Some(format!("AI analysis timed out after {}ms", timeout_ms)),
+            token_usage: None,
         }
     }
+
+    /// Attach the tokens spent on the analysis
+    pub fn with_token_usage(mut self, usage: AITokenUsage) -> Self {
+        self.token_usage = Some(usage);
+        self
+    }
 }
 
 /// Trait for AI Vision analysis providers
@@ -6,6 +6,7 @@ use crate::{
     player::{BackgroundPlayer, PlaybackEvent},
     performance::{PerformanceCollector, OperationType},
     health::CoreType,
+    event_channel::{event_channel, DEFAULT_EVENT_CAPACITY},
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -88,8 +89,8 @@ pub struct RustAutomationCore {
     config: AutomationConfig,
     recorder: Option<BackgroundRecorder>,
     player: Option<BackgroundPlayer>,
-    recording_event_receiver: Option<mpsc::UnboundedReceiver<RecordingEvent>>,
-    playback_event_receiver: Option<mpsc::UnboundedReceiver<PlaybackEvent>>,
+    recording_event_receiver: Option<mpsc::Receiver<RecordingEvent>>,
+    playback_event_receiver: Option<mpsc::Receiver<PlaybackEvent>>,
     performance_collector: PerformanceCollector,
 }
 
@@ -107,12 +108,12 @@ impl RustAutomationCore {
     }
 
     /// Get recording event receiver for UI updates
-    pub fn get_recording_events(&mut self) -> Option<&mut mpsc::UnboundedReceiver<RecordingEvent>> {
+    pub fn get_recording_events(&mut self) -> Option<&mut mpsc::Receiver<RecordingEvent>> {
         self.recording_event_receiver.as_mut()
     }
 
     /// Get playback event receiver for UI updates
-    pub fn get_playback_events(&mut self) -> Option<&mut mpsc::UnboundedReceiver<PlaybackEvent>> {
+    pub fn get_playback_events(&mut self) -> Option<&mut mpsc::Receiver<PlaybackEvent>> {
         self.playback_event_receiver.as_mut()
     }
 
@@ -166,7 +167,7 @@ impl AutomationCore for RustAutomationCore {
         let mut recorder = BackgroundRecorder::new(self.config.clone())?;
         
         // Set up event streaming
-        let (sender, receiver) = mpsc::unbounded_channel();
+        let (sender, receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         recorder.set_event_sender(sender);
         self.recording_event_receiver = Some(receiver);
         
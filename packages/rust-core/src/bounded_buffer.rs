@@ -0,0 +1,294 @@
+//! Memory-bounded in-memory buffers
+//!
+//! Long-running sessions keep logs, metrics and alert history in memory.
+//! `BoundedBuffer` caps how many entries are retained and counts what had to
+//! be dropped, so the counts can be reported through monitoring instead of
+//! memory slowly growing over a week-long session.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+/// What to do when a push would exceed the buffer's capacity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Evict the oldest entry to make room (ring buffer)
+    DropOldest,
+    /// Refuse the new entry so the producer can back off
+    RejectNew,
+    /// Evict the oldest entry without counting it as dropped, for windows of
+    /// recent entries that are also persisted elsewhere
+    Overwrite,
+}
+
+/// Occupancy and drop counters of a buffer, as reported by monitoring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BufferStats {
+    pub name: String,
+    pub len: usize,
+    pub capacity: usize,
+    pub policy: OverflowPolicy,
+    /// Entries lost because the buffer was full; `Overwrite` evictions are not counted
+    pub dropped: u64,
+}
+
+/// A fixed-capacity FIFO buffer that counts dropped entries
+#[derive(Debug, Clone)]
+pub struct BoundedBuffer<T> {
+    name: String,
+    items: VecDeque<T>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    dropped: u64,
+}
+
+impl<T> BoundedBuffer<T> {
+    /// Create an empty buffer; a capacity of zero is treated as one
+    pub fn new(name: impl Into<String>, capacity: usize, policy: OverflowPolicy) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            name: name.into(),
+            // Avoid reserving huge buffers up front for generous limits
+            items: VecDeque::with_capacity(capacity.min(1024)),
+            capacity,
+            policy,
+            dropped: 0,
+        }
+    }
+
+    /// Append an entry
+    ///
+    /// Returns false if the entry was rejected under `RejectNew`. Under
+    /// `DropOldest` and `Overwrite` the push always succeeds.
+    pub fn push(&mut self, item: T) -> bool {
+        if self.items.len() >= self.capacity {
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    self.items.pop_front();
+                    self.dropped += 1;
+                }
+                OverflowPolicy::Overwrite => {
+                    self.items.pop_front();
+                }
+                OverflowPolicy::RejectNew => {
+                    self.dropped += 1;
+                    return false;
+                }
+            }
+        }
+        self.items.push_back(item);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Total entries dropped since the buffer was created
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Iterate from oldest to newest
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::collections::vec_deque::IterMut<'_, T> {
+        self.items.iter_mut()
+    }
+
+    /// Keep only entries matching the predicate (not counted as drops)
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.items.retain(f);
+    }
+
+    /// Remove all entries (not counted as drops)
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Remove and return all entries, oldest first
+    pub fn drain(&mut self) -> Vec<T> {
+        self.items.drain(..).collect()
+    }
+
+    /// Change the capacity, evicting the oldest entries if it shrinks
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.items.len() > self.capacity {
+            self.items.pop_front();
+            if self.policy != OverflowPolicy::Overwrite {
+                self.dropped += 1;
+            }
+        }
+    }
+
+    pub fn stats(&self) -> BufferStats {
+        BufferStats {
+            name: self.name.clone(),
+            len: self.items.len(),
+            capacity: self.capacity,
+            policy: self.policy,
+            dropped: self.dropped,
+        }
+    }
+}
+
+impl<T: Clone> BoundedBuffer<T> {
+    /// Copy the entries into a vector, oldest first
+    pub fn to_vec(&self) -> Vec<T> {
+        self.items.iter().cloned().collect()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BoundedBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = std::collections::vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// A buffer or queue owned elsewhere that reports its stats to monitoring
+pub trait BufferStatsSource: Send + Sync {
+    fn buffer_stats(&self) -> BufferStats;
+}
+
+/// Buffers and queues whose stats the monitor reports
+///
+/// Sources are held weakly, so a channel or queue leaves the registry once
+/// its owner drops it. Clones share the same sources.
+#[derive(Clone, Default)]
+pub struct BufferRegistry {
+    sources: Arc<Mutex<Vec<Weak<dyn BufferStatsSource>>>>,
+}
+
+impl BufferRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report `source` until it is dropped
+    pub fn register(&self, source: &Arc<dyn BufferStatsSource>) {
+        self.sources.lock().unwrap().push(Arc::downgrade(source));
+    }
+
+    /// Stats of every registered source that is still alive
+    pub fn stats(&self) -> Vec<BufferStats> {
+        let mut sources = self.sources.lock().unwrap();
+        sources.retain(|source| source.strong_count() > 0);
+        sources
+            .iter()
+            .filter_map(|source| source.upgrade())
+            .map(|source| source.buffer_stats())
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for BufferRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufferRegistry")
+            .field("sources", &self.sources.lock().unwrap().len())
+            .finish()
+    }
+}
+
+static BUFFER_REGISTRY: OnceLock<BufferRegistry> = OnceLock::new();
+
+/// Process-wide registry that event channels and queues register with
+pub fn buffer_registry() -> &'static BufferRegistry {
+    BUFFER_REGISTRY.get_or_init(BufferRegistry::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_oldest_keeps_latest_entries() {
+        let mut buffer = BoundedBuffer::new("test", 3, OverflowPolicy::DropOldest);
+        for i in 0..5 {
+            assert!(buffer.push(i));
+        }
+
+        assert_eq!(buffer.to_vec(), vec![2, 3, 4]);
+        assert_eq!(buffer.dropped(), 2);
+        assert!(buffer.is_full());
+    }
+
+    #[test]
+    fn test_reject_new_applies_backpressure() {
+        let mut buffer = BoundedBuffer::new("test", 2, OverflowPolicy::RejectNew);
+        assert!(buffer.push("a"));
+        assert!(buffer.push("b"));
+        assert!(!buffer.push("c"));
+
+        assert_eq!(buffer.to_vec(), vec!["a", "b"]);
+        assert_eq!(buffer.stats().dropped, 1);
+    }
+
+    #[test]
+    fn test_overwrite_does_not_count_evictions() {
+        let mut buffer = BoundedBuffer::new("test", 2, OverflowPolicy::Overwrite);
+        for i in 0..5 {
+            assert!(buffer.push(i));
+        }
+
+        assert_eq!(buffer.to_vec(), vec![3, 4]);
+        assert_eq!(buffer.dropped(), 0);
+    }
+
+    #[test]
+    fn test_retain_and_shrink() {
+        let mut buffer = BoundedBuffer::new("test", 10, OverflowPolicy::DropOldest);
+        for i in 0..10 {
+            buffer.push(i);
+        }
+
+        buffer.retain(|i| i % 2 == 0);
+        assert_eq!(buffer.len(), 5);
+        assert_eq!(buffer.dropped(), 0);
+
+        buffer.set_capacity(2);
+        assert_eq!(buffer.to_vec(), vec![6, 8]);
+        assert_eq!(buffer.dropped(), 3);
+    }
+
+    struct FixedSource(BufferStats);
+
+    impl BufferStatsSource for FixedSource {
+        fn buffer_stats(&self) -> BufferStats {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_registry_forgets_dropped_sources() {
+        let registry = BufferRegistry::new();
+        let source: Arc<dyn BufferStatsSource> =
+            Arc::new(FixedSource(BoundedBuffer::<u8>::new("queue", 4, OverflowPolicy::RejectNew).stats()));
+        registry.register(&source);
+
+        let stats = registry.clone().stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].name, "queue");
+
+        drop(source);
+        assert!(registry.stats().is_empty());
+    }
+}
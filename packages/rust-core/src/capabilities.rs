@@ -0,0 +1,419 @@
+//! Core feature capability matrix
+//!
+//! Which action types, capture modes and commands each core supports on each
+//! platform. The UI and the script validator use the matrix to warn about
+//! unsupported features (e.g. a `visual_assert` step on the Python core)
+//! before a run, instead of the step being skipped or failing mid-playback.
+
+use crate::health::CoreType;
+use crate::script::{ActionType, ScriptData};
+use crate::validation::{CompatibilityIssue, IssueSeverity};
+use crate::validation_rules::action_type_name;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Every action type a script may contain
+const ALL_ACTION_TYPES: [ActionType; 13] = [
+    ActionType::MouseMove,
+    ActionType::MouseClick,
+    ActionType::MouseDoubleClick,
+    ActionType::MouseDrag,
+    ActionType::MouseScroll,
+    ActionType::AiVisionCapture,
+    ActionType::VisualAssert,
+    ActionType::KeyPress,
+    ActionType::KeyRelease,
+    ActionType::KeyType,
+    ActionType::Screenshot,
+    ActionType::Wait,
+    ActionType::Custom,
+];
+
+/// Commands shared by both cores
+const CORE_COMMANDS: [&str; 11] = [
+    "start_recording",
+    "stop_recording",
+    "start_playback",
+    "stop_playback",
+    "pause_playback",
+    "check_recordings",
+    "get_latest",
+    "list_scripts",
+    "load_script",
+    "save_script",
+    "delete_script",
+];
+
+/// Step editing commands only the Python core implements
+const STEP_COMMANDS: [&str; 7] = [
+    "set_active_step",
+    "create_step",
+    "get_recording_status",
+    "insert_action_in_step",
+    "remove_action_from_step",
+    "reorder_actions_in_step",
+    "get_step_actions",
+];
+
+/// Ways of capturing the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureMode {
+    /// Full-screen capture on demand
+    FullScreen,
+    /// Screenshot attached to each recorded click
+    OnClick,
+    /// Region capture for AI vision steps
+    VisionRegion,
+    /// Capture compared against a visual baseline
+    VisualAssert,
+    /// Annotated screenshot saved after each click during playback
+    ClickAnnotation,
+}
+
+/// How well a core supports a feature
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "support", rename_all = "snake_case")]
+pub enum SupportLevel {
+    Supported,
+    /// Works with limitations
+    Partial { note: String },
+    Unsupported { reason: String },
+}
+
+impl SupportLevel {
+    fn partial(note: &str) -> Self {
+        SupportLevel::Partial { note: note.to_string() }
+    }
+
+    fn unsupported(reason: &str) -> Self {
+        SupportLevel::Unsupported { reason: reason.to_string() }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, SupportLevel::Unsupported { .. })
+    }
+}
+
+/// Capabilities of one core on one platform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoreCapabilities {
+    pub core: CoreType,
+    pub platform: String,
+    /// Whether the core currently passes its health check
+    pub available: bool,
+    /// Keyed by the snake_case action type used in script files
+    pub action_types: BTreeMap<String, SupportLevel>,
+    pub capture_modes: BTreeMap<CaptureMode, SupportLevel>,
+    pub commands: BTreeMap<String, SupportLevel>,
+}
+
+impl CoreCapabilities {
+    /// Capabilities of `core` on `platform`
+    pub fn for_core(core: CoreType, platform: &str, available: bool) -> Self {
+        let (action_types, capture_modes, commands) = match core {
+            CoreType::Rust => rust_capabilities(platform),
+            CoreType::Python => python_capabilities(platform),
+        };
+
+        Self {
+            core,
+            platform: platform.to_string(),
+            available,
+            action_types,
+            capture_modes,
+            commands,
+        }
+    }
+
+    /// Support for an action type; unknown types are unsupported
+    pub fn action_support(&self, action_type: &ActionType) -> SupportLevel {
+        self.action_types
+            .get(&action_type_name(action_type))
+            .cloned()
+            .unwrap_or_else(|| SupportLevel::unsupported("unknown action type"))
+    }
+}
+
+/// Capabilities of every core on a platform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityMatrix {
+    pub platform: String,
+    pub cores: Vec<CoreCapabilities>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl CapabilityMatrix {
+    /// Build the matrix for `platform`, marking which cores are available
+    pub fn build(platform: &str, available_cores: &[CoreType]) -> Self {
+        let cores = [CoreType::Rust, CoreType::Python]
+            .into_iter()
+            .map(|core| {
+                let available = available_cores.contains(&core);
+                CoreCapabilities::for_core(core, platform, available)
+            })
+            .collect();
+
+        Self {
+            platform: platform.to_string(),
+            cores,
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Build the matrix for the current platform
+    pub fn current(available_cores: &[CoreType]) -> Self {
+        Self::build(std::env::consts::OS, available_cores)
+    }
+
+    pub fn core(&self, core: &CoreType) -> Option<&CoreCapabilities> {
+        self.cores.iter().find(|capabilities| &capabilities.core == core)
+    }
+
+    /// Warn about script steps `core` cannot fully execute on this platform
+    ///
+    /// One issue is reported per unsupported or partially supported action
+    /// type, listing the affected step indices.
+    pub fn check_script(&self, script: &ScriptData, core: &CoreType) -> Vec<CompatibilityIssue> {
+        let capabilities = match self.core(core) {
+            Some(capabilities) => capabilities,
+            None => return Vec::new(),
+        };
+
+        let mut limited: BTreeMap<String, (SupportLevel, Vec<usize>)> = BTreeMap::new();
+        for (index, action) in script.actions.iter().enumerate() {
+            let support = capabilities.action_support(&action.action_type);
+            if support != SupportLevel::Supported {
+                limited
+                    .entry(action_type_name(&action.action_type))
+                    .or_insert_with(|| (support, Vec::new()))
+                    .1
+                    .push(index);
+            }
+        }
+
+        limited
+            .into_iter()
+            .filter_map(|(action_type, (support, indices))| {
+                let field = format!("actions[{}]", join_indices(&indices));
+                match support {
+                    SupportLevel::Supported => None,
+                    SupportLevel::Partial { note } => {
+                        let fully_supported_elsewhere = self.cores.iter().find(|other| {
+                            &other.core != core && other.action_types.get(&action_type) == Some(&SupportLevel::Supported)
+                        });
+
+                        Some(CompatibilityIssue {
+                            severity: IssueSeverity::Warning,
+                            field,
+                            message: format!(
+                                "{} is only partially supported on the {} core on {}: {}",
+                                action_type, core, self.platform, note
+                            ),
+                            suggestion: fully_supported_elsewhere
+                                .map(|other| format!("Run this script on the {} core for full support", other.core)),
+                        })
+                    }
+                    SupportLevel::Unsupported { reason } => {
+                        let supported_elsewhere = self.cores.iter().find(|other| {
+                            &other.core != core && other.action_types.get(&action_type).is_some_and(SupportLevel::is_supported)
+                        });
+
+                        Some(CompatibilityIssue {
+                            severity: IssueSeverity::Warning,
+                            field,
+                            message: format!(
+                                "{} is not supported on the {} core on {}: {}",
+                                action_type, core, self.platform, reason
+                            ),
+                            suggestion: Some(match supported_elsewhere {
+                                Some(other) => format!("Run this script on the {} core", other.core),
+                                None => format!("Remove or replace the {} steps", action_type),
+                            }),
+                        })
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn join_indices(indices: &[usize]) -> String {
+    indices.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(",")
+}
+
+type CapabilityTables = (
+    BTreeMap<String, SupportLevel>,
+    BTreeMap<CaptureMode, SupportLevel>,
+    BTreeMap<String, SupportLevel>,
+);
+
+fn rust_capabilities(platform: &str) -> CapabilityTables {
+    let is_macos = platform == "macos";
+
+    let action_types = ALL_ACTION_TYPES
+        .iter()
+        .map(|action_type| {
+            let support = match action_type {
+                ActionType::VisualAssert if !is_macos => {
+                    SupportLevel::unsupported("visual assertion capture is only implemented on macOS")
+                }
+                ActionType::AiVisionCapture => {
+                    SupportLevel::partial("dynamic steps need a configured AI vision provider; without one only cached coordinates are used")
+                }
+                ActionType::Screenshot => SupportLevel::unsupported("screenshot steps are not executed during playback"),
+                ActionType::Custom => SupportLevel::unsupported("custom actions are not implemented"),
+                _ => SupportLevel::Supported,
+            };
+            (action_type_name(action_type), support)
+        })
+        .collect();
+
+    // Platform screenshot APIs are not implemented yet; macOS shells out to screencapture
+    let platform_capture = if is_macos {
+        SupportLevel::partial("uses the screencapture tool")
+    } else {
+        SupportLevel::unsupported("screen capture is not implemented for this platform")
+    };
+    // The recorder captures click screenshots the same way, so they share its support
+    let capture_modes = [
+        (CaptureMode::FullScreen, platform_capture.clone()),
+        (CaptureMode::OnClick, platform_capture.clone()),
+        (CaptureMode::VisionRegion, platform_capture.clone()),
+        (CaptureMode::VisualAssert, platform_capture.clone()),
+        (CaptureMode::ClickAnnotation, platform_capture),
+    ]
+    .into_iter()
+    .collect();
+
+    let commands = CORE_COMMANDS
+        .iter()
+        .map(|command| (command.to_string(), SupportLevel::Supported))
+        .chain(STEP_COMMANDS.iter().map(|command| {
+            (command.to_string(), SupportLevel::unsupported("step editing is only implemented by the Python core"))
+        }))
+        .collect();
+
+    (action_types, capture_modes, commands)
+}
+
+fn python_capabilities(platform: &str) -> CapabilityTables {
+    let action_types = ALL_ACTION_TYPES
+        .iter()
+        .map(|action_type| {
+            let support = match action_type {
+                ActionType::MouseMove
+                | ActionType::MouseClick
+                | ActionType::KeyPress
+                | ActionType::KeyRelease
+                | ActionType::AiVisionCapture => SupportLevel::Supported,
+                _ => SupportLevel::unsupported("ignored by the Python player"),
+            };
+            (action_type_name(action_type), support)
+        })
+        .collect();
+
+    // Pillow's ImageGrab needs an X11 session on Linux
+    let capture = if platform == "linux" {
+        SupportLevel::partial("requires an X11 session")
+    } else {
+        SupportLevel::Supported
+    };
+    let capture_modes = [
+        (CaptureMode::FullScreen, capture.clone()),
+        (CaptureMode::OnClick, capture.clone()),
+        (CaptureMode::VisionRegion, capture),
+        (CaptureMode::VisualAssert, SupportLevel::unsupported("visual assertions are only run by the Rust core")),
+        (
+            CaptureMode::ClickAnnotation,
+            SupportLevel::unsupported("the Python player ignores the annotate clicks option"),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let commands = CORE_COMMANDS
+        .iter()
+        .chain(STEP_COMMANDS.iter())
+        .map(|command| (command.to_string(), SupportLevel::Supported))
+        .collect();
+
+    (action_types, capture_modes, commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::Action;
+
+    fn action(action_type: ActionType) -> Action {
+        Action {
+            action_type,
+            timestamp: 0.0,
+            x: Some(10),
+            y: Some(10),
+            button: Some("left".to_string()),
+            key: None,
+            text: None,
+            modifiers: None,
+            additional_data: None,
+        }
+    }
+
+    #[test]
+    fn test_matrix_covers_every_action_type_for_each_core() {
+        let matrix = CapabilityMatrix::build("windows", &[CoreType::Rust]);
+
+        assert_eq!(matrix.cores.len(), 2);
+        for capabilities in &matrix.cores {
+            assert_eq!(capabilities.action_types.len(), ALL_ACTION_TYPES.len());
+        }
+        assert!(matrix.core(&CoreType::Rust).unwrap().available);
+        assert!(!matrix.core(&CoreType::Python).unwrap().available);
+        assert!(!matrix.core(&CoreType::Rust).unwrap().action_support(&ActionType::VisualAssert).is_supported());
+        assert!(CapabilityMatrix::build("macos", &[]).core(&CoreType::Rust).unwrap()
+            .action_support(&ActionType::VisualAssert).is_supported());
+    }
+
+    #[test]
+    fn test_click_annotations_are_only_drawn_by_the_rust_core() {
+        let matrix = CapabilityMatrix::build("macos", &[CoreType::Rust, CoreType::Python]);
+
+        assert!(matrix.core(&CoreType::Rust).unwrap().capture_modes[&CaptureMode::ClickAnnotation].is_supported());
+        assert!(!matrix.core(&CoreType::Python).unwrap().capture_modes[&CaptureMode::ClickAnnotation].is_supported());
+    }
+
+    #[test]
+    fn test_check_script_groups_unsupported_steps() {
+        let matrix = CapabilityMatrix::build("macos", &[CoreType::Rust, CoreType::Python]);
+        let mut script = ScriptData::new("rust", "macos");
+        script.actions = vec![
+            action(ActionType::MouseClick),
+            action(ActionType::MouseScroll),
+            action(ActionType::VisualAssert),
+            action(ActionType::MouseScroll),
+        ];
+
+        let issues = matrix.check_script(&script, &CoreType::Python);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].field, "actions[1,3]");
+        assert!(issues[0].message.starts_with("mouse_scroll is not supported on the python core"));
+        assert_eq!(issues[0].suggestion.as_deref(), Some("Run this script on the rust core"));
+
+        assert!(matrix.check_script(&script, &CoreType::Rust).is_empty());
+    }
+
+    #[test]
+    fn test_check_script_warns_about_partially_supported_steps() {
+        let matrix = CapabilityMatrix::build("macos", &[CoreType::Rust]);
+        let mut script = ScriptData::new("rust", "macos");
+        script.actions = vec![action(ActionType::MouseClick), action(ActionType::AiVisionCapture)];
+
+        let issues = matrix.check_script(&script, &CoreType::Rust);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "actions[1]");
+        assert!(issues[0].message.starts_with("ai_vision_capture is only partially supported on the rust core"));
+        assert_eq!(issues[0].suggestion.as_deref(), Some("Run this script on the python core for full support"));
+    }
+}
@@ -0,0 +1,374 @@
+//! Click annotations for post-run review
+//!
+//! During recording the click overlay only flashes a ripple where each click
+//! landed. For review, playback can capture the frame right after every click
+//! and bake a marker into it at the same position the overlay used, so the
+//! report shows exactly where each click hit the real UI. Annotated frames and
+//! a `click_annotations.json` manifest are written to the run's report
+//! directory.
+//!
+//! Only the capture happens on the playback thread; drawing and PNG encoding
+//! run on a worker so they do not delay the next action.
+
+use crate::logging::{get_logger, CoreType, LogLevel, OperationType};
+use crate::script::{Action, ActionType};
+use crate::{AutomationError, Result};
+use chrono::{DateTime, Utc};
+use image::{DynamicImage, Rgba, RgbaImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_hollow_circle_mut};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+/// Captured frames waiting to be annotated; more are dropped rather than
+/// blocking playback
+const ANNOTATION_QUEUE_CAPACITY: usize = 4;
+
+/// File name of the manifest written next to the annotated frames
+pub const ANNOTATION_MANIFEST_FILE: &str = "click_annotations.json";
+
+/// Settings for click annotations during playback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClickAnnotationConfig {
+    /// Capture and annotate a frame after every click
+    pub enabled: bool,
+    /// Where run reports go; defaults to `<scripts_directory>/reports`
+    pub output_directory: Option<PathBuf>,
+    /// Radius of the marker ring in screen points
+    pub marker_radius: u32,
+}
+
+impl Default for ClickAnnotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_directory: None,
+            marker_radius: 18,
+        }
+    }
+}
+
+/// Where a click landed, as shown by the live overlay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClickMarker {
+    pub action_index: usize,
+    pub loop_number: u32,
+    /// Screen coordinates in logical points
+    pub x: i32,
+    pub y: i32,
+    pub button: String,
+    pub double_click: bool,
+}
+
+impl ClickMarker {
+    /// Marker for a click action, or `None` for other actions
+    pub fn from_action(action: &Action, action_index: usize, loop_number: u32) -> Option<Self> {
+        let double_click = match action.action_type {
+            ActionType::MouseClick => false,
+            ActionType::MouseDoubleClick => true,
+            _ => return None,
+        };
+
+        Some(Self {
+            action_index,
+            loop_number,
+            x: action.x?,
+            y: action.y?,
+            button: action.button.clone().unwrap_or_else(|| "left".to_string()),
+            double_click,
+        })
+    }
+}
+
+/// An annotated frame stored with the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotatedScreenshot {
+    pub marker: ClickMarker,
+    /// Path relative to the report directory
+    pub path: String,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// All annotated frames of one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClickAnnotationReport {
+    pub run_id: String,
+    pub report_directory: PathBuf,
+    pub screenshots: Vec<AnnotatedScreenshot>,
+    /// Clicks whose frame could not be captured or saved
+    pub failed_captures: Vec<ClickMarker>,
+}
+
+type FrameSource = Box<dyn Fn() -> Result<DynamicImage> + Send>;
+
+/// A captured frame waiting for its marker
+struct AnnotationJob {
+    frame: DynamicImage,
+    marker: ClickMarker,
+    screen_size: Option<(u32, u32)>,
+    captured_at: DateTime<Utc>,
+}
+
+/// Frames the worker saved and clicks it could not save
+type WorkerOutput = (Vec<AnnotatedScreenshot>, Vec<ClickMarker>);
+
+/// Captures and annotates frames for the clicks of one playback run
+pub struct ClickAnnotator {
+    report: ClickAnnotationReport,
+    /// Logical screen size, used to map points onto frame pixels
+    screen_size: Option<(u32, u32)>,
+    frame_source: FrameSource,
+    jobs: Option<SyncSender<AnnotationJob>>,
+    worker: Option<JoinHandle<WorkerOutput>>,
+}
+
+impl ClickAnnotator {
+    /// Create an annotator writing to `<output_directory>/<run_id>`
+    pub fn new<F>(output_directory: &Path, run_id: &str, marker_radius: u32, screen_size: Option<(u32, u32)>, frame_source: F) -> Result<Self>
+    where
+        F: Fn() -> Result<DynamicImage> + Send + 'static,
+    {
+        let report_directory = output_directory.join(run_id);
+        fs::create_dir_all(&report_directory)?;
+
+        let (jobs, queue) = mpsc::sync_channel(ANNOTATION_QUEUE_CAPACITY);
+        let worker_directory = report_directory.clone();
+        let worker = thread::Builder::new()
+            .name("click-annotations".to_string())
+            .spawn(move || annotate_queued_frames(queue, &worker_directory, marker_radius))?;
+
+        Ok(Self {
+            report: ClickAnnotationReport {
+                run_id: run_id.to_string(),
+                report_directory,
+                screenshots: Vec::new(),
+                failed_captures: Vec::new(),
+            },
+            screen_size,
+            frame_source: Box::new(frame_source),
+            jobs: Some(jobs),
+            worker: Some(worker),
+        })
+    }
+
+    /// Update the logical screen size after the display layout changed
+    pub fn set_screen_size(&mut self, screen_size: Option<(u32, u32)>) {
+        self.screen_size = screen_size;
+    }
+
+    /// Capture the current frame and queue it for annotation if `action` is a click
+    ///
+    /// Capture failures are recorded in the report rather than returned, so a
+    /// missing frame never fails the run.
+    pub fn record_click(&mut self, action: &Action, action_index: usize, loop_number: u32) {
+        let marker = match ClickMarker::from_action(action, action_index, loop_number) {
+            Some(marker) => marker,
+            None => return,
+        };
+
+        let frame = match (self.frame_source)() {
+            Ok(frame) => frame,
+            Err(e) => {
+                log_capture_failure(&marker, &e);
+                self.report.failed_captures.push(marker);
+                return;
+            }
+        };
+
+        let job = AnnotationJob {
+            frame,
+            marker,
+            screen_size: self.screen_size,
+            captured_at: Utc::now(),
+        };
+        let (marker, reason) = match self.jobs.as_ref().map(|jobs| jobs.try_send(job)) {
+            Some(Ok(())) => return,
+            Some(Err(TrySendError::Full(job))) => (job.marker, "annotation queue is full"),
+            Some(Err(TrySendError::Disconnected(job))) => (job.marker, "annotation worker stopped"),
+            None => return,
+        };
+
+        log_capture_failure(&marker, &AutomationError::SystemError { message: reason.to_string() });
+        self.report.failed_captures.push(marker);
+    }
+
+    /// Wait for queued frames, write the manifest and return the report
+    pub fn finish(mut self) -> Result<ClickAnnotationReport> {
+        // Closing the queue lets the worker drain it and exit
+        self.jobs.take();
+        if let Some(worker) = self.worker.take() {
+            let (screenshots, failed) = worker.join().map_err(|_| AutomationError::SystemError {
+                message: "Click annotation worker panicked".to_string(),
+            })?;
+            self.report.screenshots.extend(screenshots);
+            self.report.failed_captures.extend(failed);
+            self.report
+                .failed_captures
+                .sort_by_key(|marker| (marker.loop_number, marker.action_index));
+        }
+
+        let manifest_path = self.report.report_directory.join(ANNOTATION_MANIFEST_FILE);
+        fs::write(&manifest_path, serde_json::to_string_pretty(&self.report)?)?;
+        Ok(self.report)
+    }
+}
+
+/// Annotate and save queued frames until the annotator closes the queue
+fn annotate_queued_frames(queue: Receiver<AnnotationJob>, report_directory: &Path, marker_radius: u32) -> WorkerOutput {
+    let mut screenshots = Vec::new();
+    let mut failed = Vec::new();
+
+    for job in queue {
+        let mut frame = job.frame.to_rgba8();
+        annotate_frame(&mut frame, &job.marker, job.screen_size, marker_radius);
+
+        let file_name = format!("click_{:04}_loop{}.png", job.marker.action_index, job.marker.loop_number);
+        let path = report_directory.join(&file_name);
+        match frame.save(&path) {
+            Ok(()) => screenshots.push(AnnotatedScreenshot {
+                marker: job.marker,
+                path: file_name,
+                captured_at: job.captured_at,
+            }),
+            Err(e) => {
+                let error = AutomationError::IoError {
+                    message: format!("Failed to save annotated screenshot {}: {}", path.display(), e),
+                };
+                log_capture_failure(&job.marker, &error);
+                failed.push(job.marker);
+            }
+        }
+    }
+
+    (screenshots, failed)
+}
+
+/// Load the annotation report stored in a report directory
+pub fn load_annotation_report(report_directory: &Path) -> Result<ClickAnnotationReport> {
+    let content = fs::read_to_string(report_directory.join(ANNOTATION_MANIFEST_FILE))?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Draw a click marker onto a frame
+///
+/// The frame may have more pixels than the logical screen (e.g. Retina), so
+/// the click position and radius are scaled by the frame/screen ratio.
+pub fn annotate_frame(frame: &mut RgbaImage, marker: &ClickMarker, screen_size: Option<(u32, u32)>, marker_radius: u32) {
+    let scale = match screen_size {
+        Some((width, _)) if width > 0 => frame.width() as f64 / width as f64,
+        _ => 1.0,
+    };
+    let center = ((marker.x as f64 * scale).round() as i32, (marker.y as f64 * scale).round() as i32);
+    let radius = ((marker_radius as f64 * scale).round() as i32).max(4);
+    let color = marker_color(&marker.button);
+
+    // Thick ring where the click landed, like the overlay ripple
+    for offset in 0..3 {
+        draw_hollow_circle_mut(frame, center, radius + offset, color);
+    }
+    if marker.double_click {
+        for offset in 0..2 {
+            draw_hollow_circle_mut(frame, center, radius + radius / 2 + offset, color);
+        }
+    }
+    draw_filled_circle_mut(frame, center, (radius / 6).max(2), color);
+}
+
+fn marker_color(button: &str) -> Rgba<u8> {
+    match button {
+        "right" => Rgba([66, 133, 244, 255]),
+        "middle" => Rgba([52, 168, 83, 255]),
+        _ => Rgba([234, 67, 53, 255]),
+    }
+}
+
+fn log_capture_failure(marker: &ClickMarker, error: &AutomationError) {
+    if let Some(logger) = get_logger() {
+        let mut metadata = HashMap::new();
+        metadata.insert("action_index".to_string(), serde_json::json!(marker.action_index));
+        metadata.insert("error".to_string(), serde_json::json!(error.to_string()));
+
+        logger.log_operation(
+            LogLevel::Warn,
+            CoreType::Rust,
+            OperationType::Playback,
+            format!("click_annotation_failed_{}", chrono::Utc::now().timestamp_millis()),
+            format!("Could not annotate click at action {}: {}", marker.action_index, error),
+            Some(metadata),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn click(action_type: ActionType, x: i32, y: i32) -> Action {
+        Action {
+            action_type,
+            timestamp: 0.0,
+            x: Some(x),
+            y: Some(y),
+            button: Some("left".to_string()),
+            key: None,
+            text: None,
+            modifiers: None,
+            additional_data: None,
+        }
+    }
+
+    #[test]
+    fn test_marker_is_scaled_onto_high_dpi_frame() {
+        let mut frame = RgbaImage::new(400, 200);
+        let marker = ClickMarker::from_action(&click(ActionType::MouseClick, 100, 50), 0, 1).unwrap();
+
+        annotate_frame(&mut frame, &marker, Some((200, 100)), 10);
+
+        // A 2x frame puts the click at (200, 100) and the ring 20px out
+        assert_eq!(*frame.get_pixel(200, 100), marker_color("left"));
+        assert_eq!(*frame.get_pixel(220, 100), marker_color("left"));
+        assert_eq!(frame.get_pixel(100, 50)[3], 0);
+    }
+
+    #[test]
+    fn test_annotator_writes_frames_and_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut annotator = ClickAnnotator::new(temp_dir.path(), "run-1", 10, Some((100, 100)), || {
+            Ok(DynamicImage::new_rgba8(100, 100))
+        })
+        .unwrap();
+
+        annotator.record_click(&click(ActionType::MouseClick, 10, 10), 0, 1);
+        annotator.record_click(&click(ActionType::KeyPress, 0, 0), 1, 1);
+        annotator.record_click(&click(ActionType::MouseDoubleClick, 50, 50), 2, 1);
+        let report = annotator.finish().unwrap();
+
+        assert_eq!(report.screenshots.len(), 2);
+        assert!(report.screenshots[1].marker.double_click);
+        assert!(report.report_directory.join(&report.screenshots[0].path).exists());
+
+        let loaded = load_annotation_report(&temp_dir.path().join("run-1")).unwrap();
+        assert_eq!(loaded.screenshots.len(), 2);
+    }
+
+    #[test]
+    fn test_capture_failures_do_not_fail_the_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut annotator = ClickAnnotator::new(temp_dir.path(), "run-2", 10, None, || {
+            Err(AutomationError::SystemError {
+                message: "no display".to_string(),
+            })
+        })
+        .unwrap();
+
+        annotator.record_click(&click(ActionType::MouseClick, 10, 10), 0, 1);
+        let report = annotator.finish().unwrap();
+
+        assert!(report.screenshots.is_empty());
+        assert_eq!(report.failed_captures.len(), 1);
+    }
+}
@@ -0,0 +1,62 @@
+//! Time source for playback timing
+//!
+//! Playback waits for each action's recorded offset, `Wait` steps and retry
+//! back-offs through a `Clock` instead of calling `thread::sleep` directly, so
+//! integration tests can swap in a virtual clock and replay long scripts
+//! instantly and deterministically.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Monotonic time source
+pub trait Clock: Send + Sync {
+    /// Time elapsed since the clock's origin
+    fn now(&self) -> Duration;
+
+    /// Block for `duration`
+    fn sleep(&self, duration: Duration);
+}
+
+/// Clock shared between a player and its playback thread
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// A new wall clock
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_advances_when_sleeping() {
+        let clock = SystemClock::default();
+        let before = clock.now();
+        clock.sleep(Duration::from_millis(5));
+        assert!(clock.now() - before >= Duration::from_millis(5));
+    }
+}
@@ -1,5 +1,6 @@
 //! Configuration types for the automation core
 
+use crate::click_annotations::ClickAnnotationConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -24,6 +25,10 @@ pub struct AutomationConfig {
     /// Screenshot capture interval in milliseconds
     pub screenshot_interval: u64,
     
+    /// Minimum time between recorded mouse moves in milliseconds
+    #[serde(default = "default_mouse_move_interval_ms")]
+    pub mouse_move_interval_ms: u64,
+    
     /// Maximum recording duration in seconds (0 = unlimited)
     pub max_recording_duration: u64,
     
@@ -32,6 +37,10 @@ pub struct AutomationConfig {
     
     /// Playback debug configuration
     pub debug_config: DebugConfig,
+    
+    /// Annotated click screenshots captured during playback
+    #[serde(default)]
+    pub click_annotations: ClickAnnotationConfig,
 }
 
 /// Debug configuration for playback
@@ -115,6 +124,10 @@ pub struct LinuxConfig {
     pub x11_display: Option<String>,
 }
 
+fn default_mouse_move_interval_ms() -> u64 {
+    50
+}
+
 impl Default for AutomationConfig {
     fn default() -> Self {
         Self {
@@ -124,9 +137,11 @@ impl Default for AutomationConfig {
             capture_screenshots: false,
             capture_screenshot_on_click: false,
             screenshot_interval: 1000,
+            mouse_move_interval_ms: default_mouse_move_interval_ms(),
             max_recording_duration: 0,
             platform_config: PlatformConfig::default(),
             debug_config: DebugConfig::default(),
+            click_annotations: ClickAnnotationConfig::default(),
         }
     }
 }
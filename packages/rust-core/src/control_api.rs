@@ -0,0 +1,755 @@
+//! Access control for the remote control API
+//!
+//! When the agent is exposed over REST/WebSocket, every remote invocation
+//! carries an API token. Tokens are granted scopes (read-only status, run
+//! scripts, manage baselines, admin) and each invocation is checked against
+//! the scope it requires and written to an audit log, whether it was allowed
+//! or denied. Only SHA-256 hashes of token secrets are kept. A persisted
+//! store appends audit records to a JSON Lines file next to its token file.
+//!
+//! No REST or WebSocket transport ships yet. Transports must hand every
+//! request to `ControlApiGateway::handle`, which authorizes it, confines
+//! script paths to the scripts directory, and only then runs the command.
+
+use crate::automation::{AutomationCommand, CommandResult};
+use crate::bounded_buffer::{BoundedBuffer, OverflowPolicy};
+use crate::logging::{get_logger, CoreType, LogLevel, OperationType};
+use crate::{AutomationError, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Prefix of generated token secrets, to make leaked tokens easy to spot
+const TOKEN_PREFIX: &str = "gqa_";
+
+/// Audit records kept in memory
+const DEFAULT_AUDIT_CAPACITY: usize = 5_000;
+
+/// Minimum age of a saved usage time before a new use rewrites the token file
+const LAST_USED_SAVE_INTERVAL_SECS: i64 = 60;
+
+/// Permission granted to an API token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiScope {
+    /// Query status, scripts and results
+    ReadStatus,
+    /// Start, pause and stop recording and playback
+    RunScripts,
+    /// Approve, update and delete visual baselines
+    ManageBaselines,
+    /// Everything, including script changes and token management
+    Admin,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ReadStatus => "read-status",
+            ApiScope::RunScripts => "run-scripts",
+            ApiScope::ManageBaselines => "manage-baselines",
+            ApiScope::Admin => "admin",
+        }
+    }
+}
+
+/// Scope required to execute an automation command remotely
+///
+/// Reading a script only needs read access because the gateway confines
+/// script paths to the scripts directory.
+pub fn required_scope(command: &AutomationCommand) -> ApiScope {
+    match command {
+        AutomationCommand::GetStatus
+        | AutomationCommand::CheckRecordings
+        | AutomationCommand::GetLatestRecording
+        | AutomationCommand::ListScripts
+        | AutomationCommand::LoadScript { .. } => ApiScope::ReadStatus,
+        AutomationCommand::StartRecording
+        | AutomationCommand::StopRecording
+        | AutomationCommand::StartPlayback { .. }
+        | AutomationCommand::StopPlayback
+        | AutomationCommand::PausePlayback => ApiScope::RunScripts,
+        // Writing or deleting scripts changes what later runs will do
+        AutomationCommand::SaveScript { .. } | AutomationCommand::DeleteScript { .. } => ApiScope::Admin,
+    }
+}
+
+/// Script path an automation command reads or writes, if any
+pub fn script_path(command: &AutomationCommand) -> Option<&str> {
+    match command {
+        AutomationCommand::StartPlayback { script_path, .. } => script_path.as_deref(),
+        AutomationCommand::LoadScript { path }
+        | AutomationCommand::SaveScript { path, .. }
+        | AutomationCommand::DeleteScript { path } => Some(path),
+        _ => None,
+    }
+}
+
+/// Stable name of an automation command for audit records
+pub fn command_name(command: &AutomationCommand) -> &'static str {
+    match command {
+        AutomationCommand::StartRecording => "start_recording",
+        AutomationCommand::StopRecording => "stop_recording",
+        AutomationCommand::StartPlayback { .. } => "start_playback",
+        AutomationCommand::StopPlayback => "stop_playback",
+        AutomationCommand::PausePlayback => "pause_playback",
+        AutomationCommand::CheckRecordings => "check_recordings",
+        AutomationCommand::GetLatestRecording => "get_latest_recording",
+        AutomationCommand::ListScripts => "list_scripts",
+        AutomationCommand::LoadScript { .. } => "load_script",
+        AutomationCommand::SaveScript { .. } => "save_script",
+        AutomationCommand::DeleteScript { .. } => "delete_script",
+        AutomationCommand::GetStatus => "get_status",
+    }
+}
+
+/// An issued API token (without its secret)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: String,
+    /// Human-readable label, e.g. the lab machine or CI job using it
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    /// Hex SHA-256 of the secret
+    pub secret_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    /// Whether this token grants `scope`; admin grants every scope
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        self.scopes.iter().any(|granted| *granted == scope || *granted == ApiScope::Admin)
+    }
+
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// Transport a remote invocation arrived on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlTransport {
+    Rest,
+    WebSocket,
+}
+
+/// Where a remote invocation came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestContext {
+    pub transport: ControlTransport,
+    pub remote_addr: Option<String>,
+}
+
+impl RequestContext {
+    pub fn new(transport: ControlTransport, remote_addr: Option<String>) -> Self {
+        Self { transport, remote_addr }
+    }
+}
+
+/// Outcome of an authorization check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Allowed,
+    Denied { reason: String },
+}
+
+/// One remote invocation, allowed or denied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Absent when the presented token was unknown
+    pub token_id: Option<String>,
+    pub token_name: Option<String>,
+    pub operation: String,
+    pub required_scope: ApiScope,
+    pub transport: ControlTransport,
+    pub remote_addr: Option<String>,
+    #[serde(flatten)]
+    pub outcome: AuditOutcome,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TokenFile {
+    tokens: Vec<ApiToken>,
+}
+
+struct StoreState {
+    /// Tokens keyed by secret hash
+    tokens: HashMap<String, ApiToken>,
+    audit_log: BoundedBuffer<AuditRecord>,
+}
+
+/// Issues tokens and authorizes remote invocations against their scopes
+#[derive(Clone)]
+pub struct ApiTokenStore {
+    state: Arc<Mutex<StoreState>>,
+    /// File tokens are persisted to, if any
+    path: Option<PathBuf>,
+    /// Append-only file audit records are written to, if any
+    audit_path: Option<PathBuf>,
+}
+
+impl Default for ApiTokenStore {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl ApiTokenStore {
+    /// Create a store that is not persisted
+    pub fn in_memory() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(StoreState {
+                tokens: HashMap::new(),
+                audit_log: BoundedBuffer::new("control_api_audit", DEFAULT_AUDIT_CAPACITY, OverflowPolicy::DropOldest),
+            })),
+            path: None,
+            audit_path: None,
+        }
+    }
+
+    /// Open a store persisted at `path`, loading existing tokens and recent audit records
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let audit_path = Self::audit_path_for(&path);
+        let store = Self {
+            path: Some(path.clone()),
+            audit_path: Some(audit_path.clone()),
+            ..Self::in_memory()
+        };
+
+        let mut state = store.state.lock().unwrap();
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            let file: TokenFile = serde_json::from_str(&content).map_err(|e| AutomationError::ConfigError {
+                message: format!("Failed to parse API token file {}: {}", path.display(), e),
+            })?;
+            state.tokens = file.tokens.into_iter().map(|token| (token.secret_hash.clone(), token)).collect();
+        }
+
+        if audit_path.exists() {
+            // A line cut short by a crash is skipped rather than failing the open
+            for line in fs::read_to_string(&audit_path)?.lines() {
+                if let Ok(record) = serde_json::from_str::<AuditRecord>(line) {
+                    state.audit_log.push(record);
+                }
+            }
+        }
+        drop(state);
+
+        Ok(store)
+    }
+
+    /// Audit file kept next to the token file at `path`
+    pub fn audit_path_for(path: &Path) -> PathBuf {
+        path.with_extension("audit.jsonl")
+    }
+
+    /// Default location of the token file
+    pub fn default_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("GeniusQA")
+            .join("api_tokens.json")
+    }
+
+    /// Issue a token, returning it along with its secret
+    ///
+    /// The secret is only available here; the store keeps its hash.
+    pub fn issue(&self, name: &str, scopes: Vec<ApiScope>, ttl: Option<Duration>) -> Result<(ApiToken, String)> {
+        if scopes.is_empty() {
+            return Err(AutomationError::InvalidInput {
+                message: "An API token needs at least one scope".to_string(),
+            });
+        }
+
+        let secret = format!("{}{}{}", TOKEN_PREFIX, uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+        let now = Utc::now();
+        let token = ApiToken {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            scopes,
+            secret_hash: hash_secret(&secret),
+            created_at: now,
+            expires_at: ttl.map(|ttl| now + ttl),
+            revoked: false,
+            last_used_at: None,
+        };
+
+        {
+            let mut state = self.state.lock().unwrap();
+            state.tokens.insert(token.secret_hash.clone(), token.clone());
+        }
+        self.save()?;
+
+        log_token_change(&token, "issued");
+        Ok((token, secret))
+    }
+
+    /// Revoke a token by id; returns false if no such token exists
+    pub fn revoke(&self, token_id: &str) -> Result<bool> {
+        let revoked = {
+            let mut state = self.state.lock().unwrap();
+            match state.tokens.values_mut().find(|token| token.id == token_id) {
+                Some(token) => {
+                    token.revoked = true;
+                    Some(token.clone())
+                }
+                None => None,
+            }
+        };
+
+        match revoked {
+            Some(token) => {
+                self.save()?;
+                log_token_change(&token, "revoked");
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// All tokens, oldest first
+    pub fn list(&self) -> Vec<ApiToken> {
+        let state = self.state.lock().unwrap();
+        let mut tokens: Vec<ApiToken> = state.tokens.values().cloned().collect();
+        tokens.sort_by_key(|token| token.created_at);
+        tokens
+    }
+
+    /// Check that `secret` grants `required` for `operation`, recording the attempt
+    pub fn authorize(
+        &self,
+        secret: &str,
+        operation: &str,
+        required: ApiScope,
+        context: &RequestContext,
+    ) -> Result<ApiToken> {
+        self.authorize_with(secret, operation, required, context, None)
+    }
+
+    /// Authorize an invocation, denying it with `violation` if the token is otherwise valid
+    fn authorize_with(
+        &self,
+        secret: &str,
+        operation: &str,
+        required: ApiScope,
+        context: &RequestContext,
+        violation: Option<String>,
+    ) -> Result<ApiToken> {
+        let now = Utc::now();
+        let mut save_usage = false;
+        let mut state = self.state.lock().unwrap();
+
+        let (token, outcome) = match state.tokens.get_mut(&hash_secret(secret)) {
+            None if secret.is_empty() => (None, AuditOutcome::Denied { reason: "missing token".to_string() }),
+            None => (None, AuditOutcome::Denied { reason: "unknown token".to_string() }),
+            Some(token) if token.revoked => {
+                (Some(token.clone()), AuditOutcome::Denied { reason: "token revoked".to_string() })
+            }
+            Some(token) if token.is_expired_at(now) => {
+                (Some(token.clone()), AuditOutcome::Denied { reason: "token expired".to_string() })
+            }
+            Some(token) if !token.has_scope(required) => (
+                Some(token.clone()),
+                AuditOutcome::Denied { reason: format!("missing scope {}", required.as_str()) },
+            ),
+            Some(token) => match violation {
+                Some(reason) => (Some(token.clone()), AuditOutcome::Denied { reason }),
+                None => {
+                    // Busy tokens only rewrite the token file once per interval
+                    save_usage = token.last_used_at.map_or(true, |last_used_at| {
+                        now - last_used_at >= Duration::seconds(LAST_USED_SAVE_INTERVAL_SECS)
+                    });
+                    token.last_used_at = Some(now);
+                    (Some(token.clone()), AuditOutcome::Allowed)
+                }
+            },
+        };
+
+        let record = AuditRecord {
+            timestamp: now,
+            token_id: token.as_ref().map(|t| t.id.clone()),
+            token_name: token.as_ref().map(|t| t.name.clone()),
+            operation: operation.to_string(),
+            required_scope: required,
+            transport: context.transport,
+            remote_addr: context.remote_addr.clone(),
+            outcome: outcome.clone(),
+        };
+        log_audit_record(&record);
+        // Appending under the lock keeps the file in the same order as the log
+        if let Err(e) = self.append_audit_record(&record) {
+            log_store_error(&format!("Failed to write API audit record: {}", e));
+        }
+        state.audit_log.push(record);
+        drop(state);
+
+        match (outcome, token) {
+            (AuditOutcome::Allowed, Some(token)) => {
+                // A failed write only loses the usage time, so the request still goes through
+                if save_usage {
+                    if let Err(e) = self.save() {
+                        log_store_error(&format!("Failed to persist API token usage: {}", e));
+                    }
+                }
+                Ok(token)
+            }
+            (AuditOutcome::Denied { reason }, _) => Err(AutomationError::PermissionDenied {
+                operation: format!("{} ({})", operation, reason),
+            }),
+            (AuditOutcome::Allowed, None) => unreachable!("allowed invocations always have a token"),
+        }
+    }
+
+    /// Authorize an automation command using its required scope
+    pub fn authorize_command(
+        &self,
+        secret: &str,
+        command: &AutomationCommand,
+        context: &RequestContext,
+    ) -> Result<ApiToken> {
+        self.authorize(secret, command_name(command), required_scope(command), context)
+    }
+
+    /// Authorize an automation command, denying script paths outside `scripts_directory`
+    pub fn authorize_command_in(
+        &self,
+        secret: &str,
+        command: &AutomationCommand,
+        context: &RequestContext,
+        scripts_directory: &Path,
+    ) -> Result<ApiToken> {
+        let violation = script_path(command)
+            .filter(|path| !is_within_directory(scripts_directory, path))
+            .map(|path| format!("script path {} is outside the scripts directory", path));
+        self.authorize_with(secret, command_name(command), required_scope(command), context, violation)
+    }
+
+    /// Most recent audit records, newest last
+    pub fn audit_log(&self, limit: Option<usize>) -> Vec<AuditRecord> {
+        let state = self.state.lock().unwrap();
+        let records = state.audit_log.to_vec();
+        match limit {
+            Some(limit) => records[records.len().saturating_sub(limit)..].to_vec(),
+            None => records,
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let file = TokenFile { tokens: self.list() };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write a sibling file and rename it over the old one, so a crash
+        // mid-write never leaves a truncated token file behind
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_string_pretty(&file)?)?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    fn append_audit_record(&self, record: &AuditRecord) -> Result<()> {
+        let audit_path = match &self.audit_path {
+            Some(audit_path) => audit_path,
+            None => return Ok(()),
+        };
+
+        if let Some(parent) = audit_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(audit_path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+}
+
+/// Runs an authorized automation command
+pub type CommandHandler = Arc<dyn Fn(AutomationCommand) -> Result<CommandResult> + Send + Sync>;
+
+/// The only way for a remote transport to run automation commands
+///
+/// Each request is authorized against the caller's token and audited before
+/// the handler sees it.
+#[derive(Clone)]
+pub struct ControlApiGateway {
+    tokens: ApiTokenStore,
+    scripts_directory: PathBuf,
+    handler: CommandHandler,
+}
+
+impl ControlApiGateway {
+    pub fn new<F>(tokens: ApiTokenStore, scripts_directory: impl Into<PathBuf>, handler: F) -> Self
+    where
+        F: Fn(AutomationCommand) -> Result<CommandResult> + Send + Sync + 'static,
+    {
+        Self {
+            tokens,
+            scripts_directory: scripts_directory.into(),
+            handler: Arc::new(handler),
+        }
+    }
+
+    pub fn tokens(&self) -> &ApiTokenStore {
+        &self.tokens
+    }
+
+    /// Authorize and run a remote command
+    ///
+    /// `authorization` is the raw `Authorization` header (or WebSocket
+    /// handshake field); a missing or malformed value is denied and audited.
+    pub fn handle(
+        &self,
+        authorization: Option<&str>,
+        command: AutomationCommand,
+        context: &RequestContext,
+    ) -> Result<CommandResult> {
+        let secret = authorization.and_then(bearer_token).unwrap_or("");
+        self.tokens
+            .authorize_command_in(secret, &command, context, &self.scripts_directory)?;
+        (self.handler)(command)
+    }
+}
+
+/// Extract the secret from a `Bearer <secret>` header value
+pub fn bearer_token(header: &str) -> Option<&str> {
+    let (scheme, secret) = header.trim().split_once(' ')?;
+    if scheme.eq_ignore_ascii_case("bearer") && !secret.trim().is_empty() {
+        Some(secret.trim())
+    } else {
+        None
+    }
+}
+
+/// Whether `path` (relative to `directory` unless absolute) stays inside `directory`
+fn is_within_directory(directory: &Path, path: &str) -> bool {
+    let candidate = Path::new(path);
+    if candidate.components().any(|component| component == Component::ParentDir) {
+        return false;
+    }
+
+    let candidate = if candidate.is_absolute() { candidate.to_path_buf() } else { directory.join(candidate) };
+    // Resolve symlinks when the file exists; new files are checked lexically
+    match (fs::canonicalize(directory), fs::canonicalize(&candidate)) {
+        (Ok(directory), Ok(candidate)) => candidate.starts_with(directory),
+        _ => candidate.starts_with(directory),
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(secret.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn log_token_change(token: &ApiToken, action: &str) {
+    if let Some(logger) = get_logger() {
+        let mut metadata = HashMap::new();
+        metadata.insert("token_id".to_string(), serde_json::json!(token.id));
+        metadata.insert("scopes".to_string(), serde_json::json!(token.scopes));
+
+        logger.log_operation(
+            LogLevel::Info,
+            CoreType::Rust,
+            OperationType::Configuration,
+            format!("api_token_{}_{}", action, token.id),
+            format!("API token '{}' {}", token.name, action),
+            Some(metadata),
+        );
+    }
+}
+
+fn log_store_error(message: &str) {
+    if let Some(logger) = get_logger() {
+        logger.log_operation(
+            LogLevel::Warn,
+            CoreType::Rust,
+            OperationType::Configuration,
+            format!("api_token_store_{}", Utc::now().timestamp_millis()),
+            message.to_string(),
+            None,
+        );
+    }
+}
+
+fn log_audit_record(record: &AuditRecord) {
+    if let Some(logger) = get_logger() {
+        let mut metadata = HashMap::new();
+        metadata.insert("operation".to_string(), serde_json::json!(record.operation));
+        metadata.insert("required_scope".to_string(), serde_json::json!(record.required_scope));
+        metadata.insert("transport".to_string(), serde_json::json!(record.transport));
+        metadata.insert("remote_addr".to_string(), serde_json::json!(record.remote_addr));
+        metadata.insert("token_id".to_string(), serde_json::json!(record.token_id));
+
+        let (level, message) = match &record.outcome {
+            AuditOutcome::Allowed => (
+                LogLevel::Info,
+                format!("Remote {} allowed for token '{}'", record.operation, record.token_name.as_deref().unwrap_or("?")),
+            ),
+            AuditOutcome::Denied { reason } => (
+                LogLevel::Warn,
+                format!("Remote {} denied: {}", record.operation, reason),
+            ),
+        };
+
+        logger.log_operation(
+            level,
+            CoreType::Rust,
+            OperationType::SystemIntegration,
+            format!("control_api_{}", record.timestamp.timestamp_millis()),
+            message,
+            Some(metadata),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rest() -> RequestContext {
+        RequestContext::new(ControlTransport::Rest, Some("10.0.0.7:51234".to_string()))
+    }
+
+    #[test]
+    fn test_scopes_gate_commands() {
+        let store = ApiTokenStore::in_memory();
+        let (_, reader) = store.issue("dashboard", vec![ApiScope::ReadStatus], None).unwrap();
+        let (_, admin) = store.issue("ops", vec![ApiScope::Admin], None).unwrap();
+
+        assert!(store.authorize_command(&reader, &AutomationCommand::GetStatus, &rest()).is_ok());
+        assert!(store.authorize_command(&reader, &AutomationCommand::StopPlayback, &rest()).is_err());
+        assert!(store.authorize(&reader, "approve_baseline", ApiScope::ManageBaselines, &rest()).is_err());
+        assert!(store.authorize(&admin, "approve_baseline", ApiScope::ManageBaselines, &rest()).is_ok());
+        assert!(store.authorize_command("gqa_bogus", &AutomationCommand::GetStatus, &rest()).is_err());
+
+        let log = store.audit_log(None);
+        assert_eq!(log.len(), 5);
+        assert_eq!(log[0].outcome, AuditOutcome::Allowed);
+        assert_eq!(log[1].outcome, AuditOutcome::Denied { reason: "missing scope run-scripts".to_string() });
+        assert_eq!(log[4].token_id, None);
+        assert_eq!(log[4].remote_addr.as_deref(), Some("10.0.0.7:51234"));
+    }
+
+    #[test]
+    fn test_revoked_and_expired_tokens_are_denied() {
+        let store = ApiTokenStore::in_memory();
+        let (token, secret) = store.issue("ci", vec![ApiScope::RunScripts], None).unwrap();
+        let (_, expired) = store.issue("old", vec![ApiScope::RunScripts], Some(Duration::seconds(-1))).unwrap();
+
+        assert!(store.authorize(&secret, "start_playback", ApiScope::RunScripts, &rest()).is_ok());
+        assert!(store.revoke(&token.id).unwrap());
+        assert!(store.authorize(&secret, "start_playback", ApiScope::RunScripts, &rest()).is_err());
+        assert!(store.authorize(&expired, "start_playback", ApiScope::RunScripts, &rest()).is_err());
+
+        let reasons: Vec<_> = store.audit_log(Some(2)).into_iter().map(|r| r.outcome).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                AuditOutcome::Denied { reason: "token revoked".to_string() },
+                AuditOutcome::Denied { reason: "token expired".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gateway_authorizes_every_request_and_confines_script_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let scripts = temp_dir.path().join("scripts");
+        fs::create_dir_all(&scripts).unwrap();
+        fs::write(scripts.join("login.json"), "{}").unwrap();
+
+        let store = ApiTokenStore::in_memory();
+        let (_, reader) = store.issue("dashboard", vec![ApiScope::ReadStatus], None).unwrap();
+        let executed = Arc::new(Mutex::new(Vec::new()));
+        let executed_in_handler = Arc::clone(&executed);
+        let gateway = ControlApiGateway::new(store.clone(), &scripts, move |command| {
+            executed_in_handler.lock().unwrap().push(command_name(&command));
+            Ok(CommandResult::Success)
+        });
+
+        let header = format!("Bearer {}", reader);
+        let load = |path: &str| AutomationCommand::LoadScript { path: path.to_string() };
+        assert!(gateway.handle(Some(&header), load("login.json"), &rest()).is_ok());
+        assert!(gateway.handle(Some(&header), load("../../etc/passwd"), &rest()).is_err());
+        assert!(gateway.handle(Some(&header), load("/etc/passwd"), &rest()).is_err());
+        assert!(gateway.handle(None, AutomationCommand::GetStatus, &rest()).is_err());
+        assert!(gateway.handle(Some(&header), AutomationCommand::StopPlayback, &rest()).is_err());
+
+        assert_eq!(*executed.lock().unwrap(), vec!["load_script"]);
+        let reasons: Vec<_> = store.audit_log(None).into_iter().map(|r| r.outcome).collect();
+        assert_eq!(reasons.len(), 5);
+        assert_eq!(
+            reasons[1],
+            AuditOutcome::Denied { reason: "script path ../../etc/passwd is outside the scripts directory".to_string() }
+        );
+        assert_eq!(reasons[3], AuditOutcome::Denied { reason: "missing token".to_string() });
+    }
+
+    #[test]
+    fn test_tokens_persist_without_secrets() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("api_tokens.json");
+
+        let store = ApiTokenStore::open(&path).unwrap();
+        let (_, secret) = store.issue("lab-runner", vec![ApiScope::RunScripts], None).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains(&secret));
+
+        let reopened = ApiTokenStore::open(&path).unwrap();
+        assert_eq!(reopened.list().len(), 1);
+        assert!(reopened.authorize(&secret, "stop_playback", ApiScope::RunScripts, &rest()).is_ok());
+
+        // Usage time survives a restart
+        let last_used_at = ApiTokenStore::open(&path).unwrap().list()[0].last_used_at;
+        assert!(last_used_at.is_some());
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_repeated_use_does_not_rewrite_the_token_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("api_tokens.json");
+
+        let store = ApiTokenStore::open(&path).unwrap();
+        let (_, secret) = store.issue("lab-runner", vec![ApiScope::RunScripts], None).unwrap();
+        assert!(store.authorize(&secret, "stop_playback", ApiScope::RunScripts, &rest()).is_ok());
+        let saved = fs::read_to_string(&path).unwrap();
+
+        assert!(store.authorize(&secret, "stop_playback", ApiScope::RunScripts, &rest()).is_ok());
+        assert_eq!(fs::read_to_string(&path).unwrap(), saved);
+    }
+
+    #[test]
+    fn test_audit_records_survive_a_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("api_tokens.json");
+
+        let store = ApiTokenStore::open(&path).unwrap();
+        let (_, secret) = store.issue("lab-runner", vec![ApiScope::ReadStatus], None).unwrap();
+        assert!(store.authorize_command(&secret, &AutomationCommand::GetStatus, &rest()).is_ok());
+        assert!(store.authorize_command(&secret, &AutomationCommand::StopPlayback, &rest()).is_err());
+
+        let reopened = ApiTokenStore::open(&path).unwrap();
+        let log = reopened.audit_log(None);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].operation, "get_status");
+        assert_eq!(log[1].outcome, AuditOutcome::Denied { reason: "missing scope run-scripts".to_string() });
+        assert!(!fs::read_to_string(ApiTokenStore::audit_path_for(&path)).unwrap().contains(&secret));
+    }
+}
@@ -0,0 +1,257 @@
+//! Crash-loop protection for restartable subsystems
+//!
+//! Tracks restarts of long-running subsystems (focus service, Python core,
+//! AI provider). When a subsystem restarts more than the configured number
+//! of times within the time window, its circuit breaker opens and further
+//! restarts are refused until the breaker is reset manually.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+
+/// Subsystems protected by a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    FocusService,
+    PythonCore,
+    AiProvider,
+}
+
+impl std::fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subsystem::FocusService => write!(f, "focus_service"),
+            Subsystem::PythonCore => write!(f, "python_core"),
+            Subsystem::AiProvider => write!(f, "ai_provider"),
+        }
+    }
+}
+
+/// Crash-loop detection thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashLoopConfig {
+    /// Restarts allowed within the window before the breaker opens
+    pub max_restarts: u32,
+    pub window_minutes: u64,
+    /// Number of crash reasons kept per subsystem
+    pub max_recorded_crashes: usize,
+}
+
+impl Default for CrashLoopConfig {
+    fn default() -> Self {
+        Self {
+            max_restarts: 3,
+            window_minutes: 5,
+            max_recorded_crashes: 10,
+        }
+    }
+}
+
+/// State of a subsystem's circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Restarts are allowed
+    Closed,
+    /// Restarts are refused until a manual reset
+    Open,
+}
+
+/// A crash that led to a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashRecord {
+    pub reason: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Snapshot of a subsystem's circuit breaker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerStatus {
+    pub subsystem: Subsystem,
+    pub state: CircuitState,
+    /// Restarts within the current window
+    pub recent_restarts: usize,
+    pub crashes: Vec<CrashRecord>,
+    pub tripped_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of recording a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartDecision {
+    /// Whether the caller may restart the subsystem
+    pub allowed: bool,
+    /// True only for the restart that opened the breaker
+    pub tripped: bool,
+    pub status: CircuitBreakerStatus,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    restarts: Vec<DateTime<Utc>>,
+    crashes: Vec<CrashRecord>,
+    tripped_at: Option<DateTime<Utc>>,
+}
+
+/// Per-subsystem restart tracking shared between clones
+#[derive(Debug, Clone, Default)]
+pub struct CrashLoopGuard {
+    config: CrashLoopConfig,
+    breakers: Arc<Mutex<HashMap<Subsystem, BreakerState>>>,
+}
+
+impl CrashLoopGuard {
+    /// Create a guard with the given thresholds
+    pub fn new(config: CrashLoopConfig) -> Self {
+        Self {
+            config,
+            breakers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Record that a subsystem crashed and is about to be restarted
+    pub fn record_restart(&self, subsystem: Subsystem, reason: &str) -> RestartDecision {
+        self.record_restart_at(subsystem, reason, Utc::now())
+    }
+
+    fn record_restart_at(&self, subsystem: Subsystem, reason: &str, now: DateTime<Utc>) -> RestartDecision {
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = breakers.entry(subsystem).or_default();
+
+        breaker.crashes.push(CrashRecord {
+            reason: reason.to_string(),
+            occurred_at: now,
+        });
+        if breaker.crashes.len() > self.config.max_recorded_crashes {
+            let excess = breaker.crashes.len() - self.config.max_recorded_crashes;
+            breaker.crashes.drain(0..excess);
+        }
+
+        if breaker.tripped_at.is_some() {
+            return RestartDecision {
+                allowed: false,
+                tripped: false,
+                status: self.snapshot(subsystem, breaker, now),
+            };
+        }
+
+        let window_start = now - chrono::Duration::minutes(self.config.window_minutes as i64);
+        breaker.restarts.retain(|t| *t > window_start);
+        breaker.restarts.push(now);
+
+        let tripped = breaker.restarts.len() > self.config.max_restarts as usize;
+        if tripped {
+            breaker.tripped_at = Some(now);
+        }
+
+        RestartDecision {
+            allowed: !tripped,
+            tripped,
+            status: self.snapshot(subsystem, breaker, now),
+        }
+    }
+
+    /// Whether the subsystem may currently be restarted
+    pub fn can_restart(&self, subsystem: Subsystem) -> bool {
+        let breakers = self.breakers.lock().unwrap();
+        breakers
+            .get(&subsystem)
+            .map(|b| b.tripped_at.is_none())
+            .unwrap_or(true)
+    }
+
+    /// Close the breaker and forget the restart history
+    ///
+    /// Returns true if the breaker was open.
+    pub fn reset(&self, subsystem: Subsystem) -> bool {
+        let mut breakers = self.breakers.lock().unwrap();
+        breakers
+            .remove(&subsystem)
+            .map(|b| b.tripped_at.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Status of a single subsystem
+    pub fn status(&self, subsystem: Subsystem) -> CircuitBreakerStatus {
+        let mut breakers = self.breakers.lock().unwrap();
+        let breaker = breakers.entry(subsystem).or_default();
+        self.snapshot(subsystem, breaker, Utc::now())
+    }
+
+    /// Status of every subsystem that has restarted at least once
+    pub fn statuses(&self) -> Vec<CircuitBreakerStatus> {
+        let breakers = self.breakers.lock().unwrap();
+        let now = Utc::now();
+        breakers
+            .iter()
+            .map(|(subsystem, breaker)| self.snapshot(*subsystem, breaker, now))
+            .collect()
+    }
+
+    fn snapshot(&self, subsystem: Subsystem, breaker: &BreakerState, now: DateTime<Utc>) -> CircuitBreakerStatus {
+        let window_start = now - chrono::Duration::minutes(self.config.window_minutes as i64);
+        CircuitBreakerStatus {
+            subsystem,
+            state: if breaker.tripped_at.is_some() { CircuitState::Open } else { CircuitState::Closed },
+            recent_restarts: breaker.restarts.iter().filter(|t| **t > window_start).count(),
+            crashes: breaker.crashes.clone(),
+            tripped_at: breaker.tripped_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_trips_after_too_many_restarts() {
+        let guard = CrashLoopGuard::new(CrashLoopConfig::default());
+        let now = Utc::now();
+
+        for i in 0..3 {
+            let decision = guard.record_restart_at(Subsystem::PythonCore, &format!("crash {}", i), now);
+            assert!(decision.allowed);
+        }
+
+        let decision = guard.record_restart_at(Subsystem::PythonCore, "crash 3", now);
+        assert!(!decision.allowed);
+        assert!(decision.tripped);
+        assert_eq!(decision.status.state, CircuitState::Open);
+        assert_eq!(decision.status.crashes.len(), 4);
+
+        // Further restarts stay blocked without re-tripping
+        let decision = guard.record_restart_at(Subsystem::PythonCore, "crash 4", now);
+        assert!(!decision.allowed);
+        assert!(!decision.tripped);
+
+        // Other subsystems are unaffected
+        assert!(guard.can_restart(Subsystem::FocusService));
+    }
+
+    #[test]
+    fn test_restarts_outside_window_are_forgotten() {
+        let guard = CrashLoopGuard::new(CrashLoopConfig::default());
+        let start = Utc::now() - chrono::Duration::minutes(30);
+
+        for i in 0..3 {
+            guard.record_restart_at(Subsystem::FocusService, "crash", start + chrono::Duration::minutes(i * 10));
+        }
+
+        assert!(guard.record_restart_at(Subsystem::FocusService, "crash", Utc::now()).allowed);
+    }
+
+    #[test]
+    fn test_manual_reset_closes_breaker() {
+        let guard = CrashLoopGuard::new(CrashLoopConfig { max_restarts: 0, ..Default::default() });
+
+        assert!(guard.record_restart(Subsystem::AiProvider, "timeout").tripped);
+        assert!(!guard.can_restart(Subsystem::AiProvider));
+
+        assert!(guard.reset(Subsystem::AiProvider));
+        assert!(guard.can_restart(Subsystem::AiProvider));
+        assert_eq!(guard.status(Subsystem::AiProvider).state, CircuitState::Closed);
+        assert!(!guard.reset(Subsystem::AiProvider));
+    }
+}
@@ -0,0 +1,314 @@
+//! Display configuration change detection
+//!
+//! Monitors being attached or detached, or changing resolution or scale
+//! while the app runs, invalidate cached screen dimensions and make recorded
+//! coordinates land in the wrong place. `DisplayMonitor` polls the display
+//! layout, publishes a `CoreEvent::DisplayChanged` for every difference, and
+//! invalidates the shared platform cache so the next query sees the new
+//! layout. Playback subscribes to these events and pauses with a reason.
+
+use crate::events::{event_bus, CoreEvent};
+use crate::logging::{get_logger, CoreType, LogLevel, OperationType};
+use crate::platform::{platform_cache, PlatformHandleCache};
+use crate::resource_profile::configured_resource_profile;
+use crate::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Default interval between display layout checks
+pub const DEFAULT_DISPLAY_POLL_INTERVAL_MS: u64 = 2000;
+
+/// Scale factors closer than this are considered equal
+const SCALE_EPSILON: f64 = 0.01;
+
+/// A connected display
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DisplayInfo {
+    /// Platform display identifier
+    pub id: String,
+    /// Origin in the global desktop coordinate space
+    pub x: i32,
+    pub y: i32,
+    /// Size in logical points
+    pub width: u32,
+    pub height: u32,
+    /// Physical pixels per logical point
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+impl DisplayInfo {
+    /// A primary display at the origin with a scale factor of 1
+    pub fn primary(width: u32, height: u32) -> Self {
+        Self {
+            id: "primary".to_string(),
+            x: 0,
+            y: 0,
+            width,
+            height,
+            scale_factor: 1.0,
+            is_primary: true,
+        }
+    }
+}
+
+/// Kind of display configuration change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayChangeKind {
+    Attached,
+    Detached,
+    ResolutionChanged,
+    ScaleChanged,
+}
+
+/// A single change between two display layouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayChangeEvent {
+    pub kind: DisplayChangeKind,
+    pub display_id: String,
+    /// The display before the change (absent when attached)
+    pub previous: Option<DisplayInfo>,
+    /// The display after the change (absent when detached)
+    pub current: Option<DisplayInfo>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl DisplayChangeEvent {
+    /// Human-readable description, used as the playback pause reason
+    pub fn description(&self) -> String {
+        match (self.kind, &self.previous, &self.current) {
+            (DisplayChangeKind::Attached, _, Some(current)) => {
+                format!("display {} attached ({}x{})", self.display_id, current.width, current.height)
+            }
+            (DisplayChangeKind::Detached, _, _) => format!("display {} detached", self.display_id),
+            (DisplayChangeKind::ResolutionChanged, Some(previous), Some(current)) => format!(
+                "display {} resolution changed from {}x{} to {}x{}",
+                self.display_id, previous.width, previous.height, current.width, current.height
+            ),
+            (DisplayChangeKind::ScaleChanged, Some(previous), Some(current)) => format!(
+                "display {} scale changed from {:.2} to {:.2}",
+                self.display_id, previous.scale_factor, current.scale_factor
+            ),
+            _ => format!("display {} changed", self.display_id),
+        }
+    }
+}
+
+/// Compare two display layouts and list the changes
+pub fn diff_displays(previous: &[DisplayInfo], current: &[DisplayInfo]) -> Vec<DisplayChangeEvent> {
+    let now = Utc::now();
+    let previous_by_id: HashMap<&str, &DisplayInfo> = previous.iter().map(|d| (d.id.as_str(), d)).collect();
+    let current_by_id: HashMap<&str, &DisplayInfo> = current.iter().map(|d| (d.id.as_str(), d)).collect();
+    let change = |kind, id: &str, previous: Option<&DisplayInfo>, current: Option<&DisplayInfo>| DisplayChangeEvent {
+        kind,
+        display_id: id.to_string(),
+        previous: previous.cloned(),
+        current: current.cloned(),
+        detected_at: now,
+    };
+
+    let mut changes = Vec::new();
+    for display in previous {
+        if !current_by_id.contains_key(display.id.as_str()) {
+            changes.push(change(DisplayChangeKind::Detached, &display.id, Some(display), None));
+        }
+    }
+
+    for display in current {
+        match previous_by_id.get(display.id.as_str()) {
+            None => changes.push(change(DisplayChangeKind::Attached, &display.id, None, Some(display))),
+            Some(&old) => {
+                if (old.width, old.height, old.x, old.y) != (display.width, display.height, display.x, display.y) {
+                    changes.push(change(DisplayChangeKind::ResolutionChanged, &display.id, Some(old), Some(display)));
+                }
+                if (old.scale_factor - display.scale_factor).abs() > SCALE_EPSILON {
+                    changes.push(change(DisplayChangeKind::ScaleChanged, &display.id, Some(old), Some(display)));
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+type DisplaySource = Arc<dyn Fn() -> Result<Vec<DisplayInfo>> + Send + Sync>;
+
+/// Polls the display layout and reports changes
+#[derive(Clone)]
+pub struct DisplayMonitor {
+    source: DisplaySource,
+    /// Cache invalidated on changes, taken from the creating thread
+    platform_cache: Arc<PlatformHandleCache>,
+    poll_interval: Duration,
+    last_layout: Arc<Mutex<Option<Vec<DisplayInfo>>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl Default for DisplayMonitor {
+    fn default() -> Self {
+        Self::new(configured_resource_profile().poll_interval(Duration::from_millis(DEFAULT_DISPLAY_POLL_INTERVAL_MS)))
+    }
+}
+
+impl DisplayMonitor {
+    /// Create a monitor that reads the layout from the shared platform handle
+    pub fn new(poll_interval: Duration) -> Self {
+        let platform_cache = platform_cache();
+        Self::with_source(poll_interval, move || platform_cache.get()?.get_displays())
+    }
+
+    /// Create a monitor with a custom layout source
+    pub fn with_source<F>(poll_interval: Duration, source: F) -> Self
+    where
+        F: Fn() -> Result<Vec<DisplayInfo>> + Send + Sync + 'static,
+    {
+        Self {
+            source: Arc::new(source),
+            platform_cache: platform_cache(),
+            poll_interval,
+            last_layout: Arc::new(Mutex::new(None)),
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Check the layout once, publishing and returning any changes
+    ///
+    /// The first successful check only records the baseline layout.
+    pub fn poll(&self) -> Result<Vec<DisplayChangeEvent>> {
+        let layout = (self.source)()?;
+        let changes = {
+            let mut last_layout = self.last_layout.lock().unwrap();
+            let changes = match last_layout.as_ref() {
+                Some(previous) => diff_displays(previous, &layout),
+                None => Vec::new(),
+            };
+            *last_layout = Some(layout);
+            changes
+        };
+
+        if !changes.is_empty() {
+            // Cached dimensions (and the handle bound to the old layout) are stale
+            self.platform_cache.invalidate();
+
+            for change in &changes {
+                log_display_change(change);
+                event_bus().publish(CoreEvent::DisplayChanged(change.clone()));
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// The most recently observed layout
+    pub fn current_layout(&self) -> Option<Vec<DisplayInfo>> {
+        self.last_layout.lock().unwrap().clone()
+    }
+
+    /// Start polling in a background thread; does nothing if already running
+    pub fn start(&self) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let monitor = self.clone();
+        thread::spawn(move || {
+            while monitor.running.load(Ordering::SeqCst) {
+                // Transient failures (e.g. no display during sleep) are retried next tick
+                let _ = monitor.poll();
+                thread::sleep(monitor.poll_interval);
+            }
+        });
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+fn log_display_change(change: &DisplayChangeEvent) {
+    if let Some(logger) = get_logger() {
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!(change.kind));
+        metadata.insert("display_id".to_string(), serde_json::json!(change.display_id));
+
+        logger.log_operation(
+            LogLevel::Info,
+            CoreType::Rust,
+            OperationType::SystemIntegration,
+            format!("display_change_{}", change.detected_at.timestamp_millis()),
+            format!("Display configuration changed: {}", change.description()),
+            Some(metadata),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(id: &str, width: u32, height: u32, scale_factor: f64) -> DisplayInfo {
+        DisplayInfo {
+            id: id.to_string(),
+            x: 0,
+            y: 0,
+            width,
+            height,
+            scale_factor,
+            is_primary: id == "1",
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_each_change_kind() {
+        let previous = vec![display("1", 1920, 1080, 1.0), display("2", 1280, 720, 1.0)];
+        let current = vec![display("1", 2560, 1440, 2.0), display("3", 1920, 1080, 1.0)];
+
+        let kinds: Vec<_> = diff_displays(&previous, &current).iter().map(|c| (c.kind, c.display_id.clone())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (DisplayChangeKind::Detached, "2".to_string()),
+                (DisplayChangeKind::ResolutionChanged, "1".to_string()),
+                (DisplayChangeKind::ScaleChanged, "1".to_string()),
+                (DisplayChangeKind::Attached, "3".to_string()),
+            ]
+        );
+        assert!(diff_displays(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_poll_publishes_changes_after_baseline() {
+        let layout = Arc::new(Mutex::new(vec![display("1", 1920, 1080, 1.0)]));
+        let source_layout = Arc::clone(&layout);
+        let monitor = DisplayMonitor::with_source(Duration::from_millis(10), move || {
+            Ok(source_layout.lock().unwrap().clone())
+        });
+        let mut events = event_bus().subscribe();
+
+        assert!(monitor.poll().unwrap().is_empty());
+
+        layout.lock().unwrap().push(display("2", 1280, 720, 1.0));
+        let changes = monitor.poll().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, DisplayChangeKind::Attached);
+
+        // Other tests may publish on the shared bus, so look for our event
+        let mut found = false;
+        while let Ok(event) = events.try_recv() {
+            if let Some(change) = event.as_display_change() {
+                found |= change.display_id == "2" && change.kind == DisplayChangeKind::Attached;
+            }
+        }
+        assert!(found);
+    }
+}
@@ -0,0 +1,362 @@
+//! Pre-run environment validation gates
+//!
+//! Suites can declare environment gates (required applications, minimum screen
+//! resolution, OS version, permissions) that are checked before any script runs.
+//! When a gate fails, the suite is reported as skipped with a reason instead of
+//! failing midway through playback.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use crate::platform::{platform_cache, PlatformAutomation};
+
+/// A single environment requirement that must hold before a suite runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "gate", rename_all = "snake_case")]
+pub enum EnvironmentGate {
+    /// An application must be installed at the given path
+    AppInstalled {
+        path: PathBuf,
+        #[serde(default)]
+        name: Option<String>,
+    },
+    /// The primary screen must be at least this large
+    MinScreenResolution { width: u32, height: u32 },
+    /// The host OS must match, optionally at or above a minimum version
+    OsVersion {
+        os: String,
+        #[serde(default)]
+        min_version: Option<String>,
+    },
+    /// A system permission must be granted
+    Permission { permission: RequiredPermission },
+}
+
+/// System permissions that can be required by a gate
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequiredPermission {
+    /// Mouse and keyboard control (Accessibility on macOS)
+    InputControl,
+    /// Ability to capture the screen (Screen Recording on macOS)
+    ScreenCapture,
+}
+
+impl std::fmt::Display for RequiredPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequiredPermission::InputControl => write!(f, "input_control"),
+            RequiredPermission::ScreenCapture => write!(f, "screen_capture"),
+        }
+    }
+}
+
+/// Snapshot of the host environment that gates are evaluated against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentSnapshot {
+    pub os: String,
+    pub os_version: Option<String>,
+    pub screen_resolution: Option<(u32, u32)>,
+    pub granted_permissions: Vec<RequiredPermission>,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Outcome of evaluating a single gate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateCheckResult {
+    pub gate: EnvironmentGate,
+    pub passed: bool,
+    pub reason: Option<String>,
+}
+
+/// Outcome of evaluating all gates of a suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentGateReport {
+    pub passed: bool,
+    pub results: Vec<GateCheckResult>,
+    pub snapshot: EnvironmentSnapshot,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl EnvironmentSnapshot {
+    /// Collect a snapshot of the current host environment
+    pub fn collect() -> Self {
+        let cache = platform_cache();
+        match cache.get() {
+            Ok(platform) => Self::from_platform(&*platform, cache.screen_size().ok()),
+            Err(_) => Self::new(None, Vec::new()),
+        }
+    }
+
+    /// Collect a snapshot with the permissions granted to `platform`
+    pub fn from_platform(platform: &dyn PlatformAutomation, screen_resolution: Option<(u32, u32)>) -> Self {
+        let mut granted_permissions = Vec::new();
+
+        if platform.check_permissions().unwrap_or(false) {
+            granted_permissions.push(RequiredPermission::InputControl);
+        }
+
+        if platform.check_screen_capture_permission().unwrap_or(false) {
+            granted_permissions.push(RequiredPermission::ScreenCapture);
+        }
+
+        Self::new(screen_resolution, granted_permissions)
+    }
+
+    fn new(screen_resolution: Option<(u32, u32)>, granted_permissions: Vec<RequiredPermission>) -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            os_version: detect_os_version(),
+            screen_resolution,
+            granted_permissions,
+            captured_at: Utc::now(),
+        }
+    }
+}
+
+impl EnvironmentGateReport {
+    /// Human-readable reason the suite should be skipped, if any gate failed
+    pub fn skip_reason(&self) -> Option<String> {
+        if self.passed {
+            return None;
+        }
+
+        let reasons: Vec<String> = self
+            .results
+            .iter()
+            .filter(|r| !r.passed)
+            .filter_map(|r| r.reason.clone())
+            .collect();
+
+        Some(format!("Environment gates not satisfied: {}", reasons.join("; ")))
+    }
+}
+
+/// Evaluates environment gates against an environment snapshot
+pub struct EnvironmentGateChecker;
+
+impl EnvironmentGateChecker {
+    /// Collect the current environment and evaluate the gates against it
+    pub fn check(gates: &[EnvironmentGate]) -> EnvironmentGateReport {
+        Self::check_with_snapshot(gates, EnvironmentSnapshot::collect())
+    }
+
+    /// Evaluate the gates against a previously collected snapshot
+    pub fn check_with_snapshot(gates: &[EnvironmentGate], snapshot: EnvironmentSnapshot) -> EnvironmentGateReport {
+        let results: Vec<GateCheckResult> = gates
+            .iter()
+            .map(|gate| Self::check_gate(gate, &snapshot))
+            .collect();
+
+        EnvironmentGateReport {
+            passed: results.iter().all(|r| r.passed),
+            results,
+            snapshot,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Evaluate a single gate
+    fn check_gate(gate: &EnvironmentGate, snapshot: &EnvironmentSnapshot) -> GateCheckResult {
+        let failure = match gate {
+            EnvironmentGate::AppInstalled { path, name } => {
+                if Path::new(path).exists() {
+                    None
+                } else {
+                    let label = name.clone().unwrap_or_else(|| path.display().to_string());
+                    Some(format!("Required application '{}' is not installed at {}", label, path.display()))
+                }
+            }
+            EnvironmentGate::MinScreenResolution { width, height } => match snapshot.screen_resolution {
+                Some((actual_width, actual_height)) if actual_width >= *width && actual_height >= *height => None,
+                Some((actual_width, actual_height)) => Some(format!(
+                    "Screen resolution {}x{} is below the required {}x{}",
+                    actual_width, actual_height, width, height
+                )),
+                None => Some(format!(
+                    "Screen resolution could not be detected (required {}x{})",
+                    width, height
+                )),
+            },
+            EnvironmentGate::OsVersion { os, min_version } => {
+                if !os_matches(os, &snapshot.os) {
+                    Some(format!("Requires {} but running on {}", os, snapshot.os))
+                } else {
+                    match (min_version, &snapshot.os_version) {
+                        (None, _) => None,
+                        (Some(required), Some(actual)) if compare_versions(actual, required) != std::cmp::Ordering::Less => None,
+                        (Some(required), Some(actual)) => Some(format!(
+                            "{} version {} is below the required {}",
+                            snapshot.os, actual, required
+                        )),
+                        (Some(required), None) => Some(format!(
+                            "{} version could not be detected (required {} or later)",
+                            snapshot.os, required
+                        )),
+                    }
+                }
+            }
+            EnvironmentGate::Permission { permission } => {
+                if snapshot.granted_permissions.contains(permission) {
+                    None
+                } else {
+                    Some(format!("Required permission '{}' has not been granted", permission))
+                }
+            }
+        };
+
+        GateCheckResult {
+            gate: gate.clone(),
+            passed: failure.is_none(),
+            reason: failure,
+        }
+    }
+}
+
+/// Check whether a gate OS name refers to the running OS ("darwin" is accepted for macOS)
+fn os_matches(required: &str, actual: &str) -> bool {
+    let normalize = |os: &str| match os.to_lowercase().as_str() {
+        "darwin" | "osx" | "mac" => "macos".to_string(),
+        "win" | "win32" => "windows".to_string(),
+        other => other.to_string(),
+    };
+    normalize(required) == normalize(actual)
+}
+
+/// Compare dotted version strings numerically ("10.15" < "13.0.1")
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split(|c: char| c == '.' || c == '-')
+            .map(|part| part.chars().take_while(|c| c.is_ascii_digit()).collect::<String>())
+            .map(|digits| digits.parse().unwrap_or(0))
+            .collect()
+    };
+
+    let (a, b) = (parse(a), parse(b));
+    let len = a.len().max(b.len());
+    for i in 0..len {
+        let ordering = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ordering != std::cmp::Ordering::Equal {
+            return ordering;
+        }
+    }
+    std::cmp::Ordering::Equal
+}
+
+/// Detect the host OS version
+fn detect_os_version() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("sw_vers").arg("-productVersion").output().ok()?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if version.is_empty() { None } else { Some(version) }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let os_release = std::fs::read_to_string("/etc/os-release").ok()?;
+        os_release
+            .lines()
+            .find_map(|line| line.strip_prefix("VERSION_ID="))
+            .map(|v| v.trim_matches('"').to_string())
+    }
+
+    #[cfg(windows)]
+    {
+        let output = std::process::Command::new("cmd").args(["/C", "ver"]).output().ok()?;
+        let text = String::from_utf8_lossy(&output.stdout).to_string();
+        // Output looks like "Microsoft Windows [Version 10.0.22631.3007]"
+        text.split("Version")
+            .nth(1)
+            .map(|v| v.trim().trim_end_matches(']').trim().to_string())
+    }
+
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            os: "macos".to_string(),
+            os_version: Some("13.4.1".to_string()),
+            screen_resolution: Some((1920, 1080)),
+            granted_permissions: vec![RequiredPermission::InputControl],
+            captured_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_all_gates_pass() {
+        let gates = vec![
+            EnvironmentGate::MinScreenResolution { width: 1280, height: 720 },
+            EnvironmentGate::OsVersion { os: "darwin".to_string(), min_version: Some("12".to_string()) },
+            EnvironmentGate::Permission { permission: RequiredPermission::InputControl },
+        ];
+
+        let report = EnvironmentGateChecker::check_with_snapshot(&gates, snapshot());
+        assert!(report.passed);
+        assert!(report.skip_reason().is_none());
+    }
+
+    #[test]
+    fn test_failed_gates_produce_skip_reason() {
+        let gates = vec![
+            EnvironmentGate::MinScreenResolution { width: 2560, height: 1440 },
+            EnvironmentGate::Permission { permission: RequiredPermission::ScreenCapture },
+            EnvironmentGate::AppInstalled { path: PathBuf::from("/nonexistent/App.app"), name: Some("App".to_string()) },
+        ];
+
+        let report = EnvironmentGateChecker::check_with_snapshot(&gates, snapshot());
+        assert!(!report.passed);
+        assert!(report.results.iter().all(|r| !r.passed));
+
+        let reason = report.skip_reason().unwrap();
+        assert!(reason.contains("1920x1080"));
+        assert!(reason.contains("screen_capture"));
+        assert!(reason.contains("'App'"));
+    }
+
+    #[test]
+    fn test_os_version_gate() {
+        let too_new = vec![EnvironmentGate::OsVersion { os: "macos".to_string(), min_version: Some("14.0".to_string()) }];
+        assert!(!EnvironmentGateChecker::check_with_snapshot(&too_new, snapshot()).passed);
+
+        let wrong_os = vec![EnvironmentGate::OsVersion { os: "windows".to_string(), min_version: None }];
+        assert!(!EnvironmentGateChecker::check_with_snapshot(&wrong_os, snapshot()).passed);
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        use std::cmp::Ordering;
+        assert_eq!(compare_versions("10.15", "13.0.1"), Ordering::Less);
+        assert_eq!(compare_versions("13.0", "13"), Ordering::Equal);
+        assert_eq!(compare_versions("22.04", "20.04"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_gate_serialization() {
+        let json = r#"{"gate": "min_screen_resolution", "width": 1920, "height": 1080}"#;
+        let gate: EnvironmentGate = serde_json::from_str(json).unwrap();
+        assert_eq!(gate, EnvironmentGate::MinScreenResolution { width: 1920, height: 1080 });
+    }
+
+    #[test]
+    fn test_snapshot_checks_permissions_without_capturing() {
+        let platform = crate::test_harness::MockPlatform::default();
+        platform.set_screen_capture_granted(false);
+
+        let snapshot = EnvironmentSnapshot::from_platform(&platform, Some((1920, 1080)));
+        assert_eq!(snapshot.granted_permissions, vec![RequiredPermission::InputControl]);
+        assert!(platform.calls().is_empty());
+
+        platform.set_screen_capture_granted(true);
+        let snapshot = EnvironmentSnapshot::from_platform(&platform, None);
+        assert!(snapshot.granted_permissions.contains(&RequiredPermission::ScreenCapture));
+    }
+}
@@ -2,6 +2,7 @@
 
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
+use crate::localization::{Locale, LocalizedMessage};
 
 /// Result type alias for automation operations
 pub type Result<T> = std::result::Result<T, AutomationError>;
@@ -249,94 +250,68 @@ impl ErrorInfo {
         }
     }
 
+    /// Recovery hint shown to the user, rendered in the given locale
+    pub fn localized_suggested_action(&self, locale: Locale) -> Option<String> {
+        match Self::recovery_message(&self.error) {
+            Some(message) => Some(message.format(locale)),
+            // Free-form hints (such as dependency install instructions) are passed through as-is
+            None => self.suggested_action.clone(),
+        }
+    }
+
     /// Determine recovery options based on error type
     fn determine_recovery_options(error: &AutomationError) -> (bool, bool, Option<String>) {
-        match error {
-            AutomationError::UnsupportedPlatform { .. } => (
-                false, 
-                false, 
-                Some("This platform is not supported. Please use a supported operating system.".to_string())
-            ),
-            AutomationError::PermissionDenied { operation } => (
-                true, 
-                false, 
-                Some(format!("Permission denied for {}. Please grant the required permissions and try again.", operation))
-            ),
-            AutomationError::CoreUnavailable { .. } => (
-                true, 
-                true, 
-                Some("The selected automation core is unavailable. Try switching to an alternative core.".to_string())
-            ),
-            AutomationError::CoreHealthCheckFailed { .. } => (
-                true, 
-                true, 
-                Some("Core health check failed. Try restarting the core or switching to an alternative.".to_string())
-            ),
-            AutomationError::FallbackFailed { .. } => (
-                false, 
-                false, 
-                Some("All automation cores are unavailable. Please check your system configuration.".to_string())
-            ),
-            AutomationError::RuntimeFailure { .. } => (
-                true, 
-                true, 
-                Some("Runtime failure detected. Try restarting the operation or switching cores.".to_string())
-            ),
-            AutomationError::PerformanceDegradation { .. } => (
-                false, 
-                true, 
-                Some("Performance issues detected. Consider switching to a different automation core.".to_string())
-            ),
-            AutomationError::DependencyMissing { suggestion, .. } => (
-                true, 
-                true, 
-                Some(suggestion.clone())
-            ),
-            AutomationError::RecordingError { .. } => (
-                true, 
-                true, 
-                Some("Recording failed. Try stopping and restarting the recording, or switch cores.".to_string())
-            ),
-            AutomationError::PlaybackError { .. } => (
-                true, 
-                true, 
-                Some("Playback failed. Try restarting playback or switch to a different core.".to_string())
-            ),
-            AutomationError::ScriptError { .. } => (
-                true, 
-                false, 
-                Some("Script error detected. Please check the script format and try again.".to_string())
-            ),
-            AutomationError::IoError { .. } => (
-                true, 
-                false, 
-                Some("File system error. Please check file permissions and disk space.".to_string())
-            ),
-            AutomationError::SerializationError { .. } => (
-                true, 
-                false, 
-                Some("Data format error. The script file may be corrupted.".to_string())
-            ),
-            AutomationError::ConfigError { .. } => (
-                true, 
-                false, 
-                Some("Configuration error. Please check your settings and try again.".to_string())
-            ),
-            AutomationError::SystemError { .. } => (
-                true, 
-                true, 
-                Some("System error detected. Try restarting the operation or switching cores.".to_string())
-            ),
-            AutomationError::InvalidInput { .. } => (
-                true, 
-                false, 
-                Some("Invalid input provided. Please check your input and try again.".to_string())
-            ),
-            AutomationError::Timeout { .. } => (
-                true, 
-                true, 
-                Some("Operation timed out. Try increasing timeout settings or switching cores.".to_string())
-            ),
-        }
+        let (can_retry, can_fallback) = match error {
+            AutomationError::UnsupportedPlatform { .. } => (false, false),
+            AutomationError::PermissionDenied { .. } => (true, false),
+            AutomationError::CoreUnavailable { .. } => (true, true),
+            AutomationError::CoreHealthCheckFailed { .. } => (true, true),
+            AutomationError::FallbackFailed { .. } => (false, false),
+            AutomationError::RuntimeFailure { .. } => (true, true),
+            AutomationError::PerformanceDegradation { .. } => (false, true),
+            AutomationError::DependencyMissing { .. } => (true, true),
+            AutomationError::RecordingError { .. } => (true, true),
+            AutomationError::PlaybackError { .. } => (true, true),
+            AutomationError::ScriptError { .. } => (true, false),
+            AutomationError::IoError { .. } => (true, false),
+            AutomationError::SerializationError { .. } => (true, false),
+            AutomationError::ConfigError { .. } => (true, false),
+            AutomationError::SystemError { .. } => (true, true),
+            AutomationError::InvalidInput { .. } => (true, false),
+            AutomationError::Timeout { .. } => (true, true),
+        };
+
+        let suggested_action = match error {
+            AutomationError::DependencyMissing { suggestion, .. } => Some(suggestion.clone()),
+            _ => Self::recovery_message(error).map(|message| message.format(Locale::English)),
+        };
+
+        (can_retry, can_fallback, suggested_action)
+    }
+
+    /// Localizable recovery hint for an error type
+    fn recovery_message(error: &AutomationError) -> Option<LocalizedMessage> {
+        let id = match error {
+            AutomationError::UnsupportedPlatform { .. } => "recovery-unsupported-platform",
+            AutomationError::PermissionDenied { operation } => {
+                return Some(LocalizedMessage::new("recovery-permission-denied").with_arg("operation", operation));
+            }
+            AutomationError::CoreUnavailable { .. } => "recovery-core-unavailable",
+            AutomationError::CoreHealthCheckFailed { .. } => "recovery-core-health-check-failed",
+            AutomationError::FallbackFailed { .. } => "recovery-fallback-failed",
+            AutomationError::RuntimeFailure { .. } => "recovery-runtime-failure",
+            AutomationError::PerformanceDegradation { .. } => "recovery-performance-degradation",
+            AutomationError::DependencyMissing { .. } => return None,
+            AutomationError::RecordingError { .. } => "recovery-recording",
+            AutomationError::PlaybackError { .. } => "recovery-playback",
+            AutomationError::ScriptError { .. } => "recovery-script",
+            AutomationError::IoError { .. } => "recovery-io",
+            AutomationError::SerializationError { .. } => "recovery-serialization",
+            AutomationError::ConfigError { .. } => "recovery-config",
+            AutomationError::SystemError { .. } => "recovery-system",
+            AutomationError::InvalidInput { .. } => "recovery-invalid-input",
+            AutomationError::Timeout { .. } => "recovery-timeout",
+        };
+        Some(LocalizedMessage::new(id))
     }
 }
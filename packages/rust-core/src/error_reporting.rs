@@ -1,6 +1,7 @@
 //! Cross-core error reporting and consistency mechanisms
 
 use crate::{AutomationError, ErrorInfo, ErrorSeverity, health::CoreType};
+use crate::localization::{Locale, Localize, LocalizedMessage};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,6 +18,37 @@ pub struct ErrorReport {
     pub suggested_actions: Vec<SuggestedAction>,
 }
 
+/// Error report with its user-facing strings rendered in a specific locale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalizedErrorReport {
+    pub id: String,
+    pub locale: Locale,
+    pub core_type: CoreType,
+    pub severity: ErrorSeverity,
+    pub message: String,
+    pub suggested_action: Option<String>,
+    pub suggested_actions: Vec<SuggestedAction>,
+}
+
+impl ErrorReport {
+    /// Render the report's error message and suggestions in the given locale
+    pub fn localized(&self, locale: Locale) -> LocalizedErrorReport {
+        LocalizedErrorReport {
+            id: self.id.clone(),
+            locale,
+            core_type: self.core_type.clone(),
+            severity: self.error_info.severity.clone(),
+            message: self.error_info.error.localize(locale),
+            suggested_action: self.error_info.localized_suggested_action(locale),
+            suggested_actions: self
+                .suggested_actions
+                .iter()
+                .map(|action| action.localized(locale))
+                .collect(),
+        }
+    }
+}
+
 /// Context information about the operation that failed
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationContext {
@@ -49,12 +81,52 @@ pub enum PerformanceImpactSeverity {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SuggestedAction {
     pub action_type: ActionType,
+    /// English description, kept for consumers that do not localize
     pub description: String,
+    /// Message id the description was rendered from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<LocalizedMessage>,
     pub priority: ActionPriority,
     pub estimated_success_rate: f32,
     pub requires_user_intervention: bool,
 }
 
+impl SuggestedAction {
+    /// Create a suggested action whose description is rendered from a localized message
+    pub fn new(
+        action_type: ActionType,
+        message: LocalizedMessage,
+        priority: ActionPriority,
+        estimated_success_rate: f32,
+        requires_user_intervention: bool,
+    ) -> Self {
+        Self {
+            action_type,
+            description: message.format(Locale::English),
+            message: Some(message),
+            priority,
+            estimated_success_rate,
+            requires_user_intervention,
+        }
+    }
+
+    /// Copy of this action with its description rendered in the given locale
+    pub fn localized(&self, locale: Locale) -> Self {
+        let mut action = self.clone();
+        action.description = self.localize(locale);
+        action
+    }
+}
+
+impl Localize for SuggestedAction {
+    fn localize(&self, locale: Locale) -> String {
+        match &self.message {
+            Some(message) => message.format(locale),
+            None => self.description.clone(),
+        }
+    }
+}
+
 /// Type of suggested action
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ActionType {
@@ -125,68 +197,70 @@ impl CoreErrorHandler for PythonCoreErrorHandler {
     fn handle_error(&self, error: &AutomationError, _context: &OperationContext) -> Vec<SuggestedAction> {
         match error {
             AutomationError::PermissionDenied { operation } => vec![
-                SuggestedAction {
-                    action_type: ActionType::CheckPermissions,
-                    description: format!("Check and grant permissions for: {}", operation),
-                    priority: ActionPriority::High,
-                    estimated_success_rate: 0.8,
-                    requires_user_intervention: true,
-                },
-                SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: "Try using Rust core which may have different permission requirements".to_string(),
-                    priority: ActionPriority::Medium,
-                    estimated_success_rate: 0.6,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::CheckPermissions,
+                    LocalizedMessage::new("action-python-check-permissions").with_arg("operation", operation),
+                    ActionPriority::High,
+                    0.8,
+                    true,
+                ),
+                SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-python-switch-for-permissions"),
+                    ActionPriority::Medium,
+                    0.6,
+                    false,
+                ),
             ],
             AutomationError::DependencyMissing { dependency, suggestion } => vec![
-                SuggestedAction {
-                    action_type: ActionType::UpdateConfiguration,
-                    description: format!("Install missing dependency: {} - {}", dependency, suggestion),
-                    priority: ActionPriority::Critical,
-                    estimated_success_rate: 0.9,
-                    requires_user_intervention: true,
-                },
-                SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: "Switch to Rust core to avoid Python dependencies".to_string(),
-                    priority: ActionPriority::High,
-                    estimated_success_rate: 0.8,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::UpdateConfiguration,
+                    LocalizedMessage::new("action-install-dependency")
+                        .with_arg("dependency", dependency)
+                        .with_arg("suggestion", suggestion),
+                    ActionPriority::Critical,
+                    0.9,
+                    true,
+                ),
+                SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-python-switch-for-dependencies"),
+                    ActionPriority::High,
+                    0.8,
+                    false,
+                ),
             ],
             AutomationError::RecordingError { .. } | AutomationError::PlaybackError { .. } => vec![
-                SuggestedAction {
-                    action_type: ActionType::Retry,
-                    description: "Retry the operation after a brief delay".to_string(),
-                    priority: ActionPriority::Medium,
-                    estimated_success_rate: 0.7,
-                    requires_user_intervention: false,
-                },
-                SuggestedAction {
-                    action_type: ActionType::RestartProcess,
-                    description: "Restart Python automation process".to_string(),
-                    priority: ActionPriority::Medium,
-                    estimated_success_rate: 0.8,
-                    requires_user_intervention: false,
-                },
-                SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: "Switch to Rust core for potentially better performance".to_string(),
-                    priority: ActionPriority::Low,
-                    estimated_success_rate: 0.6,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::Retry,
+                    LocalizedMessage::new("action-retry-after-delay"),
+                    ActionPriority::Medium,
+                    0.7,
+                    false,
+                ),
+                SuggestedAction::new(
+                    ActionType::RestartProcess,
+                    LocalizedMessage::new("action-restart-python"),
+                    ActionPriority::Medium,
+                    0.8,
+                    false,
+                ),
+                SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-python-switch-for-performance"),
+                    ActionPriority::Low,
+                    0.6,
+                    false,
+                ),
             ],
             _ => vec![
-                SuggestedAction {
-                    action_type: ActionType::Retry,
-                    description: "Retry the operation".to_string(),
-                    priority: ActionPriority::Low,
-                    estimated_success_rate: 0.5,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::Retry,
+                    LocalizedMessage::new("action-retry"),
+                    ActionPriority::Low,
+                    0.5,
+                    false,
+                ),
             ],
         }
     }
@@ -232,54 +306,54 @@ impl CoreErrorHandler for RustCoreErrorHandler {
     fn handle_error(&self, error: &AutomationError, _context: &OperationContext) -> Vec<SuggestedAction> {
         match error {
             AutomationError::PermissionDenied { operation } => vec![
-                SuggestedAction {
-                    action_type: ActionType::CheckPermissions,
-                    description: format!("Grant system permissions for: {}", operation),
-                    priority: ActionPriority::High,
-                    estimated_success_rate: 0.9,
-                    requires_user_intervention: true,
-                },
-                SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: "Try Python core which may work with current permissions".to_string(),
-                    priority: ActionPriority::Medium,
-                    estimated_success_rate: 0.7,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::CheckPermissions,
+                    LocalizedMessage::new("action-rust-grant-permissions").with_arg("operation", operation),
+                    ActionPriority::High,
+                    0.9,
+                    true,
+                ),
+                SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-rust-switch-for-permissions"),
+                    ActionPriority::Medium,
+                    0.7,
+                    false,
+                ),
             ],
             AutomationError::UnsupportedPlatform { platform } => vec![
-                SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: format!("Platform {} not supported by Rust core, switch to Python core", platform),
-                    priority: ActionPriority::Critical,
-                    estimated_success_rate: 0.9,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-rust-unsupported-platform").with_arg("platform", platform),
+                    ActionPriority::Critical,
+                    0.9,
+                    false,
+                ),
             ],
             AutomationError::RecordingError { .. } | AutomationError::PlaybackError { .. } => vec![
-                SuggestedAction {
-                    action_type: ActionType::Retry,
-                    description: "Retry the operation with Rust core".to_string(),
-                    priority: ActionPriority::Medium,
-                    estimated_success_rate: 0.8,
-                    requires_user_intervention: false,
-                },
-                SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: "Switch to Python core as fallback".to_string(),
-                    priority: ActionPriority::Low,
-                    estimated_success_rate: 0.7,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::Retry,
+                    LocalizedMessage::new("action-retry-with-rust"),
+                    ActionPriority::Medium,
+                    0.8,
+                    false,
+                ),
+                SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-rust-switch-fallback"),
+                    ActionPriority::Low,
+                    0.7,
+                    false,
+                ),
             ],
             _ => vec![
-                SuggestedAction {
-                    action_type: ActionType::Retry,
-                    description: "Retry the operation".to_string(),
-                    priority: ActionPriority::Low,
-                    estimated_success_rate: 0.6,
-                    requires_user_intervention: false,
-                },
+                SuggestedAction::new(
+                    ActionType::Retry,
+                    LocalizedMessage::new("action-retry"),
+                    ActionPriority::Low,
+                    0.6,
+                    false,
+                ),
             ],
         }
     }
@@ -380,68 +454,58 @@ impl CrossCoreErrorReporter {
 
         // Check response time
         if current_metrics.avg_response_time > self.performance_thresholds.response_time_critical {
-            suggestions.push(SuggestedAction {
-                action_type: ActionType::SwitchCore,
-                description: format!(
-                    "Response time ({:?}) exceeds critical threshold ({:?}). Consider switching cores.",
-                    current_metrics.avg_response_time,
-                    self.performance_thresholds.response_time_critical
-                ),
-                priority: ActionPriority::High,
-                estimated_success_rate: 0.8,
-                requires_user_intervention: false,
-            });
+            suggestions.push(SuggestedAction::new(
+                ActionType::SwitchCore,
+                LocalizedMessage::new("action-response-time-critical")
+                    .with_arg("actual", format!("{:?}", current_metrics.avg_response_time))
+                    .with_arg("threshold", format!("{:?}", self.performance_thresholds.response_time_critical)),
+                ActionPriority::High,
+                0.8,
+                false,
+            ));
         } else if current_metrics.avg_response_time > self.performance_thresholds.response_time_warning {
-            suggestions.push(SuggestedAction {
-                action_type: ActionType::SwitchCore,
-                description: format!(
-                    "Response time ({:?}) is elevated. Switching cores may improve performance.",
-                    current_metrics.avg_response_time
-                ),
-                priority: ActionPriority::Medium,
-                estimated_success_rate: 0.6,
-                requires_user_intervention: false,
-            });
+            suggestions.push(SuggestedAction::new(
+                ActionType::SwitchCore,
+                LocalizedMessage::new("action-response-time-elevated")
+                    .with_arg("actual", format!("{:?}", current_metrics.avg_response_time)),
+                ActionPriority::Medium,
+                0.6,
+                false,
+            ));
         }
 
         // Check success rate
         if current_metrics.success_rate < self.performance_thresholds.success_rate_critical {
-            suggestions.push(SuggestedAction {
-                action_type: ActionType::SwitchCore,
-                description: format!(
-                    "Success rate ({:.1}%) is critically low. Switching cores is recommended.",
-                    current_metrics.success_rate * 100.0
-                ),
-                priority: ActionPriority::Critical,
-                estimated_success_rate: 0.9,
-                requires_user_intervention: false,
-            });
+            suggestions.push(SuggestedAction::new(
+                ActionType::SwitchCore,
+                LocalizedMessage::new("action-success-rate-critical")
+                    .with_arg("rate", format!("{:.1}", current_metrics.success_rate * 100.0)),
+                ActionPriority::Critical,
+                0.9,
+                false,
+            ));
         } else if current_metrics.success_rate < self.performance_thresholds.success_rate_warning {
-            suggestions.push(SuggestedAction {
-                action_type: ActionType::SwitchCore,
-                description: format!(
-                    "Success rate ({:.1}%) is below optimal. Consider switching cores.",
-                    current_metrics.success_rate * 100.0
-                ),
-                priority: ActionPriority::Medium,
-                estimated_success_rate: 0.7,
-                requires_user_intervention: false,
-            });
+            suggestions.push(SuggestedAction::new(
+                ActionType::SwitchCore,
+                LocalizedMessage::new("action-success-rate-low")
+                    .with_arg("rate", format!("{:.1}", current_metrics.success_rate * 100.0)),
+                ActionPriority::Medium,
+                0.7,
+                false,
+            ));
         }
 
         // Check memory usage if available
         if let Some(memory_usage) = current_metrics.memory_usage {
             if memory_usage > self.performance_thresholds.memory_usage_critical {
-                suggestions.push(SuggestedAction {
-                    action_type: ActionType::SwitchCore,
-                    description: format!(
-                        "Memory usage ({} MB) is critically high. Switching to a more efficient core is recommended.",
-                        memory_usage / (1024 * 1024)
-                    ),
-                    priority: ActionPriority::High,
-                    estimated_success_rate: 0.8,
-                    requires_user_intervention: false,
-                });
+                suggestions.push(SuggestedAction::new(
+                    ActionType::SwitchCore,
+                    LocalizedMessage::new("action-memory-critical")
+                        .with_arg("memory_mb", memory_usage / (1024 * 1024)),
+                    ActionPriority::High,
+                    0.8,
+                    false,
+                ));
             }
         }
 
@@ -661,6 +725,36 @@ mod tests {
         assert!(rust_action_types.contains(&ActionType::CheckPermissions), "Rust should suggest checking permissions");
     }
 
+    #[tokio::test]
+    async fn test_localized_error_report() {
+        let reporter = CrossCoreErrorReporter::new();
+
+        let context = OperationContext {
+            operation_type: "automation".to_string(),
+            operation_id: None,
+            user_action: None,
+            system_state: HashMap::new(),
+            duration_before_failure: None,
+        };
+
+        let report = reporter
+            .report_error(CoreType::Rust, AutomationError::PermissionDenied { operation: "mouse_control".to_string() }, context)
+            .await;
+
+        // Descriptions stay in English for existing consumers
+        assert_eq!(report.suggested_actions[0].description, "Grant system permissions for: mouse_control");
+
+        let english = report.localized(Locale::English);
+        assert_eq!(english.message, report.error_info.error.to_string());
+        assert_eq!(english.suggested_action, report.error_info.suggested_action);
+
+        let vietnamese = report.localized(Locale::Vietnamese);
+        assert_eq!(vietnamese.message, "Không có quyền thực hiện: mouse_control");
+        assert_eq!(vietnamese.suggested_actions[0].description, "Cấp quyền hệ thống cho: mouse_control");
+        assert_eq!(vietnamese.suggested_actions.len(), report.suggested_actions.len());
+        assert!(vietnamese.suggested_action.unwrap().contains("mouse_control"));
+    }
+
     #[tokio::test]
     async fn test_error_statistics_tracking() {
         let reporter = CrossCoreErrorReporter::new();
@@ -0,0 +1,256 @@
+//! Bounded event channels for recorder and player updates
+//!
+//! Recorders and players stream events to the UI while they run. A consumer
+//! that stops draining must not make the queue grow for the rest of a long
+//! session, so these channels are bounded and follow
+//! `OverflowPolicy::RejectNew`: a full queue refuses the new event and counts
+//! it as dropped, and the producer never blocks on a UI update. Events the
+//! consumer must see, such as playback completion, are sent with
+//! `send_waiting`, which waits for space instead.
+//!
+//! Every channel registers with the process-wide `buffer_registry`, so its
+//! occupancy and drop counter show up in the monitor's buffer stats.
+
+use crate::bounded_buffer::{buffer_registry, BufferStats, BufferStatsSource, OverflowPolicy};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+/// Events queued per channel before new ones are dropped
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// How long `send_waiting` waits for a full queue to drain
+pub const EVENT_SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Create a bounded event channel; a capacity of zero is treated as one
+pub fn event_channel<T: Send + 'static>(capacity: usize) -> (EventSender<T>, mpsc::Receiver<T>) {
+    named_event_channel("events", capacity)
+}
+
+/// Create a bounded event channel reported to monitoring under `name`
+pub fn named_event_channel<T: Send + 'static>(name: &str, capacity: usize) -> (EventSender<T>, mpsc::Receiver<T>) {
+    let (sender, receiver) = mpsc::channel(capacity.max(1));
+    let queue = Arc::new(QueueState {
+        name: name.to_string(),
+        capacity: sender.max_capacity(),
+        sender: sender.downgrade(),
+        dropped: AtomicU64::new(0),
+    });
+    let source: Arc<dyn BufferStatsSource> = queue.clone();
+    buffer_registry().register(&source);
+
+    (EventSender { sender, queue }, receiver)
+}
+
+/// Hand events to a synchronous consumer that waits with `recv_timeout`
+///
+/// A dedicated thread blocks on `receiver` and forwards each event; it exits
+/// once every sender is gone or the returned receiver is dropped.
+pub fn blocking_receiver<T: Send + 'static>(mut receiver: mpsc::Receiver<T>) -> std::sync::mpsc::Receiver<T> {
+    let (forward, blocking) = std::sync::mpsc::sync_channel(0);
+    thread::spawn(move || {
+        while let Some(event) = receiver.blocking_recv() {
+            if forward.send(event).is_err() {
+                break;
+            }
+        }
+    });
+    blocking
+}
+
+/// Drop counter and stats shared by the senders of one channel
+struct QueueState<T> {
+    name: String,
+    capacity: usize,
+    // Weak so that monitoring never keeps the channel open
+    sender: mpsc::WeakSender<T>,
+    dropped: AtomicU64,
+}
+
+impl<T: Send> BufferStatsSource for QueueState<T> {
+    fn buffer_stats(&self) -> BufferStats {
+        let len = self
+            .sender
+            .upgrade()
+            .map(|sender| sender.max_capacity() - sender.capacity())
+            .unwrap_or(0);
+        BufferStats {
+            name: self.name.clone(),
+            len,
+            capacity: self.capacity,
+            policy: OverflowPolicy::RejectNew,
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Why an event was not queued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSendError {
+    /// The queue was full and the event was dropped
+    Full,
+    /// The receiver is gone
+    Closed,
+}
+
+impl fmt::Display for EventSendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventSendError::Full => write!(f, "event queue is full"),
+            EventSendError::Closed => write!(f, "event channel closed"),
+        }
+    }
+}
+
+impl std::error::Error for EventSendError {}
+
+/// Sending half of a bounded event channel
+///
+/// Clones share the queue and the drop counter.
+pub struct EventSender<T> {
+    sender: mpsc::Sender<T>,
+    queue: Arc<QueueState<T>>,
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            queue: Arc::clone(&self.queue),
+        }
+    }
+}
+
+impl<T> fmt::Debug for EventSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSender")
+            .field("capacity", &self.sender.max_capacity())
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
+
+impl<T> EventSender<T> {
+    /// Queue an event without blocking, dropping it if the queue is full
+    pub fn send(&self, event: T) -> Result<(), EventSendError> {
+        match self.sender.try_send(event) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.queue.dropped.fetch_add(1, Ordering::Relaxed);
+                Err(EventSendError::Full)
+            }
+            Err(TrySendError::Closed(_)) => Err(EventSendError::Closed),
+        }
+    }
+
+    /// Queue an event, waiting up to `timeout` for space
+    ///
+    /// Sleeps the calling thread, so only use it from worker threads and not
+    /// from async tasks.
+    pub fn send_waiting(&self, event: T, timeout: Duration) -> Result<(), EventSendError> {
+        let deadline = Instant::now() + timeout;
+        let mut event = event;
+        loop {
+            match self.sender.try_send(event) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Closed(_)) => return Err(EventSendError::Closed),
+                Err(TrySendError::Full(returned)) => {
+                    if Instant::now() >= deadline {
+                        self.queue.dropped.fetch_add(1, Ordering::Relaxed);
+                        return Err(EventSendError::Full);
+                    }
+                    event = returned;
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+        }
+    }
+
+    /// Events dropped because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Whether the receiver has been dropped
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_queue_rejects_new_events() {
+        let (sender, mut receiver) = event_channel(2);
+        assert!(sender.send(1).is_ok());
+        assert!(sender.send(2).is_ok());
+        assert_eq!(sender.send(3), Err(EventSendError::Full));
+        assert_eq!(sender.clone().dropped(), 1);
+
+        assert_eq!(receiver.try_recv().unwrap(), 1);
+        assert!(sender.send(4).is_ok());
+
+        drop(receiver);
+        assert_eq!(sender.send(5), Err(EventSendError::Closed));
+    }
+
+    #[test]
+    fn test_send_waiting_waits_for_space() {
+        let (sender, mut receiver) = event_channel(1);
+        sender.send("progress").unwrap();
+
+        let consumer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            let first = receiver.try_recv().unwrap();
+            loop {
+                match receiver.try_recv() {
+                    Ok(second) => return (first, second),
+                    Err(_) => thread::sleep(Duration::from_millis(1)),
+                }
+            }
+        });
+
+        assert!(sender.send_waiting("complete", Duration::from_secs(5)).is_ok());
+        assert_eq!(consumer.join().unwrap(), ("progress", "complete"));
+        assert_eq!(sender.dropped(), 0);
+    }
+
+    #[test]
+    fn test_blocking_receiver_waits_for_events() {
+        let (sender, receiver) = event_channel(4);
+        let blocking = blocking_receiver(receiver);
+
+        let producer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            sender.send("complete").unwrap();
+        });
+
+        assert_eq!(blocking.recv_timeout(Duration::from_secs(5)), Ok("complete"));
+        producer.join().unwrap();
+        assert_eq!(
+            blocking.recv_timeout(Duration::from_secs(5)),
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn test_channels_report_to_the_buffer_registry() {
+        let (sender, _receiver) = named_event_channel("test_registry_events", 2);
+        sender.send(1).unwrap();
+        sender.send(2).unwrap();
+        let _ = sender.send(3);
+
+        let find = || buffer_registry().stats().into_iter().find(|s| s.name == "test_registry_events");
+        let stats = find().unwrap();
+        assert_eq!((stats.len, stats.capacity, stats.dropped), (2, 2, 1));
+
+        drop(sender);
+        assert!(find().is_none());
+    }
+}
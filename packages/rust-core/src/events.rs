@@ -0,0 +1,72 @@
+//! Core event bus
+//!
+//! Runtime conditions that several components react to (e.g. display
+//! configuration changes) are published here. Subscribers get their own
+//! receiver; slow subscribers lose the oldest events rather than blocking
+//! publishers.
+
+use crate::display::DisplayChangeEvent;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+/// Events queued per subscriber before the oldest are dropped
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Event published on the core event bus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum CoreEvent {
+    /// A display was attached, detached, or changed resolution or scale
+    DisplayChanged(DisplayChangeEvent),
+}
+
+impl CoreEvent {
+    /// The display change carried by this event, if any
+    pub fn as_display_change(&self) -> Option<&DisplayChangeEvent> {
+        match self {
+            CoreEvent::DisplayChanged(change) => Some(change),
+        }
+    }
+}
+
+/// Broadcast channel for core events
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<CoreEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    /// Publish an event, returning the number of subscribers that received it
+    pub fn publish(&self, event: CoreEvent) -> usize {
+        // Publishing without subscribers is not an error
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    /// Subscribe to events published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<CoreEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+static EVENT_BUS: OnceLock<EventBus> = OnceLock::new();
+
+/// Get the process-wide core event bus
+pub fn event_bus() -> &'static EventBus {
+    EVENT_BUS.get_or_init(EventBus::new)
+}
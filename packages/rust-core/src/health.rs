@@ -1,6 +1,7 @@
 //! Core health checking and availability detection
 
 use crate::{Result, AutomationError, ErrorInfo, ErrorSeverity};
+use crate::capabilities::CapabilityMatrix;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -417,6 +418,11 @@ impl CoreHealthChecker {
         &self.health_status
     }
 
+    /// Capability matrix for the current platform, marking healthy cores as available
+    pub fn capability_matrix(&self) -> CapabilityMatrix {
+        CapabilityMatrix::current(&self.get_available_cores())
+    }
+
     /// Check if a core needs health check (based on last check time)
     pub fn needs_health_check(&self, core_type: &CoreType) -> bool {
         match self.health_status.get(core_type) {
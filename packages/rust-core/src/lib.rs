@@ -6,23 +6,43 @@
 pub mod automation;
 pub mod error;
 pub mod platform;
+pub mod events;
+pub mod clock;
+pub mod display;
 pub mod recorder;
+pub mod recording_quality;
+pub mod click_annotations;
 pub mod player;
 pub mod script;
 pub mod config;
 pub mod preferences;
 pub mod health;
+pub mod capabilities;
 pub mod fallback;
 pub mod error_reporting;
+pub mod localization;
 pub mod performance;
 pub mod validation;
+pub mod validation_rules;
 pub mod cross_core_testing;
 pub mod logging;
+pub mod bounded_buffer;
+pub mod event_channel;
 pub mod monitoring;
+pub mod performance_assertions;
+pub mod resource_profile;
+pub mod crash_loop;
 pub mod debug;
 pub mod asset_manager;
 pub mod ai_vision_integration;
+pub mod ai_budget;
 pub mod visual_testing;
+pub mod environment_gates;
+pub mod suite;
+pub mod control_api;
+
+#[cfg(any(test, feature = "test-harness"))]
+pub mod test_harness;
 
 #[cfg(test)]
 mod preferences_property_tests;
@@ -54,19 +74,37 @@ mod ai_vision_capture_property_tests;
 pub use automation::{AutomationCore, AutomationCommand, CommandResult};
 pub use error::{AutomationError, Result, ErrorInfo, ErrorSeverity};
 pub use config::AutomationConfig;
-pub use script::{ScriptData, Action, ActionType, AIVisionCaptureAction, StaticData, DynamicConfig, CacheData, VisionROI, InteractionType, SearchScope};
+pub use script::{ScriptData, Action, ActionType, AIVisionCaptureAction, StaticData, DynamicConfig, CacheData, VisionROI, InteractionType, SearchScope, prepare_playback_json};
 pub use preferences::{PreferenceManager, UserPreferences};
 pub use health::{CoreHealthChecker, CoreHealth, PerformanceMetrics};
+pub use capabilities::{CapabilityMatrix, CoreCapabilities, CaptureMode, SupportLevel};
 pub use fallback::{FallbackManager, FallbackConfig, FallbackResult};
-pub use error_reporting::{CrossCoreErrorReporter, ErrorReport, SuggestedAction, ActionType as ErrorActionType};
+pub use error_reporting::{CrossCoreErrorReporter, ErrorReport, LocalizedErrorReport, SuggestedAction, ActionType as ErrorActionType};
+pub use localization::{Locale, Localize, LocalizedMessage, Localizer, localizer, localized_error, preferred_locale, set_preferred_locale};
 pub use performance::{PerformanceCollector, PerformanceManager, PerformanceComparison, CoreRecommendation, OperationType, OperationMetric, BenchmarkResult};
-pub use validation::{ScriptValidator, ScriptMigrator, CompatibilityTester, CompatibilityResult, CompatibilityIssue, IssueSeverity};
+pub use validation::{default_validator, reload_default_validator, ScriptValidator, ScriptMigrator, CompatibilityTester, CompatibilityResult, CompatibilityIssue, IssueSeverity};
+pub use validation_rules::{SignedRuleFile, TrustedRuleKeys, ValidationRuleSet, ValidationRule, RuleCondition, RuleVersionStore};
 pub use cross_core_testing::{CrossCoreTestSuite, TestScript, CrossCoreTestResult, RecordingComparator, create_default_test_scripts};
 pub use logging::{AutomationLogger, LoggingConfig, LogEntry, LogLevel, OperationType as LogOperationType, CoreType as LogCoreType, PerformanceReport, init_logger, get_logger};
-pub use monitoring::{CoreMonitor, MonitoringConfig, HealthStatus, CoreHealthInfo, Alert, AlertType, MonitoringMetrics, HealthCheckResult};
+pub use monitoring::{CoreMonitor, MonitoringConfig, HealthStatus, CoreHealthInfo, Alert, AlertType, MonitoringMetrics, HealthCheckResult, ProcessMetrics, ProcessSampler, find_process_by_name};
+pub use resource_profile::{ResourceMode, ResourceProfile, HostResources, init_resource_profile, resource_profile, configured_resource_profile};
+pub use performance_assertions::{MetricAssertion, MetricAssertionMonitor, MetricKind, MetricAggregation, ProcessTarget, AssertionStatus, PerformanceAssertionResult, script_assertions};
+pub use bounded_buffer::{BoundedBuffer, BufferStats, OverflowPolicy, BufferRegistry, BufferStatsSource, buffer_registry};
+pub use event_channel::{EventSender, EventSendError, blocking_receiver, event_channel, named_event_channel, DEFAULT_EVENT_CAPACITY, EVENT_SEND_TIMEOUT};
+pub use crash_loop::{CrashLoopGuard, CrashLoopConfig, CircuitBreakerStatus, CircuitState, CrashRecord, RestartDecision, Subsystem};
 pub use asset_manager::{AssetManager, to_posix_path, to_native_path, generate_unique_filename, is_safe_path};
-pub use player::{scale_coordinates, scale_roi, ScreenDimensions, ScaledCoordinates, execute_ai_vision_capture, execute_dynamic_mode_with_ai, AIVisionExecutionResult, AIVisionExecutionMode, DynamicModeExecutionResult, CacheUpdate};
+pub use platform::{PlatformHandleCache, SharedPlatform, platform_cache, shared_platform};
+pub use events::{CoreEvent, EventBus, event_bus};
+pub use clock::{Clock, SharedClock, SystemClock, system_clock};
+pub use display::{DisplayInfo, DisplayChangeKind, DisplayChangeEvent, DisplayMonitor, diff_displays};
+pub use player::{scale_coordinates, scale_roi, ScreenDimensions, ScaledCoordinates, execute_ai_vision_capture, execute_dynamic_mode_with_ai, execute_dynamic_mode_with_budget, AIVisionExecutionResult, AIVisionExecutionMode, DynamicModeExecutionResult, CacheUpdate};
 pub use ai_vision_integration::{AIVisionAnalysisRequest, AIVisionAnalysisResponse, AIVisionProvider, DynamicModeResult, build_analysis_request, apply_cache_update, persist_cache_update, DEFAULT_AI_TIMEOUT_MS};
+pub use ai_budget::{TokenBudget, TokenBudgetConfig, BudgetLimits, BudgetExceededAction, BudgetDecision, BudgetAlert, BudgetStatus, BudgetUsage, BudgetScope, BudgetLimitKind, BudgetAlertHandler, AITokenUsage};
+pub use environment_gates::{EnvironmentGate, EnvironmentGateChecker, EnvironmentGateReport, EnvironmentSnapshot, GateCheckResult, RequiredPermission};
+pub use click_annotations::{ClickAnnotationConfig, ClickAnnotationReport, ClickAnnotator, ClickMarker, AnnotatedScreenshot, annotate_frame, load_annotation_report};
+pub use recording_quality::{RecordingQualityAnalyzer, RecordingQualityConfig, RecordingQualityReport, QualityFinding, QualityFindingKind};
+pub use control_api::{ApiScope, ApiToken, ApiTokenStore, AuditOutcome, AuditRecord, CommandHandler, ControlApiGateway, ControlTransport, RequestContext, bearer_token, required_scope};
+pub use suite::{SuiteDefinition, SuiteRunner, SuiteRunResult, SuiteRunStatus, ScriptRunResult, ScriptRunStatus, DEFAULT_SCRIPT_TIMEOUT};
 pub use visual_testing::{Region, ComparisonConfig, ComparisonResult, ComparisonMethod, SensitivityProfile, DifferenceType, PerformanceMetrics as VRTPerformanceMetrics, VisualTestConfig, VisualError, VisualResult};
 
 /// Re-export commonly used types
@@ -94,6 +132,8 @@ pub mod prelude {
         CrossCoreErrorReporter,
         ErrorReport,
         SuggestedAction,
+        Locale,
+        Localize,
         PerformanceCollector,
         PerformanceManager,
         PerformanceComparison,
@@ -0,0 +1,367 @@
+//! Localization of user-facing error messages
+//!
+//! Error messages, recovery hints and suggested actions are looked up by
+//! message id in Fluent resources bundled with the crate. English and
+//! Vietnamese are provided; lookups that fail in the requested locale fall
+//! back to English.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+use crate::AutomationError;
+
+const EN_ERRORS: &str = include_str!("../locales/en/errors.ftl");
+const VI_ERRORS: &str = include_str!("../locales/vi/errors.ftl");
+
+/// Languages user-facing messages can be rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "vi")]
+    Vietnamese,
+}
+
+impl Locale {
+    /// All supported locales
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::Vietnamese];
+
+    /// BCP 47 language tag of the locale
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Vietnamese => "vi",
+        }
+    }
+
+    /// Resolve a language tag such as "vi", "vi-VN" or "en_US"
+    ///
+    /// Unknown languages resolve to `None`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.to_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::English),
+            "vi" => Some(Locale::Vietnamese),
+            _ => None,
+        }
+    }
+
+    fn resources(&self) -> &'static str {
+        match self {
+            Locale::English => EN_ERRORS,
+            Locale::Vietnamese => VI_ERRORS,
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+/// A message id together with its arguments, rendered on demand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalizedMessage {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub args: HashMap<String, String>,
+}
+
+impl LocalizedMessage {
+    /// Create a message without arguments
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            args: HashMap::new(),
+        }
+    }
+
+    /// Add an argument referenced by the message as `{ $name }`
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.args.insert(name.into(), value.to_string());
+        self
+    }
+
+    /// Render the message in the given locale
+    pub fn format(&self, locale: Locale) -> String {
+        localizer().format(locale, &self.id, &self.args)
+    }
+}
+
+/// Types whose user-facing text can be rendered in a given locale
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+}
+
+impl Localize for LocalizedMessage {
+    fn localize(&self, locale: Locale) -> String {
+        self.format(locale)
+    }
+}
+
+/// Holds one Fluent bundle per supported locale
+pub struct Localizer {
+    bundles: HashMap<Locale, FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    /// Build bundles from the resources embedded in the crate
+    pub fn new() -> Self {
+        let mut bundles = HashMap::new();
+
+        for locale in Locale::ALL {
+            let langid: LanguageIdentifier = locale
+                .tag()
+                .parse()
+                .expect("built-in locale tags are valid");
+            let resource = match FluentResource::try_new(locale.resources().to_string()) {
+                Ok(resource) => resource,
+                // Keep the entries that did parse rather than dropping the locale
+                Err((resource, _errors)) => resource,
+            };
+
+            let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+            // Unicode isolation marks around arguments show up as stray characters in the UI
+            bundle.set_use_isolating(false);
+            if let Err(errors) = bundle.add_resource(resource) {
+                log::warn!("Duplicate messages in {} error resources: {:?}", locale, errors);
+            }
+            bundles.insert(locale, bundle);
+        }
+
+        Self { bundles }
+    }
+
+    /// Whether a message id exists for the given locale
+    pub fn has_message(&self, locale: Locale, id: &str) -> bool {
+        self.bundles
+            .get(&locale)
+            .map(|bundle| bundle.has_message(id))
+            .unwrap_or(false)
+    }
+
+    /// Render a message, falling back to English and then to the bare id
+    pub fn format(&self, locale: Locale, id: &str, args: &HashMap<String, String>) -> String {
+        self.try_format(locale, id, args)
+            .or_else(|| self.try_format(Locale::English, id, args))
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn try_format(&self, locale: Locale, id: &str, args: &HashMap<String, String>) -> Option<String> {
+        let bundle = self.bundles.get(&locale)?;
+        let pattern = bundle.get_message(id)?.value()?;
+
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(name.as_str(), value.as_str());
+        }
+
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+        if !errors.is_empty() {
+            log::warn!("Failed to format message '{}' for {}: {:?}", id, locale, errors);
+            return None;
+        }
+        Some(text.into_owned())
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Shared localizer built on first use
+pub fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(Localizer::new)
+}
+
+static PREFERRED_LOCALE: RwLock<Locale> = RwLock::new(Locale::English);
+
+/// Set the locale messages shown to the user are rendered in
+///
+/// The desktop app calls this at startup and whenever the user changes
+/// the language in their preferences.
+pub fn set_preferred_locale(locale: Locale) {
+    match PREFERRED_LOCALE.write() {
+        Ok(mut preferred) => *preferred = locale,
+        Err(poisoned) => *poisoned.into_inner() = locale,
+    }
+}
+
+/// Locale messages shown to the user are currently rendered in
+pub fn preferred_locale() -> Locale {
+    match PREFERRED_LOCALE.read() {
+        Ok(preferred) => *preferred,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// Render an error for the UI, prefixed with what the user was trying to do
+///
+/// `context_id` names a message such as `ui-failed-start-playback`; both the
+/// context and the error are rendered in the preferred locale.
+pub fn localized_error(context_id: &str, error: &AutomationError) -> String {
+    let locale = preferred_locale();
+    LocalizedMessage::new("ui-error-with-context")
+        .with_arg("context", LocalizedMessage::new(context_id).format(locale))
+        .with_arg("error", error.localize(locale))
+        .format(locale)
+}
+
+impl AutomationError {
+    /// Message id and arguments describing this error
+    pub fn localized_message(&self) -> LocalizedMessage {
+        match self {
+            AutomationError::UnsupportedPlatform { platform } => {
+                LocalizedMessage::new("error-unsupported-platform").with_arg("platform", platform)
+            }
+            AutomationError::PermissionDenied { operation } => {
+                LocalizedMessage::new("error-permission-denied").with_arg("operation", operation)
+            }
+            AutomationError::RecordingError { message } => {
+                LocalizedMessage::new("error-recording").with_arg("message", message)
+            }
+            AutomationError::PlaybackError { message } => {
+                LocalizedMessage::new("error-playback").with_arg("message", message)
+            }
+            AutomationError::ScriptError { message } => {
+                LocalizedMessage::new("error-script").with_arg("message", message)
+            }
+            AutomationError::IoError { message } => {
+                LocalizedMessage::new("error-io").with_arg("message", message)
+            }
+            AutomationError::SerializationError { message } => {
+                LocalizedMessage::new("error-serialization").with_arg("message", message)
+            }
+            AutomationError::ConfigError { message } => {
+                LocalizedMessage::new("error-config").with_arg("message", message)
+            }
+            AutomationError::SystemError { message } => {
+                LocalizedMessage::new("error-system").with_arg("message", message)
+            }
+            AutomationError::InvalidInput { message } => {
+                LocalizedMessage::new("error-invalid-input").with_arg("message", message)
+            }
+            AutomationError::Timeout { operation } => {
+                LocalizedMessage::new("error-timeout").with_arg("operation", operation)
+            }
+            AutomationError::CoreUnavailable { core_type } => {
+                LocalizedMessage::new("error-core-unavailable").with_arg("core_type", core_type)
+            }
+            AutomationError::CoreHealthCheckFailed { core_type, reason } => {
+                LocalizedMessage::new("error-core-health-check-failed")
+                    .with_arg("core_type", core_type)
+                    .with_arg("reason", reason)
+            }
+            AutomationError::FallbackFailed { reason } => {
+                LocalizedMessage::new("error-fallback-failed").with_arg("reason", reason)
+            }
+            AutomationError::RuntimeFailure { operation, reason } => {
+                LocalizedMessage::new("error-runtime-failure")
+                    .with_arg("operation", operation)
+                    .with_arg("reason", reason)
+            }
+            AutomationError::PerformanceDegradation { metric, details } => {
+                LocalizedMessage::new("error-performance-degradation")
+                    .with_arg("metric", metric)
+                    .with_arg("details", details)
+            }
+            AutomationError::DependencyMissing { dependency, suggestion } => {
+                LocalizedMessage::new("error-dependency-missing")
+                    .with_arg("dependency", dependency)
+                    .with_arg("suggestion", suggestion)
+            }
+        }
+    }
+}
+
+impl Localize for AutomationError {
+    fn localize(&self, locale: Locale) -> String {
+        self.localized_message().format(locale)
+    }
+}
+
+impl AutomationError {
+    /// Message shown to the user, rendered in the preferred locale
+    pub fn user_message(&self) -> String {
+        self.localize(preferred_locale())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_errors() -> Vec<AutomationError> {
+        vec![
+            AutomationError::UnsupportedPlatform { platform: "freebsd".to_string() },
+            AutomationError::PermissionDenied { operation: "mouse_control".to_string() },
+            AutomationError::PlaybackError { message: "window closed".to_string() },
+            AutomationError::CoreHealthCheckFailed { core_type: "python".to_string(), reason: "no response".to_string() },
+            AutomationError::DependencyMissing { dependency: "pyautogui".to_string(), suggestion: "pip install pyautogui".to_string() },
+        ]
+    }
+
+    #[test]
+    fn test_english_messages_match_display() {
+        for error in sample_errors() {
+            assert_eq!(error.localize(Locale::English), error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_vietnamese_messages_are_translated() {
+        let error = AutomationError::PermissionDenied { operation: "mouse_control".to_string() };
+        let message = error.localize(Locale::Vietnamese);
+
+        assert_eq!(message, "Không có quyền thực hiện: mouse_control");
+        assert_ne!(message, error.to_string());
+    }
+
+    #[test]
+    fn test_every_english_message_has_vietnamese_translation() {
+        let localizer = localizer();
+        for line in EN_ERRORS.lines() {
+            if let Some((id, _)) = line.split_once(" = ") {
+                assert!(localizer.has_message(Locale::Vietnamese, id.trim()), "missing vi message: {}", id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_message_falls_back_to_id() {
+        let message = LocalizedMessage::new("does-not-exist");
+        assert_eq!(message.format(Locale::Vietnamese), "does-not-exist");
+    }
+
+    #[test]
+    fn test_localized_error_follows_preferred_locale() {
+        let error = AutomationError::PermissionDenied { operation: "mouse_control".to_string() };
+
+        set_preferred_locale(Locale::Vietnamese);
+        let vietnamese = localized_error("ui-failed-start-playback", &error);
+        set_preferred_locale(Locale::English);
+        let english = localized_error("ui-failed-start-playback", &error);
+
+        assert_eq!(vietnamese, "Không thể bắt đầu phát lại: Không có quyền thực hiện: mouse_control");
+        assert_eq!(english, format!("Failed to start playback: {}", error));
+        assert_eq!(preferred_locale(), Locale::English);
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("vi-VN"), Some(Locale::Vietnamese));
+        assert_eq!(Locale::from_tag("en_US"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("fr"), None);
+        assert_eq!(serde_json::to_string(&Locale::Vietnamese).unwrap(), "\"vi\"");
+    }
+}
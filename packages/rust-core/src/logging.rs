@@ -10,6 +10,7 @@ use std::sync::{Arc, Mutex};
 use tracing::{info, warn, error, debug, trace};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 use chrono::{DateTime, Utc};
+use crate::bounded_buffer::{BoundedBuffer, BufferStats, OverflowPolicy};
 
 /// Core type identifier for logging
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -37,6 +38,19 @@ pub enum LogLevel {
     Error,
 }
 
+impl LogLevel {
+    /// Ordering from least (trace) to most (error) severe
+    pub fn severity(&self) -> u8 {
+        match self {
+            LogLevel::Trace => 0,
+            LogLevel::Debug => 1,
+            LogLevel::Info => 2,
+            LogLevel::Warn => 3,
+            LogLevel::Error => 4,
+        }
+    }
+}
+
 impl From<LogLevel> for tracing::Level {
     fn from(level: LogLevel) -> Self {
         match level {
@@ -151,7 +165,11 @@ pub struct LoggingConfig {
     pub max_files: u32,
     pub enable_json_format: bool,
     pub enable_performance_logging: bool,
+    /// Maximum log entries kept in memory; the oldest are dropped first
     pub buffer_size: usize,
+    /// Drop entries below `log_level` instead of keeping them in memory
+    #[serde(default)]
+    pub drop_below_level: bool,
 }
 
 impl Default for LoggingConfig {
@@ -169,6 +187,7 @@ impl Default for LoggingConfig {
             enable_json_format: true,
             enable_performance_logging: true,
             buffer_size: 1000,
+            drop_below_level: false,
         }
     }
 }
@@ -236,7 +255,7 @@ impl LogRotationManager {
 pub struct AutomationLogger {
     config: LoggingConfig,
     rotation_manager: LogRotationManager,
-    log_buffer: Arc<Mutex<Vec<LogEntry>>>,
+    log_buffer: Arc<Mutex<BoundedBuffer<LogEntry>>>,
     performance_metrics: Arc<Mutex<HashMap<(OperationType, CoreType), PerformanceLogMetrics>>>,
     _file_appender: Option<tracing_appender::non_blocking::WorkerGuard>,
 }
@@ -314,7 +333,7 @@ impl AutomationLogger {
         Ok(Self {
             config: config.clone(),
             rotation_manager,
-            log_buffer: Arc::new(Mutex::new(Vec::with_capacity(config.buffer_size))),
+            log_buffer: Arc::new(Mutex::new(BoundedBuffer::new("log_buffer", config.buffer_size, OverflowPolicy::Overwrite))),
             performance_metrics: Arc::new(Mutex::new(HashMap::new())),
             _file_appender: file_appender,
         })
@@ -330,6 +349,10 @@ impl AutomationLogger {
         message: String,
         metadata: Option<HashMap<String, serde_json::Value>>,
     ) {
+        if self.config.drop_below_level && level.severity() < self.config.log_level.severity() {
+            return;
+        }
+
         let mut entry = LogEntry::new(level.clone(), core_type.clone(), operation_type.clone(), operation_id.clone(), message.clone());
         
         if let Some(meta) = metadata {
@@ -340,13 +363,10 @@ impl AutomationLogger {
 
         // Add to buffer
         {
+            // The buffer keeps the most recent entries for queries; tracing persists
+            // every entry, so evicting the oldest loses nothing and is not a drop
             let mut buffer = self.log_buffer.lock().unwrap();
             buffer.push(entry.clone());
-            
-            // Flush buffer if it's full
-            if buffer.len() >= self.config.buffer_size {
-                self.flush_buffer_internal(&mut buffer);
-            }
         }
 
         // Log using tracing
@@ -490,7 +510,7 @@ impl AutomationLogger {
         self.flush_buffer_internal(&mut buffer);
     }
 
-    fn flush_buffer_internal(&self, buffer: &mut Vec<LogEntry>) {
+    fn flush_buffer_internal(&self, buffer: &mut BoundedBuffer<LogEntry>) {
         if buffer.is_empty() {
             return;
         }
@@ -501,6 +521,11 @@ impl AutomationLogger {
         buffer.clear();
     }
 
+    /// Occupancy and drop counters of the in-memory log buffer
+    pub fn buffer_stats(&self) -> BufferStats {
+        self.log_buffer.lock().unwrap().stats()
+    }
+
     /// Get recent log entries from buffer
     pub fn get_recent_logs(&self, limit: usize) -> Vec<LogEntry> {
         let buffer = self.log_buffer.lock().unwrap();
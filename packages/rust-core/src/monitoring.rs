@@ -11,6 +11,9 @@ use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 use crate::logging::{CoreType, OperationType, LogLevel, get_logger};
 use crate::error::{AutomationError, Result, ErrorSeverity};
+use crate::bounded_buffer::{buffer_registry, BoundedBuffer, BufferRegistry, BufferStats, OverflowPolicy};
+use crate::ai_budget::BudgetAlert;
+use crate::crash_loop::{CircuitBreakerStatus, CircuitState, CrashLoopConfig, CrashLoopGuard, RestartDecision, Subsystem};
 
 /// Health status levels
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -124,6 +127,22 @@ pub struct MonitoringConfig {
     pub enable_continuous_monitoring: bool,
     pub enable_predictive_alerts: bool,
     pub data_retention_days: u32,
+    #[serde(default)]
+    pub crash_loop: CrashLoopConfig,
+    /// Maximum metrics entries kept in memory; the oldest are dropped first
+    #[serde(default = "default_max_metrics_entries")]
+    pub max_metrics_entries: usize,
+    /// Maximum alerts kept in the alert history; the oldest are dropped first
+    #[serde(default = "default_max_alert_history")]
+    pub max_alert_history: usize,
+}
+
+fn default_max_metrics_entries() -> usize {
+    10_000
+}
+
+fn default_max_alert_history() -> usize {
+    1_000
 }
 
 impl Default for MonitoringConfig {
@@ -135,6 +154,9 @@ impl Default for MonitoringConfig {
             enable_continuous_monitoring: true,
             enable_predictive_alerts: true,
             data_retention_days: 30, // Keep 30 days of monitoring data
+            crash_loop: CrashLoopConfig::default(),
+            max_metrics_entries: default_max_metrics_entries(),
+            max_alert_history: default_max_alert_history(),
         }
     }
 }
@@ -164,6 +186,8 @@ pub enum AlertType {
     SystemResourceIssue,
     ConfigurationProblem,
     PredictiveFailure,
+    CrashLoop,
+    BudgetExceeded,
 }
 
 /// Performance metrics for monitoring
@@ -193,27 +217,42 @@ pub struct HealthCheckResult {
 pub struct CoreMonitor {
     config: MonitoringConfig,
     health_info: Arc<RwLock<HashMap<CoreType, CoreHealthInfo>>>,
-    metrics_history: Arc<Mutex<Vec<MonitoringMetrics>>>,
+    metrics_history: Arc<Mutex<BoundedBuffer<MonitoringMetrics>>>,
     active_alerts: Arc<RwLock<HashMap<String, Alert>>>,
-    alert_history: Arc<Mutex<Vec<Alert>>>,
+    alert_history: Arc<Mutex<BoundedBuffer<Alert>>>,
     last_alert_times: Arc<Mutex<HashMap<(CoreType, AlertType), DateTime<Utc>>>>,
     monitoring_active: Arc<RwLock<bool>>,
+    crash_loop_guard: CrashLoopGuard,
+    /// Channels and queues owned by other components
+    buffers: BufferRegistry,
 }
 
 impl CoreMonitor {
     /// Create a new core monitor with configuration
     pub fn new(config: MonitoringConfig) -> Self {
+        let crash_loop_guard = CrashLoopGuard::new(config.crash_loop.clone());
+        let metrics_history = BoundedBuffer::new("metrics_history", config.max_metrics_entries, OverflowPolicy::DropOldest);
+        let alert_history = BoundedBuffer::new("alert_history", config.max_alert_history, OverflowPolicy::DropOldest);
         Self {
             config,
             health_info: Arc::new(RwLock::new(HashMap::new())),
-            metrics_history: Arc::new(Mutex::new(Vec::new())),
+            metrics_history: Arc::new(Mutex::new(metrics_history)),
             active_alerts: Arc::new(RwLock::new(HashMap::new())),
-            alert_history: Arc::new(Mutex::new(Vec::new())),
+            alert_history: Arc::new(Mutex::new(alert_history)),
             last_alert_times: Arc::new(Mutex::new(HashMap::new())),
             monitoring_active: Arc::new(RwLock::new(false)),
+            crash_loop_guard,
+            buffers: buffer_registry().clone(),
         }
     }
 
+    /// Registry of channels and queues included in `get_buffer_stats`
+    ///
+    /// This is the process-wide registry that event channels join on creation.
+    pub fn buffer_registry(&self) -> &BufferRegistry {
+        &self.buffers
+    }
+
     /// Start continuous monitoring
     pub async fn start_monitoring(&self) -> Result<()> {
         let mut active = self.monitoring_active.write().await;
@@ -875,6 +914,12 @@ impl CoreMonitor {
             AlertType::PredictiveFailure => {
                 format!("{} core may fail soon based on trends", core_type)
             }
+            AlertType::CrashLoop => {
+                format!("{} core subsystem is crash looping", core_type)
+            }
+            AlertType::BudgetExceeded => {
+                format!("{} core AI usage exceeded its budget", core_type)
+            }
         }
     }
 
@@ -982,7 +1027,7 @@ impl CoreMonitor {
         let alert_history = self.alert_history.lock().unwrap();
         match limit {
             Some(n) => alert_history.iter().rev().take(n).cloned().collect(),
-            None => alert_history.clone(),
+            None => alert_history.to_vec(),
         }
     }
 
@@ -1017,6 +1062,193 @@ impl CoreMonitor {
         }
     }
 
+    /// Record that a subsystem crashed and is about to be restarted
+    ///
+    /// When the subsystem has restarted too often within the crash-loop
+    /// window its circuit breaker opens, the restart is refused and a
+    /// critical alert carrying the collected crash reasons is raised.
+    pub async fn record_subsystem_restart(&self, subsystem: Subsystem, reason: &str) -> RestartDecision {
+        let decision = self.crash_loop_guard.record_restart(subsystem, reason);
+
+        if let Some(logger) = get_logger() {
+            logger.log_operation(
+                if decision.allowed { LogLevel::Warn } else { LogLevel::Error },
+                Self::subsystem_core_type(subsystem),
+                OperationType::ErrorHandling,
+                format!("restart_{}", subsystem),
+                format!(
+                    "Subsystem {} restarting after crash ({} restarts in window): {}",
+                    subsystem, decision.status.recent_restarts, reason
+                ),
+                None,
+            );
+        }
+
+        if decision.tripped {
+            self.raise_crash_loop_alert(&decision.status).await;
+        }
+
+        decision
+    }
+
+    /// Whether a subsystem may currently be restarted
+    pub fn can_restart_subsystem(&self, subsystem: Subsystem) -> bool {
+        self.crash_loop_guard.can_restart(subsystem)
+    }
+
+    /// Circuit breaker status of every subsystem that has restarted
+    pub fn get_circuit_breaker_status(&self) -> Vec<CircuitBreakerStatus> {
+        self.crash_loop_guard.statuses()
+    }
+
+    /// Manually close a subsystem's circuit breaker and resolve its crash-loop alerts
+    pub async fn reset_circuit_breaker(&self, subsystem: Subsystem) {
+        let was_open = self.crash_loop_guard.reset(subsystem);
+
+        let alert_ids: Vec<String> = {
+            let active_alerts = self.active_alerts.read().await;
+            active_alerts
+                .values()
+                .filter(|a| a.alert_type == AlertType::CrashLoop)
+                .filter(|a| a.metadata.get("subsystem") == Some(&serde_json::json!(subsystem)))
+                .map(|a| a.id.clone())
+                .collect()
+        };
+        for alert_id in alert_ids {
+            let _ = self.resolve_alert(&alert_id).await;
+        }
+
+        if let Some(logger) = get_logger() {
+            logger.log_operation(
+                LogLevel::Info,
+                Self::subsystem_core_type(subsystem),
+                OperationType::ErrorHandling,
+                format!("reset_breaker_{}", subsystem),
+                format!("Circuit breaker for {} reset (was open: {})", subsystem, was_open),
+                None,
+            );
+        }
+    }
+
+    /// Raise a critical crash-loop alert
+    ///
+    /// Bypasses the cooldown and rate limit applied to health alerts, since
+    /// the subsystem stays down until someone acts on it.
+    async fn raise_crash_loop_alert(&self, status: &CircuitBreakerStatus) {
+        let core_type = Self::subsystem_core_type(status.subsystem);
+        let alert_id = uuid::Uuid::new_v4().to_string();
+        let message = format!(
+            "{} crashed {} times within {} minutes; automatic restarts are disabled",
+            status.subsystem, status.recent_restarts, self.config.crash_loop.window_minutes
+        );
+
+        let mut metadata = HashMap::new();
+        metadata.insert("subsystem".to_string(), serde_json::json!(status.subsystem));
+        metadata.insert("restart_count".to_string(), serde_json::json!(status.recent_restarts));
+        metadata.insert("window_minutes".to_string(), serde_json::json!(self.config.crash_loop.window_minutes));
+        metadata.insert(
+            "crash_reasons".to_string(),
+            serde_json::json!(status.crashes.iter().map(|c| c.reason.clone()).collect::<Vec<_>>()),
+        );
+
+        let alert = Alert {
+            id: alert_id.clone(),
+            alert_type: AlertType::CrashLoop,
+            core_type: core_type.clone(),
+            severity: HealthIssueSeverity::Critical,
+            message: message.clone(),
+            triggered_at: Utc::now(),
+            resolved_at: None,
+            suggested_actions: vec![
+                "Review the crash reasons attached to this alert".to_string(),
+                format!("Fix the underlying issue, then reset the {} circuit breaker", status.subsystem),
+            ],
+            metadata,
+        };
+
+        {
+            let mut active_alerts = self.active_alerts.write().await;
+            active_alerts.insert(alert_id.clone(), alert.clone());
+        }
+
+        {
+            let mut alert_history = self.alert_history.lock().unwrap();
+            alert_history.push(alert);
+        }
+
+        if let Some(logger) = get_logger() {
+            logger.log_operation(
+                LogLevel::Error,
+                core_type,
+                OperationType::ErrorHandling,
+                format!("alert_{}", alert_id),
+                format!("Alert triggered: {}", message),
+                None,
+            );
+        }
+    }
+
+    /// Raise an alert for an exceeded AI token or cost budget
+    ///
+    /// The budget raises each limit once per day or run, so no cooldown is
+    /// applied.
+    pub async fn raise_budget_alert(&self, budget_alert: &BudgetAlert) {
+        let alert_id = uuid::Uuid::new_v4().to_string();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("scope".to_string(), serde_json::json!(budget_alert.scope));
+        metadata.insert("kind".to_string(), serde_json::json!(budget_alert.kind));
+        metadata.insert("limit".to_string(), serde_json::json!(budget_alert.limit));
+        metadata.insert("used".to_string(), serde_json::json!(budget_alert.used));
+        if let Some(ref run_id) = budget_alert.run_id {
+            metadata.insert("run_id".to_string(), serde_json::json!(run_id));
+        }
+
+        let alert = Alert {
+            id: alert_id.clone(),
+            alert_type: AlertType::BudgetExceeded,
+            core_type: CoreType::Rust,
+            severity: HealthIssueSeverity::High,
+            message: budget_alert.message.clone(),
+            triggered_at: budget_alert.triggered_at,
+            resolved_at: None,
+            suggested_actions: vec![
+                "Review AI usage in the budget status".to_string(),
+                "Raise the budget limits or wait for the next day or run".to_string(),
+            ],
+            metadata,
+        };
+
+        {
+            let mut active_alerts = self.active_alerts.write().await;
+            active_alerts.insert(alert_id.clone(), alert.clone());
+        }
+
+        {
+            let mut alert_history = self.alert_history.lock().unwrap();
+            alert_history.push(alert);
+        }
+
+        if let Some(logger) = get_logger() {
+            logger.log_operation(
+                LogLevel::Warn,
+                CoreType::Rust,
+                OperationType::ErrorHandling,
+                format!("alert_{}", alert_id),
+                format!("Alert triggered: {}", budget_alert.message),
+                None,
+            );
+        }
+    }
+
+    /// Core a subsystem's alerts are attributed to
+    fn subsystem_core_type(subsystem: Subsystem) -> CoreType {
+        match subsystem {
+            Subsystem::PythonCore => CoreType::Python,
+            Subsystem::FocusService | Subsystem::AiProvider => CoreType::Rust,
+        }
+    }
+
     /// Clean up old data
     async fn cleanup_old_data(&self) {
         let retention_cutoff = Utc::now() - chrono::Duration::days(self.config.data_retention_days as i64);
@@ -1064,6 +1296,22 @@ impl CoreMonitor {
         }
     }
 
+    /// Occupancy and drop counters of the in-memory buffers
+    ///
+    /// Includes the global logger's buffer when a logger is initialized, and
+    /// every live channel or queue in the buffer registry.
+    pub fn get_buffer_stats(&self) -> Vec<BufferStats> {
+        let mut stats = vec![
+            self.metrics_history.lock().unwrap().stats(),
+            self.alert_history.lock().unwrap().stats(),
+        ];
+        if let Some(logger) = get_logger() {
+            stats.push(logger.buffer_stats());
+        }
+        stats.extend(self.buffers.stats());
+        stats
+    }
+
     /// Get monitoring statistics
     pub async fn get_monitoring_stats(&self) -> MonitoringStats {
         let metrics_count = self.metrics_history.lock().unwrap().len();
@@ -1071,11 +1319,24 @@ impl CoreMonitor {
         let alert_history_count = self.alert_history.lock().unwrap().len();
         let health_info = self.health_info.read().await;
 
+        let open_circuit_breakers = self
+            .crash_loop_guard
+            .statuses()
+            .iter()
+            .filter(|status| status.state == CircuitState::Open)
+            .count();
+
+        let buffers = self.get_buffer_stats();
+        let dropped_entries = buffers.iter().map(|b| b.dropped).sum();
+
         MonitoringStats {
             metrics_count,
             active_alerts_count,
             alert_history_count,
             cores_monitored: health_info.len(),
+            open_circuit_breakers,
+            buffers,
+            dropped_entries,
             monitoring_active: *self.monitoring_active.read().await,
             last_cleanup: Utc::now(), // This would be tracked in a real implementation
         }
@@ -1092,6 +1353,8 @@ impl Clone for CoreMonitor {
             alert_history: Arc::clone(&self.alert_history),
             last_alert_times: Arc::clone(&self.last_alert_times),
             monitoring_active: Arc::clone(&self.monitoring_active),
+            crash_loop_guard: self.crash_loop_guard.clone(),
+            buffers: self.buffers.clone(),
         }
     }
 }
@@ -1103,10 +1366,245 @@ pub struct MonitoringStats {
     pub active_alerts_count: usize,
     pub alert_history_count: usize,
     pub cores_monitored: usize,
+    pub open_circuit_breakers: usize,
+    /// Per-buffer occupancy and drop counters
+    pub buffers: Vec<BufferStats>,
+    /// Entries dropped across all buffers because they were full
+    pub dropped_entries: u64,
     pub monitoring_active: bool,
     pub last_cleanup: DateTime<Utc>,
 }
 
+/// Resource usage of a single process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessMetrics {
+    pub pid: u32,
+    /// CPU usage since the previous sample of the same process, where 100 is
+    /// one fully used core; `None` for the first sample
+    pub cpu_usage_percent: Option<f64>,
+    /// Resident memory
+    pub memory_usage_mb: f64,
+    pub sampled_at: DateTime<Utc>,
+}
+
+/// Samples CPU and memory usage of arbitrary processes
+///
+/// CPU usage is the change in cumulative CPU time between two samples, so the
+/// sampler remembers the previous reading of each process.
+#[derive(Debug, Default)]
+pub struct ProcessSampler {
+    previous: HashMap<u32, (f64, Instant)>,
+}
+
+impl ProcessSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sample the process with the given pid
+    pub fn sample(&mut self, pid: u32) -> Result<ProcessMetrics> {
+        let usage = read_process_usage(pid)?;
+        let now = Instant::now();
+
+        let cpu_usage_percent = self.previous.get(&pid).and_then(|(cpu_seconds, sampled_at)| {
+            let elapsed = now.duration_since(*sampled_at).as_secs_f64();
+            if elapsed > 0.0 {
+                Some(((usage.cpu_seconds - cpu_seconds) / elapsed * 100.0).max(0.0))
+            } else {
+                None
+            }
+        });
+        self.previous.insert(pid, (usage.cpu_seconds, now));
+
+        Ok(ProcessMetrics {
+            pid,
+            cpu_usage_percent,
+            memory_usage_mb: usage.resident_bytes as f64 / (1024.0 * 1024.0),
+            sampled_at: Utc::now(),
+        })
+    }
+}
+
+/// Cumulative CPU time and resident memory of a process
+struct ProcessUsage {
+    cpu_seconds: f64,
+    resident_bytes: u64,
+}
+
+/// Clock ticks per second used by /proc (USER_HZ is 100 on supported kernels)
+#[cfg(target_os = "linux")]
+const PROC_CLOCK_TICKS: f64 = 100.0;
+
+#[cfg(target_os = "linux")]
+fn read_process_usage(pid: u32) -> Result<ProcessUsage> {
+    let read = |file: &str| {
+        std::fs::read_to_string(format!("/proc/{}/{}", pid, file)).map_err(|e| AutomationError::SystemError {
+            message: format!("Failed to read /proc/{}/{}: {}", pid, file, e),
+        })
+    };
+    let stat = read("stat")?;
+    let status = read("status")?;
+
+    let cpu_ticks = parse_proc_stat_cpu_ticks(&stat).ok_or_else(|| AutomationError::SystemError {
+        message: format!("Unexpected /proc/{}/stat format", pid),
+    })?;
+    let rss_kb = status
+        .lines()
+        .find(|line| line.starts_with("VmRSS:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .unwrap_or(0);
+
+    Ok(ProcessUsage {
+        cpu_seconds: cpu_ticks as f64 / PROC_CLOCK_TICKS,
+        resident_bytes: rss_kb * 1024,
+    })
+}
+
+/// utime + stime from a /proc/<pid>/stat line
+///
+/// The command name may contain spaces, so fields are counted after its
+/// closing parenthesis.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_stat_cpu_ticks(stat: &str) -> Option<u64> {
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    // Fields 14 and 15 of the full line; the first field after ")" is field 3
+    let utime = fields.get(11)?.parse::<u64>().ok()?;
+    let stime = fields.get(12)?.parse::<u64>().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(target_os = "macos")]
+fn read_process_usage(pid: u32) -> Result<ProcessUsage> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "time=,rss=", "-p", &pid.to_string()])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.split_whitespace();
+
+    match (fields.next().and_then(parse_cpu_time), fields.next().and_then(|kb| kb.parse::<u64>().ok())) {
+        (Some(cpu_seconds), Some(rss_kb)) => Ok(ProcessUsage {
+            cpu_seconds,
+            resident_bytes: rss_kb * 1024,
+        }),
+        _ => Err(AutomationError::SystemError {
+            message: format!("Process {} not found", pid),
+        }),
+    }
+}
+
+/// Parse a `ps` cumulative CPU time such as `1:02.50` or `1-02:03:04`
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_cpu_time(value: &str) -> Option<f64> {
+    let (days, clock) = match value.split_once('-') {
+        Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+        None => (0.0, value),
+    };
+
+    let mut seconds = 0.0;
+    for part in clock.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(days * 86_400.0 + seconds)
+}
+
+#[cfg(target_os = "windows")]
+fn read_process_usage(pid: u32) -> Result<ProcessUsage> {
+    use winapi::shared::minwindef::{DWORD, FILETIME, FALSE};
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetProcessTimes, OpenProcess};
+    use winapi::um::psapi::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use winapi::um::winnt::{PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ};
+
+    // FILETIME counts 100ns intervals
+    let filetime_seconds = |time: &FILETIME| {
+        (((time.dwHighDateTime as u64) << 32) | time.dwLowDateTime as u64) as f64 / 10_000_000.0
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ, FALSE, pid);
+        if handle.is_null() {
+            return Err(AutomationError::SystemError {
+                message: format!("Process {} not found or not accessible", pid),
+            });
+        }
+
+        let mut creation: FILETIME = std::mem::zeroed();
+        let mut exit: FILETIME = std::mem::zeroed();
+        let mut kernel: FILETIME = std::mem::zeroed();
+        let mut user: FILETIME = std::mem::zeroed();
+        let mut counters: PROCESS_MEMORY_COUNTERS = std::mem::zeroed();
+        counters.cb = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as DWORD;
+
+        let times_ok = GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) != 0;
+        let memory_ok = GetProcessMemoryInfo(handle, &mut counters, counters.cb) != 0;
+        CloseHandle(handle);
+
+        if !times_ok || !memory_ok {
+            return Err(AutomationError::SystemError {
+                message: format!("Failed to read usage of process {}", pid),
+            });
+        }
+
+        Ok(ProcessUsage {
+            cpu_seconds: filetime_seconds(&kernel) + filetime_seconds(&user),
+            resident_bytes: counters.WorkingSetSize as u64,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn read_process_usage(_pid: u32) -> Result<ProcessUsage> {
+    Err(AutomationError::UnsupportedPlatform {
+        platform: std::env::consts::OS.to_string(),
+    })
+}
+
+/// Find a running process by executable name, returning its pid
+pub fn find_process_by_name(name: &str) -> Option<u32> {
+    #[cfg(target_os = "windows")]
+    {
+        use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+        use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS};
+
+        let image = if name.to_lowercase().ends_with(".exe") {
+            name.to_lowercase()
+        } else {
+            format!("{}.exe", name.to_lowercase())
+        };
+
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+            if snapshot == INVALID_HANDLE_VALUE {
+                return None;
+            }
+
+            let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+            entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+            let mut found = None;
+            let mut has_entry = Process32FirstW(snapshot, &mut entry) != 0;
+            while has_entry {
+                let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                if String::from_utf16_lossy(&entry.szExeFile[..len]).to_lowercase() == image {
+                    found = Some(entry.th32ProcessID);
+                    break;
+                }
+                has_entry = Process32NextW(snapshot, &mut entry) != 0;
+            }
+            CloseHandle(snapshot);
+            found
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let output = std::process::Command::new("pgrep").args(["-x", name]).output().ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|pid| pid.trim().parse::<u32>().ok())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1267,4 +1765,108 @@ mod tests {
         let active_alerts = monitor.get_active_alerts().await;
         assert!(active_alerts.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_buffer_stats_include_event_channels() {
+        let monitor = CoreMonitor::new(MonitoringConfig::default());
+        let (sender, _receiver) = crate::event_channel::named_event_channel("test_monitor_events", 1);
+        sender.send(1).unwrap();
+        assert!(sender.send(2).is_err());
+
+        let channel = monitor
+            .get_buffer_stats()
+            .into_iter()
+            .find(|stats| stats.name == "test_monitor_events")
+            .unwrap();
+        assert_eq!((channel.len, channel.dropped), (1, 1));
+        assert!(monitor.get_monitoring_stats().await.dropped_entries >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_crash_loop_trips_breaker_and_raises_alert() {
+        let mut config = MonitoringConfig::default();
+        config.crash_loop.max_restarts = 2;
+        let monitor = CoreMonitor::new(config);
+
+        assert!(monitor.record_subsystem_restart(Subsystem::PythonCore, "exit code 1").await.allowed);
+        assert!(monitor.record_subsystem_restart(Subsystem::PythonCore, "exit code 1").await.allowed);
+
+        let decision = monitor.record_subsystem_restart(Subsystem::PythonCore, "segfault").await;
+        assert!(!decision.allowed);
+        assert!(!monitor.can_restart_subsystem(Subsystem::PythonCore));
+
+        let alerts = monitor.get_active_alerts().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].alert_type, AlertType::CrashLoop);
+        assert_eq!(alerts[0].severity, HealthIssueSeverity::Critical);
+        assert_eq!(alerts[0].core_type, CoreType::Python);
+        assert_eq!(
+            alerts[0].metadata["crash_reasons"],
+            serde_json::json!(["exit code 1", "exit code 1", "segfault"])
+        );
+        assert_eq!(monitor.get_monitoring_stats().await.open_circuit_breakers, 1);
+
+        monitor.reset_circuit_breaker(Subsystem::PythonCore).await;
+        assert!(monitor.can_restart_subsystem(Subsystem::PythonCore));
+        assert!(monitor.get_active_alerts().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_budget_alerts_become_monitor_alerts() {
+        let monitor = CoreMonitor::new(MonitoringConfig::default());
+        let budget = crate::ai_budget::TokenBudget::new(crate::ai_budget::TokenBudgetConfig {
+            daily: crate::ai_budget::BudgetLimits { max_tokens: Some(100), max_cost_usd: None },
+            ..Default::default()
+        });
+
+        let raised = budget.record_usage(crate::ai_budget::AITokenUsage::new(80, 40));
+        monitor.raise_budget_alert(&raised[0]).await;
+
+        let alerts = monitor.get_active_alerts().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].alert_type, AlertType::BudgetExceeded);
+        assert_eq!(alerts[0].message, raised[0].message);
+        assert_eq!(alerts[0].metadata["scope"], serde_json::json!("daily"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_history_is_bounded() {
+        let config = MonitoringConfig {
+            max_metrics_entries: 5,
+            ..Default::default()
+        };
+        let monitor = CoreMonitor::new(config);
+
+        for i in 0..8 {
+            monitor.record_metrics(MonitoringMetrics {
+                core_type: CoreType::Rust,
+                timestamp: Utc::now(),
+                response_time_ms: i,
+                success: true,
+                error_code: None,
+                cpu_usage_percent: None,
+                memory_usage_mb: None,
+                operation_type: OperationType::Recording,
+            }).await;
+        }
+
+        let stats = monitor.get_monitoring_stats().await;
+        assert_eq!(stats.metrics_count, 5);
+        let metrics_buffer = stats.buffers.iter().find(|b| b.name == "metrics_history").unwrap();
+        assert_eq!(metrics_buffer.capacity, 5);
+        assert_eq!(metrics_buffer.dropped, 3);
+        assert!(stats.dropped_entries >= 3);
+    }
+
+    #[test]
+    fn test_process_cpu_time_parsing() {
+        let stat = "4242 (my app (1)) S 1 4242 4242 0 -1 4194560 500 0 0 0 250 50 0 0 20 0 4 0 100 0 0";
+        assert_eq!(parse_proc_stat_cpu_ticks(stat), Some(300));
+        assert_eq!(parse_proc_stat_cpu_ticks("garbage"), None);
+
+        assert_eq!(parse_cpu_time("0:01.50"), Some(1.5));
+        assert_eq!(parse_cpu_time("1:02:03"), Some(3723.0));
+        assert_eq!(parse_cpu_time("1-00:00:10"), Some(86_410.0));
+        assert_eq!(parse_cpu_time("n/a"), None);
+    }
 }
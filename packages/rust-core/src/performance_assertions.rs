@@ -0,0 +1,393 @@
+//! Performance assertions on system metrics during playback
+//!
+//! Scripts and suites can assert that a process stays within resource limits
+//! while they run, e.g. "the app's CPU stays under 80%" or "the target's
+//! memory stays under 1 GB". `MetricAssertionMonitor` samples the targeted
+//! processes through the monitoring subsystem for the duration of the run and
+//! evaluates every assertion when playback finishes.
+
+use crate::logging::{get_logger, CoreType, LogLevel, OperationType};
+use crate::monitoring::{find_process_by_name, ProcessSampler};
+use crate::script::ScriptData;
+use crate::{AutomationError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Key in a script's metadata `additional_data` holding its assertions
+pub const SCRIPT_ASSERTIONS_KEY: &str = "performance_assertions";
+
+/// Default interval between metric samples
+pub const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1000;
+
+/// Metric an assertion applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    /// CPU usage, where 100 is one fully used core
+    CpuPercent,
+    /// Resident memory in megabytes
+    MemoryMb,
+}
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MetricKind::CpuPercent => write!(f, "CPU %"),
+            MetricKind::MemoryMb => write!(f, "memory MB"),
+        }
+    }
+}
+
+/// How samples are combined before comparing against the limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricAggregation {
+    /// The limit must hold for every sample
+    #[default]
+    Max,
+    Average,
+}
+
+/// Process whose metrics are asserted on
+///
+/// With neither a name nor a pid the automation process itself is sampled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProcessTarget {
+    /// Executable name, resolved while the run is in progress so the process
+    /// may be launched by the script itself
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+impl fmt::Display for ProcessTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.name, self.pid) {
+            (Some(name), _) => write!(f, "{}", name),
+            (None, Some(pid)) => write!(f, "pid {}", pid),
+            (None, None) => write!(f, "automation core"),
+        }
+    }
+}
+
+/// Upper bound on a process metric during a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricAssertion {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub metric: MetricKind,
+    #[serde(default)]
+    pub process: ProcessTarget,
+    /// Limit in the metric's unit (percent or megabytes)
+    pub max: f64,
+    #[serde(default)]
+    pub aggregation: MetricAggregation,
+}
+
+impl MetricAssertion {
+    /// Name shown in reports
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{} {} <= {}", self.process, self.metric, self.max),
+        }
+    }
+
+    /// Evaluate the assertion against the samples collected for its process
+    pub fn evaluate(&self, summary: &MetricSummary) -> PerformanceAssertionResult {
+        let observed = summary.value(self.aggregation);
+        let (status, message) = match observed {
+            None => (
+                AssertionStatus::Inconclusive,
+                format!("{}: no samples collected for {}", self.label(), self.process),
+            ),
+            Some(value) if value <= self.max => (
+                AssertionStatus::Passed,
+                format!("{}: observed {:.1} within limit {}", self.label(), value, self.max),
+            ),
+            Some(value) => (
+                AssertionStatus::Failed,
+                format!("{}: observed {:.1} exceeds limit {}", self.label(), value, self.max),
+            ),
+        };
+
+        PerformanceAssertionResult {
+            assertion: self.clone(),
+            status,
+            observed,
+            sample_count: summary.count,
+            message,
+        }
+    }
+}
+
+/// Outcome of a performance assertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssertionStatus {
+    Passed,
+    Failed,
+    /// The process was never sampled, e.g. it was not running
+    Inconclusive,
+}
+
+/// Result of a performance assertion, as reported with the playback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceAssertionResult {
+    pub assertion: MetricAssertion,
+    pub status: AssertionStatus,
+    /// Aggregated value compared against the limit
+    pub observed: Option<f64>,
+    pub sample_count: usize,
+    pub message: String,
+}
+
+/// Running aggregate of one metric of one process
+#[derive(Debug, Clone, Default)]
+pub struct MetricSummary {
+    pub count: usize,
+    pub sum: f64,
+    pub max: f64,
+}
+
+impl MetricSummary {
+    pub fn record(&mut self, value: f64) {
+        self.max = if self.count == 0 { value } else { self.max.max(value) };
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Aggregated value, or `None` without samples
+    pub fn value(&self, aggregation: MetricAggregation) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(match aggregation {
+            MetricAggregation::Max => self.max,
+            MetricAggregation::Average => self.sum / self.count as f64,
+        })
+    }
+}
+
+/// Samples collected for one process target
+#[derive(Debug, Default)]
+struct TargetSamples {
+    /// Pid the target name resolved to
+    pid: Option<u32>,
+    cpu: MetricSummary,
+    memory: MetricSummary,
+}
+
+type SampleTable = Arc<Mutex<HashMap<ProcessTarget, TargetSamples>>>;
+
+/// Samples asserted processes in the background while a script plays
+pub struct MetricAssertionMonitor {
+    assertions: Vec<MetricAssertion>,
+    samples: SampleTable,
+    stop_sender: mpsc::Sender<()>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricAssertionMonitor {
+    /// Start sampling the processes targeted by `assertions`
+    pub fn start(assertions: Vec<MetricAssertion>, interval: Duration) -> Self {
+        let mut targets: Vec<ProcessTarget> = Vec::new();
+        for assertion in &assertions {
+            if !targets.contains(&assertion.process) {
+                targets.push(assertion.process.clone());
+            }
+        }
+
+        let samples: SampleTable = Arc::new(Mutex::new(HashMap::new()));
+        let (stop_sender, stop_receiver) = mpsc::channel();
+        let thread_samples = Arc::clone(&samples);
+
+        let handle = thread::spawn(move || {
+            let mut sampler = ProcessSampler::new();
+            loop {
+                sample_targets(&mut sampler, &targets, &thread_samples);
+                match stop_receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+        });
+
+        Self {
+            assertions,
+            samples,
+            stop_sender,
+            handle: Some(handle),
+        }
+    }
+
+    /// Stop sampling and evaluate every assertion
+    pub fn finish(mut self) -> Vec<PerformanceAssertionResult> {
+        let _ = self.stop_sender.send(());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+
+        let samples = self.samples.lock().unwrap();
+        let empty = TargetSamples::default();
+        self.assertions
+            .iter()
+            .map(|assertion| {
+                let target = samples.get(&assertion.process).unwrap_or(&empty);
+                match assertion.metric {
+                    MetricKind::CpuPercent => assertion.evaluate(&target.cpu),
+                    MetricKind::MemoryMb => assertion.evaluate(&target.memory),
+                }
+            })
+            .collect()
+    }
+}
+
+fn sample_targets(sampler: &mut ProcessSampler, targets: &[ProcessTarget], samples: &SampleTable) {
+    // Resolving names runs pgrep, so only hold the table lock to read and record
+    let known_pids: Vec<Option<u32>> = {
+        let samples = samples.lock().unwrap();
+        targets.iter().map(|target| samples.get(target).and_then(|entry| entry.pid)).collect()
+    };
+
+    let sampled: Vec<_> = targets
+        .iter()
+        .zip(known_pids)
+        .filter_map(|(target, known_pid)| {
+            let pid = match (target.pid, &target.name) {
+                (Some(pid), _) => Some(pid),
+                (None, Some(name)) => known_pid.or_else(|| find_process_by_name(name)),
+                (None, None) => Some(std::process::id()),
+            };
+            // Not started yet; try again on the next tick
+            pid.map(|pid| (target, pid, sampler.sample(pid)))
+        })
+        .collect();
+
+    let mut samples = samples.lock().unwrap();
+    for (target, pid, result) in sampled {
+        let entry = samples.entry(target.clone()).or_default();
+        match result {
+            Ok(metrics) => {
+                entry.pid = Some(pid);
+                if let Some(cpu) = metrics.cpu_usage_percent {
+                    entry.cpu.record(cpu);
+                }
+                entry.memory.record(metrics.memory_usage_mb);
+            }
+            // The process exited; a named target is resolved again next tick
+            Err(_) => entry.pid = None,
+        }
+    }
+}
+
+/// Assertions declared in a script's metadata
+pub fn script_assertions(script: &ScriptData) -> Result<Vec<MetricAssertion>> {
+    match script.metadata.additional_data.get(SCRIPT_ASSERTIONS_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).map_err(|e| AutomationError::ScriptError {
+            message: format!("Invalid {}: {}", SCRIPT_ASSERTIONS_KEY, e),
+        }),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Log each assertion result, warning on failures
+pub fn log_assertion_results(results: &[PerformanceAssertionResult]) {
+    if let Some(logger) = get_logger() {
+        for result in results {
+            let mut metadata = HashMap::new();
+            metadata.insert("metric".to_string(), serde_json::json!(result.assertion.metric));
+            metadata.insert("process".to_string(), serde_json::json!(result.assertion.process.to_string()));
+            metadata.insert("max".to_string(), serde_json::json!(result.assertion.max));
+            metadata.insert("observed".to_string(), serde_json::json!(result.observed));
+            metadata.insert("sample_count".to_string(), serde_json::json!(result.sample_count));
+
+            logger.log_operation(
+                if result.status == AssertionStatus::Passed { LogLevel::Info } else { LogLevel::Warn },
+                CoreType::Rust,
+                OperationType::PerformanceMonitoring,
+                format!("performance_assertion_{}", chrono::Utc::now().timestamp_millis()),
+                result.message.clone(),
+                Some(metadata),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assertion(metric: MetricKind, max: f64, aggregation: MetricAggregation) -> MetricAssertion {
+        MetricAssertion {
+            name: None,
+            metric,
+            process: ProcessTarget::default(),
+            max,
+            aggregation,
+        }
+    }
+
+    #[test]
+    fn test_assertion_evaluation() {
+        let mut summary = MetricSummary::default();
+        for value in [40.0, 95.0, 45.0] {
+            summary.record(value);
+        }
+
+        let max = assertion(MetricKind::CpuPercent, 80.0, MetricAggregation::Max).evaluate(&summary);
+        assert_eq!(max.status, AssertionStatus::Failed);
+        assert_eq!(max.observed, Some(95.0));
+        assert_eq!(max.sample_count, 3);
+
+        let average = assertion(MetricKind::CpuPercent, 80.0, MetricAggregation::Average).evaluate(&summary);
+        assert_eq!(average.status, AssertionStatus::Passed);
+        assert_eq!(average.observed, Some(60.0));
+
+        let empty = assertion(MetricKind::MemoryMb, 1024.0, MetricAggregation::Max).evaluate(&MetricSummary::default());
+        assert_eq!(empty.status, AssertionStatus::Inconclusive);
+    }
+
+    #[test]
+    fn test_script_assertions_from_metadata() {
+        let mut script = ScriptData::new("rust", "linux");
+        assert!(script_assertions(&script).unwrap().is_empty());
+
+        script.metadata.additional_data.insert(
+            SCRIPT_ASSERTIONS_KEY.to_string(),
+            serde_json::json!([
+                { "metric": "cpu_percent", "process": { "name": "MyApp" }, "max": 80.0 },
+                { "metric": "memory_mb", "max": 1024.0, "aggregation": "average" }
+            ]),
+        );
+        let assertions = script_assertions(&script).unwrap();
+        assert_eq!(assertions.len(), 2);
+        assert_eq!(assertions[0].process.name.as_deref(), Some("MyApp"));
+        assert_eq!(assertions[0].aggregation, MetricAggregation::Max);
+        assert_eq!(assertions[1].label(), "automation core memory MB <= 1024");
+
+        script.metadata.additional_data.insert(SCRIPT_ASSERTIONS_KEY.to_string(), serde_json::json!({ "max": 1 }));
+        assert!(script_assertions(&script).is_err());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    #[test]
+    fn test_monitor_samples_own_process() {
+        let monitor = MetricAssertionMonitor::start(
+            vec![assertion(MetricKind::MemoryMb, 1_000_000.0, MetricAggregation::Max)],
+            Duration::from_millis(10),
+        );
+        thread::sleep(Duration::from_millis(100));
+
+        let results = monitor.finish();
+        assert_eq!(results[0].status, AssertionStatus::Passed);
+        assert!(results[0].sample_count >= 1);
+        assert!(results[0].observed.unwrap() > 0.0);
+    }
+}
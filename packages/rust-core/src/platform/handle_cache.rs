@@ -0,0 +1,357 @@
+//! Shared platform handle cache
+//!
+//! Creating a platform automation instance initializes OS input/capture
+//! APIs, which is too expensive to repeat for every screenshot or
+//! screen-size query. The cache creates the handle lazily on first use,
+//! shares it between threads, and remembers the screen dimensions until the
+//! display configuration changes and the cache is invalidated.
+//!
+//! The platform backends are not safe for concurrent use (Xlib is used
+//! without `XInitThreads`), so the shared handle serializes every call
+//! behind a mutex.
+
+use super::{create_native_platform, PlatformAutomation};
+use crate::display::DisplayInfo;
+use crate::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, RwLock};
+
+/// A platform handle shared between callers
+pub type SharedPlatform = Arc<dyn PlatformAutomation>;
+
+type PlatformFactory = Box<dyn Fn() -> Result<Box<dyn PlatformAutomation>> + Send + Sync>;
+
+/// Lazily initialized, thread-safe cache of the platform handle
+pub struct PlatformHandleCache {
+    factory: PlatformFactory,
+    handle: RwLock<Option<SharedPlatform>>,
+    screen_size: RwLock<Option<(u32, u32)>>,
+    /// Incremented on every invalidation
+    generation: AtomicU64,
+}
+
+impl Default for PlatformHandleCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlatformHandleCache {
+    /// Create a cache backed by the host OS backend
+    ///
+    /// Test overrides are ignored, as the handle is created on whichever
+    /// thread first uses the cache.
+    pub fn new() -> Self {
+        Self::with_factory(create_native_platform)
+    }
+
+    /// Create a cache backed by a custom factory
+    pub fn with_factory<F>(factory: F) -> Self
+    where
+        F: Fn() -> Result<Box<dyn PlatformAutomation>> + Send + Sync + 'static,
+    {
+        Self {
+            factory: Box::new(factory),
+            handle: RwLock::new(None),
+            screen_size: RwLock::new(None),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the platform handle, creating it on first use
+    ///
+    /// Creation errors are returned and not cached, so a later call retries
+    /// (e.g. after the user grants permissions).
+    pub fn get(&self) -> Result<SharedPlatform> {
+        if let Some(handle) = self.handle.read().unwrap().as_ref() {
+            return Ok(Arc::clone(handle));
+        }
+
+        let mut slot = self.handle.write().unwrap();
+        // Another thread may have initialized the handle while we waited
+        if let Some(handle) = slot.as_ref() {
+            return Ok(Arc::clone(handle));
+        }
+
+        let handle: SharedPlatform = Arc::new(SerializedPlatform::new((self.factory)()?));
+        *slot = Some(Arc::clone(&handle));
+        Ok(handle)
+    }
+
+    /// Get the screen dimensions, querying the platform only when not cached
+    pub fn screen_size(&self) -> Result<(u32, u32)> {
+        if let Some(size) = *self.screen_size.read().unwrap() {
+            return Ok(size);
+        }
+
+        let size = self.get()?.get_screen_size()?;
+        *self.screen_size.write().unwrap() = Some(size);
+        Ok(size)
+    }
+
+    /// Drop the cached handle and dimensions
+    ///
+    /// Call when the display configuration changes; the next `get` creates a
+    /// fresh handle. Callers still holding the old handle keep it alive until
+    /// they finish.
+    pub fn invalidate(&self) {
+        *self.handle.write().unwrap() = None;
+        *self.screen_size.write().unwrap() = None;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Drop only the cached dimensions, keeping the handle
+    pub fn invalidate_dimensions(&self) {
+        *self.screen_size.write().unwrap() = None;
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Whether a handle is currently cached
+    pub fn is_initialized(&self) -> bool {
+        self.handle.read().unwrap().is_some()
+    }
+
+    /// Number of invalidations so far, for detecting stale derived data
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+}
+
+/// Platform handle that lets one caller at a time use the wrapped backend
+struct SerializedPlatform {
+    inner: Mutex<Box<dyn PlatformAutomation>>,
+}
+
+impl SerializedPlatform {
+    fn new(platform: Box<dyn PlatformAutomation>) -> Self {
+        Self { inner: Mutex::new(platform) }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Box<dyn PlatformAutomation>> {
+        // A panic inside a platform call leaves the backend usable
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl PlatformAutomation for SerializedPlatform {
+    fn initialize(&mut self) -> Result<()> {
+        self.inner.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner()).initialize()
+    }
+    fn check_permissions(&self) -> Result<bool> { self.lock().check_permissions() }
+    fn request_permissions(&self) -> Result<bool> { self.lock().request_permissions() }
+    fn check_screen_capture_permission(&self) -> Result<bool> { self.lock().check_screen_capture_permission() }
+    fn mouse_move(&self, x: i32, y: i32) -> Result<()> { self.lock().mouse_move(x, y) }
+    fn mouse_click(&self, button: &str) -> Result<()> { self.lock().mouse_click(button) }
+    fn mouse_click_at(&self, x: i32, y: i32, button: &str) -> Result<()> { self.lock().mouse_click_at(x, y, button) }
+    fn mouse_double_click(&self, x: i32, y: i32, button: &str) -> Result<()> {
+        self.lock().mouse_double_click(x, y, button)
+    }
+    fn mouse_drag(&self, from_x: i32, from_y: i32, to_x: i32, to_y: i32, button: &str) -> Result<()> {
+        self.lock().mouse_drag(from_x, from_y, to_x, to_y, button)
+    }
+    fn mouse_scroll(&self, x: i32, y: i32, delta_x: i32, delta_y: i32) -> Result<()> {
+        self.lock().mouse_scroll(x, y, delta_x, delta_y)
+    }
+    fn key_press(&self, key: &str) -> Result<()> { self.lock().key_press(key) }
+    fn key_release(&self, key: &str) -> Result<()> { self.lock().key_release(key) }
+    fn key_type(&self, text: &str) -> Result<()> { self.lock().key_type(text) }
+    fn key_combination(&self, key: &str, modifiers: &[String]) -> Result<()> {
+        self.lock().key_combination(key, modifiers)
+    }
+    fn get_mouse_position(&self) -> Result<(i32, i32)> { self.lock().get_mouse_position() }
+    fn get_screen_size(&self) -> Result<(u32, u32)> { self.lock().get_screen_size() }
+    fn get_displays(&self) -> Result<Vec<DisplayInfo>> { self.lock().get_displays() }
+    fn take_screenshot(&self) -> Result<Vec<u8>> { self.lock().take_screenshot() }
+    fn platform_name(&self) -> &'static str { self.lock().platform_name() }
+}
+
+static PLATFORM_CACHE: OnceLock<Arc<PlatformHandleCache>> = OnceLock::new();
+
+/// Get the platform handle cache for the current thread
+///
+/// This is the process-wide cache, unless a test platform is installed on
+/// the current thread. Work handed to other threads should take the cache
+/// here and pass it along rather than look it up again.
+pub fn platform_cache() -> Arc<PlatformHandleCache> {
+    #[cfg(any(test, feature = "test-harness"))]
+    if let Some(cache) = super::overridden_platform_cache() {
+        return cache;
+    }
+
+    Arc::clone(PLATFORM_CACHE.get_or_init(|| Arc::new(PlatformHandleCache::new())))
+}
+
+/// Get the shared platform handle for the current thread
+pub fn shared_platform() -> Result<SharedPlatform> {
+    platform_cache().get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AutomationError;
+    use std::sync::atomic::AtomicUsize;
+
+    struct FakePlatform {
+        screen_size: (u32, u32),
+        size_queries: Arc<AtomicUsize>,
+    }
+
+    /// Fails if two threads are inside it at once
+    #[derive(Default)]
+    struct ExclusivePlatform {
+        busy: std::sync::atomic::AtomicBool,
+        overlaps: AtomicUsize,
+    }
+
+    impl ExclusivePlatform {
+        fn enter(&self) {
+            if self.busy.swap(true, Ordering::SeqCst) {
+                self.overlaps.fetch_add(1, Ordering::SeqCst);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            self.busy.store(false, Ordering::SeqCst);
+        }
+    }
+
+    impl PlatformAutomation for Arc<ExclusivePlatform> {
+        fn initialize(&mut self) -> Result<()> { Ok(()) }
+        fn check_permissions(&self) -> Result<bool> { Ok(true) }
+        fn request_permissions(&self) -> Result<bool> { Ok(true) }
+        fn mouse_move(&self, _x: i32, _y: i32) -> Result<()> { self.enter(); Ok(()) }
+        fn mouse_click(&self, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_click_at(&self, _x: i32, _y: i32, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_double_click(&self, _x: i32, _y: i32, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_drag(&self, _fx: i32, _fy: i32, _tx: i32, _ty: i32, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_scroll(&self, _x: i32, _y: i32, _dx: i32, _dy: i32) -> Result<()> { Ok(()) }
+        fn key_press(&self, _key: &str) -> Result<()> { Ok(()) }
+        fn key_release(&self, _key: &str) -> Result<()> { Ok(()) }
+        fn key_type(&self, _text: &str) -> Result<()> { Ok(()) }
+        fn key_combination(&self, _key: &str, _modifiers: &[String]) -> Result<()> { Ok(()) }
+        fn get_mouse_position(&self) -> Result<(i32, i32)> { Ok((0, 0)) }
+        fn get_screen_size(&self) -> Result<(u32, u32)> { Ok((1920, 1080)) }
+        fn take_screenshot(&self) -> Result<Vec<u8>> { self.enter(); Ok(Vec::new()) }
+        fn platform_name(&self) -> &'static str { "exclusive" }
+    }
+
+    impl PlatformAutomation for FakePlatform {
+        fn initialize(&mut self) -> Result<()> { Ok(()) }
+        fn check_permissions(&self) -> Result<bool> { Ok(true) }
+        fn request_permissions(&self) -> Result<bool> { Ok(true) }
+        fn mouse_move(&self, _x: i32, _y: i32) -> Result<()> { Ok(()) }
+        fn mouse_click(&self, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_click_at(&self, _x: i32, _y: i32, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_double_click(&self, _x: i32, _y: i32, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_drag(&self, _fx: i32, _fy: i32, _tx: i32, _ty: i32, _button: &str) -> Result<()> { Ok(()) }
+        fn mouse_scroll(&self, _x: i32, _y: i32, _dx: i32, _dy: i32) -> Result<()> { Ok(()) }
+        fn key_press(&self, _key: &str) -> Result<()> { Ok(()) }
+        fn key_release(&self, _key: &str) -> Result<()> { Ok(()) }
+        fn key_type(&self, _text: &str) -> Result<()> { Ok(()) }
+        fn key_combination(&self, _key: &str, _modifiers: &[String]) -> Result<()> { Ok(()) }
+        fn get_mouse_position(&self) -> Result<(i32, i32)> { Ok((0, 0)) }
+        fn get_screen_size(&self) -> Result<(u32, u32)> {
+            self.size_queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self.screen_size)
+        }
+        fn take_screenshot(&self) -> Result<Vec<u8>> { Ok(Vec::new()) }
+        fn platform_name(&self) -> &'static str { "fake" }
+    }
+
+    fn counting_cache() -> (PlatformHandleCache, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let created = Arc::new(AtomicUsize::new(0));
+        let size_queries = Arc::new(AtomicUsize::new(0));
+        let (created_in_factory, queries_in_factory) = (Arc::clone(&created), Arc::clone(&size_queries));
+
+        let cache = PlatformHandleCache::with_factory(move || {
+            created_in_factory.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(FakePlatform {
+                screen_size: (1920, 1080),
+                size_queries: Arc::clone(&queries_in_factory),
+            }) as Box<dyn PlatformAutomation>)
+        });
+        (cache, created, size_queries)
+    }
+
+    #[test]
+    fn test_handle_is_created_once_and_shared() {
+        let (cache, created, _) = counting_cache();
+        let cache = Arc::new(cache);
+        assert!(!cache.is_initialized());
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = Arc::clone(&cache);
+                std::thread::spawn(move || cache.get().unwrap().platform_name())
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), "fake");
+        }
+
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+        assert!(cache.is_initialized());
+    }
+
+    #[test]
+    fn test_shared_handle_serializes_platform_calls() {
+        let backend = Arc::new(ExclusivePlatform::default());
+        let backend_in_factory = Arc::clone(&backend);
+        let cache = Arc::new(PlatformHandleCache::with_factory(move || {
+            Ok(Box::new(Arc::clone(&backend_in_factory)) as Box<dyn PlatformAutomation>)
+        }));
+
+        let threads: Vec<_> = (0..4)
+            .map(|i| {
+                let cache = Arc::clone(&cache);
+                std::thread::spawn(move || {
+                    let platform = cache.get().unwrap();
+                    for _ in 0..10 {
+                        if i % 2 == 0 {
+                            platform.take_screenshot().unwrap();
+                        } else {
+                            platform.mouse_move(1, 1).unwrap();
+                        }
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(backend.overlaps.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_invalidate_recreates_handle_and_dimensions() {
+        let (cache, created, size_queries) = counting_cache();
+
+        assert_eq!(cache.screen_size().unwrap(), (1920, 1080));
+        assert_eq!(cache.screen_size().unwrap(), (1920, 1080));
+        assert_eq!(size_queries.load(Ordering::SeqCst), 1);
+
+        cache.invalidate();
+        assert_eq!(cache.generation(), 1);
+        assert!(!cache.is_initialized());
+
+        cache.screen_size().unwrap();
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(size_queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_creation_errors_are_not_cached() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let attempts_in_factory = Arc::clone(&attempts);
+        let cache = PlatformHandleCache::with_factory(move || {
+            attempts_in_factory.fetch_add(1, Ordering::SeqCst);
+            Err(AutomationError::PermissionDenied {
+                operation: "accessibility".to_string(),
+            })
+        });
+
+        assert!(cache.get().is_err());
+        assert!(cache.get().is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+}
@@ -10,12 +10,14 @@ use x11::{
         CurrentTime, PointerMotionMask, ButtonPressMask, ButtonReleaseMask,
     },
     xtest::{XTestFakeButtonEvent, XTestFakeKeyEvent, XTestFakeMotionEvent},
+    xrandr::{XRRGetMonitors, XRRFreeMonitors},
     keysym::{XK_Return, XK_space, XK_Tab, XK_Escape, XK_BackSpace, XK_Delete},
 };
 
 use crate::{Result, AutomationError};
 use crate::logging::{get_logger, CoreType, OperationType, LogLevel};
 use super::PlatformAutomation;
+use crate::display::DisplayInfo;
 use std::collections::HashMap;
 use std::ffi::CString;
 use std::ptr;
@@ -601,6 +603,53 @@ impl PlatformAutomation for LinuxAutomation {
         }
     }
     
+    fn get_displays(&self) -> Result<Vec<DisplayInfo>> {
+        self.log_platform_call("XRRGetMonitors", "get_active=True");
+        
+        unsafe {
+            let root = XDefaultRootWindow(self.display);
+            let mut count: i32 = 0;
+            let monitors = XRRGetMonitors(self.display, root, True, &mut count);
+            
+            // Servers without RandR 1.5 report no monitors; fall back to the whole screen
+            if monitors.is_null() || count <= 0 {
+                if !monitors.is_null() {
+                    XRRFreeMonitors(monitors);
+                }
+                let (width, height) = self.get_screen_size()?;
+                return Ok(vec![DisplayInfo::primary(width, height)]);
+            }
+            
+            let displays = std::slice::from_raw_parts(monitors, count as usize)
+                .iter()
+                .map(|monitor| {
+                    let name_ptr = x11::xlib::XGetAtomName(self.display, monitor.name);
+                    let id = if name_ptr.is_null() {
+                        monitor.name.to_string()
+                    } else {
+                        let name = std::ffi::CStr::from_ptr(name_ptr).to_string_lossy().into_owned();
+                        x11::xlib::XFree(name_ptr as *mut _);
+                        name
+                    };
+                    
+                    // X11 has no per-monitor scale; desktop scaling is applied by the toolkit
+                    DisplayInfo {
+                        id,
+                        x: monitor.x,
+                        y: monitor.y,
+                        width: monitor.width.max(0) as u32,
+                        height: monitor.height.max(0) as u32,
+                        scale_factor: 1.0,
+                        is_primary: monitor.primary != 0,
+                    }
+                })
+                .collect();
+            
+            XRRFreeMonitors(monitors);
+            Ok(displays)
+        }
+    }
+    
     fn take_screenshot(&self) -> Result<Vec<u8>> {
         // Placeholder implementation - would need XGetImage
         Err(AutomationError::SystemError {
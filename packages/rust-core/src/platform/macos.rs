@@ -19,6 +19,7 @@ use core_foundation::{
 use crate::{Result, AutomationError};
 use crate::logging::{get_logger, CoreType, OperationType, LogLevel};
 use super::PlatformAutomation;
+use crate::display::DisplayInfo;
 use std::collections::HashMap;
 use serde_json::json;
 
@@ -353,6 +354,20 @@ impl PlatformAutomation for MacOSAutomation {
         Ok(true)
     }
     
+    fn check_screen_capture_permission(&self) -> Result<bool> {
+        self.log_platform_call("CGPreflightScreenCaptureAccess", "");
+        
+        // Checks the Screen Recording permission without prompting (macOS 10.15+)
+        let granted = unsafe {
+            extern "C" {
+                fn CGPreflightScreenCaptureAccess() -> bool;
+            }
+            CGPreflightScreenCaptureAccess()
+        };
+        
+        Ok(granted)
+    }
+    
     fn request_permissions(&self) -> Result<bool> {
         // Request accessibility permissions with system prompt
         self.request_accessibility_permissions_with_prompt()?;
@@ -701,6 +716,42 @@ impl PlatformAutomation for MacOSAutomation {
         Ok((width, height))
     }
     
+    fn get_displays(&self) -> Result<Vec<DisplayInfo>> {
+        self.log_platform_call("CGDisplay::active_displays", "");
+        
+        let display_ids = CGDisplay::active_displays().map_err(|error| {
+            self.log_platform_error("CGDisplay::active_displays", &format!("CGError {}", error));
+            AutomationError::SystemError {
+                message: format!("Failed to list active displays (CGError {})", error),
+            }
+        })?;
+        
+        Ok(display_ids
+            .into_iter()
+            .map(|display_id| {
+                let display = CGDisplay::new(display_id);
+                let bounds = display.bounds();
+                let width = bounds.size.width as u32;
+                // Retina displays have more physical pixels than points
+                let scale_factor = if width > 0 {
+                    display.pixels_wide() as f64 / width as f64
+                } else {
+                    1.0
+                };
+                
+                DisplayInfo {
+                    id: display_id.to_string(),
+                    x: bounds.origin.x as i32,
+                    y: bounds.origin.y as i32,
+                    width,
+                    height: bounds.size.height as u32,
+                    scale_factor,
+                    is_primary: display.is_main(),
+                }
+            })
+            .collect())
+    }
+    
     fn take_screenshot(&self) -> Result<Vec<u8>> {
         // Placeholder implementation - would need CGDisplayCreateImage
         Err(AutomationError::SystemError {
@@ -9,9 +9,14 @@ pub mod macos;
 #[cfg(target_os = "linux")]
 pub mod linux;
 
+pub mod handle_cache;
+
+pub use handle_cache::{platform_cache, shared_platform, PlatformHandleCache, SharedPlatform};
+
 
 
 use crate::{Result, AutomationError};
+use crate::display::DisplayInfo;
 
 /// Trait for platform-specific automation implementations
 pub trait PlatformAutomation: Send + Sync {
@@ -24,6 +29,14 @@ pub trait PlatformAutomation: Send + Sync {
     /// Request permissions if needed (may show system dialogs)
     fn request_permissions(&self) -> Result<bool>;
     
+    /// Check if the screen may be captured
+    ///
+    /// Only macOS gates capture behind a permission (Screen Recording), so
+    /// other platforms report it as granted.
+    fn check_screen_capture_permission(&self) -> Result<bool> {
+        Ok(true)
+    }
+    
     /// Move mouse to specified coordinates
     fn mouse_move(&self, x: i32, y: i32) -> Result<()>;
     
@@ -60,6 +73,14 @@ pub trait PlatformAutomation: Send + Sync {
     /// Get screen dimensions
     fn get_screen_size(&self) -> Result<(u32, u32)>;
     
+    /// Get the connected displays
+    ///
+    /// Platforms without multi-display support report the primary screen only.
+    fn get_displays(&self) -> Result<Vec<DisplayInfo>> {
+        let (width, height) = self.get_screen_size()?;
+        Ok(vec![DisplayInfo::primary(width, height)])
+    }
+    
     /// Take a screenshot and return image data
     fn take_screenshot(&self) -> Result<Vec<u8>>;
     
@@ -69,6 +90,16 @@ pub trait PlatformAutomation: Send + Sync {
 
 /// Create platform-specific automation instance
 pub fn create_platform_automation() -> Result<Box<dyn PlatformAutomation>> {
+    #[cfg(any(test, feature = "test-harness"))]
+    if let Some(platform) = overridden_platform() {
+        return Ok(platform);
+    }
+
+    create_native_platform()
+}
+
+/// Create the backend of the host OS, ignoring any test override
+pub(crate) fn create_native_platform() -> Result<Box<dyn PlatformAutomation>> {
     #[cfg(windows)]
     {
         Ok(Box::new(windows::WindowsAutomation::new()?))
@@ -91,3 +122,67 @@ pub fn create_platform_automation() -> Result<Box<dyn PlatformAutomation>> {
         })
     }
 }
+
+/// Creates platform handles on another thread
+pub(crate) type PlatformCreator = Box<dyn Fn() -> Result<Box<dyn PlatformAutomation>> + Send>;
+
+/// Capture how the current thread creates platform handles
+///
+/// Background threads that recreate their handle, such as playback after a
+/// display change, use this to get the backend of the thread that started them.
+pub(crate) fn platform_creator() -> PlatformCreator {
+    #[cfg(any(test, feature = "test-harness"))]
+    if let Some(platform_override) = PLATFORM_OVERRIDE.with(|slot| slot.borrow().clone()) {
+        return Box::new(move || Ok((platform_override.factory)()));
+    }
+
+    Box::new(create_platform_automation)
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+pub(crate) type PlatformFactory = std::sync::Arc<dyn Fn() -> Box<dyn PlatformAutomation> + Send + Sync>;
+
+/// Platform used instead of the host's on one thread
+///
+/// Carries its own handle cache, so `platform_cache` and `shared_platform`
+/// follow the override without touching the process-wide cache.
+#[cfg(any(test, feature = "test-harness"))]
+#[derive(Clone)]
+pub(crate) struct PlatformOverride {
+    factory: PlatformFactory,
+    cache: std::sync::Arc<PlatformHandleCache>,
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+impl PlatformOverride {
+    pub(crate) fn new(factory: PlatformFactory) -> Self {
+        let cache_factory = factory.clone();
+        Self {
+            factory,
+            cache: std::sync::Arc::new(PlatformHandleCache::with_factory(move || Ok(cache_factory()))),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+thread_local! {
+    static PLATFORM_OVERRIDE: std::cell::RefCell<Option<PlatformOverride>> = std::cell::RefCell::new(None);
+}
+
+/// Make `create_platform_automation` and `platform_cache` use `platform_override` on the current thread
+///
+/// Returns the previous override so it can be restored.
+#[cfg(any(test, feature = "test-harness"))]
+pub(crate) fn set_platform_override(platform_override: Option<PlatformOverride>) -> Option<PlatformOverride> {
+    PLATFORM_OVERRIDE.with(|slot| std::mem::replace(&mut *slot.borrow_mut(), platform_override))
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+fn overridden_platform() -> Option<Box<dyn PlatformAutomation>> {
+    PLATFORM_OVERRIDE.with(|slot| slot.borrow().as_ref().map(|platform_override| (platform_override.factory)()))
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+pub(crate) fn overridden_platform_cache() -> Option<std::sync::Arc<PlatformHandleCache>> {
+    PLATFORM_OVERRIDE.with(|slot| slot.borrow().as_ref().map(|platform_override| std::sync::Arc::clone(&platform_override.cache)))
+}
@@ -3,8 +3,8 @@
 #[cfg(windows)]
 use winapi::{
     shared::{
-        windef::{POINT, RECT},
-        minwindef::{DWORD, UINT, WORD, LPARAM, WPARAM},
+        windef::{POINT, RECT, HDC, HMONITOR, LPRECT},
+        minwindef::{BOOL, DWORD, UINT, WORD, LPARAM, WPARAM, TRUE},
     },
     um::{
         winuser::{
@@ -15,7 +15,9 @@ use winapi::{
             MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_MIDDLEDOWN,
             MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_WHEEL, MOUSEEVENTF_ABSOLUTE,
             KEYEVENTF_KEYUP, VK_LBUTTON, VK_RBUTTON, VK_MBUTTON,
+            EnumDisplayMonitors, GetMonitorInfoW, MONITORINFOEXW, MONITORINFOF_PRIMARY,
         },
+        shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI},
         wingdi::{GetDC, CreateCompatibleDC, CreateCompatibleBitmap, SelectObject, BitBlt, SRCCOPY},
         errhandlingapi::GetLastError,
     },
@@ -24,6 +26,7 @@ use winapi::{
 use crate::{Result, AutomationError};
 use crate::logging::{get_logger, CoreType, OperationType, LogLevel};
 use super::PlatformAutomation;
+use crate::display::DisplayInfo;
 use std::collections::HashMap;
 use serde_json::json;
 
@@ -495,6 +498,70 @@ impl PlatformAutomation for WindowsAutomation {
         }
     }
     
+    fn get_displays(&self) -> Result<Vec<DisplayInfo>> {
+        self.log_platform_call("EnumDisplayMonitors", "");
+        
+        unsafe extern "system" fn collect_monitor(monitor: HMONITOR, _dc: HDC, _rect: LPRECT, data: LPARAM) -> BOOL {
+            let monitors = &mut *(data as *mut Vec<HMONITOR>);
+            monitors.push(monitor);
+            TRUE
+        }
+        
+        unsafe {
+            let mut monitors: Vec<HMONITOR> = Vec::new();
+            if EnumDisplayMonitors(
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                Some(collect_monitor),
+                &mut monitors as *mut Vec<HMONITOR> as LPARAM,
+            ) == 0 {
+                let error_code = GetLastError();
+                self.log_platform_error("EnumDisplayMonitors", error_code);
+                return Err(AutomationError::SystemError {
+                    message: format!("Failed to list displays. Error code: {} ({})",
+                        error_code,
+                        Self::get_error_message(error_code)),
+                });
+            }
+            
+            let mut displays = Vec::with_capacity(monitors.len());
+            for monitor in monitors {
+                let mut info: MONITORINFOEXW = std::mem::zeroed();
+                info.cbSize = std::mem::size_of::<MONITORINFOEXW>() as DWORD;
+                if GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut _) == 0 {
+                    // The monitor was detached while enumerating
+                    continue;
+                }
+                
+                let name_len = info.szDevice.iter().position(|&c| c == 0).unwrap_or(info.szDevice.len());
+                let rect = info.rcMonitor;
+                
+                let (mut dpi_x, mut dpi_y): (UINT, UINT) = (0, 0);
+                let scale_factor = if GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) == 0 && dpi_x > 0 {
+                    dpi_x as f64 / 96.0
+                } else {
+                    1.0
+                };
+                
+                displays.push(DisplayInfo {
+                    id: String::from_utf16_lossy(&info.szDevice[..name_len]),
+                    x: rect.left,
+                    y: rect.top,
+                    width: (rect.right - rect.left).max(0) as u32,
+                    height: (rect.bottom - rect.top).max(0) as u32,
+                    scale_factor,
+                    is_primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+                });
+            }
+            
+            if displays.is_empty() {
+                let (width, height) = self.get_screen_size()?;
+                displays.push(DisplayInfo::primary(width, height));
+            }
+            Ok(displays)
+        }
+    }
+    
     fn take_screenshot(&self) -> Result<Vec<u8>> {
         // Placeholder implementation - would need more complex GDI operations
         // for actual screenshot capture
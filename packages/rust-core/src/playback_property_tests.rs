@@ -7,7 +7,7 @@ use crate::{
 };
 use proptest::prelude::*;
 use proptest::strategy::ValueTree;
-use tokio::sync::mpsc;
+use crate::{event_channel, DEFAULT_EVENT_CAPACITY};
 
 /// **Feature: rust-core-playback-fix, Property 1: Mouse cursor movement execution**
 /// 
@@ -1100,7 +1100,7 @@ proptest! {
         prop_assert!(load_result.is_ok(), "Player should be able to load script");
         
         // Create an event channel to capture events
-        let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, mut event_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(event_sender);
         
         // Verify event sender is initialized
@@ -1350,7 +1350,7 @@ proptest! {
         prop_assert!(load_result.is_ok(), "Player should be able to load script");
         
         // Create an event channel to capture events
-        let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, mut event_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(event_sender);
         
         // Verify event sender is initialized
@@ -1604,7 +1604,7 @@ proptest! {
         prop_assert!(load_result.is_ok(), "Player should be able to load script");
         
         // Create an event channel to capture events
-        let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, mut event_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(event_sender);
         
         // Verify event sender is initialized
@@ -2303,7 +2303,7 @@ proptest! {
         prop_assert!(load_result.is_ok(), "Player should be able to load script");
         
         // Create an event channel to capture events
-        let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, mut event_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(event_sender);
         
         // Verify event sender is initialized
@@ -2786,7 +2786,7 @@ proptest! {
         prop_assert!(load_result.is_ok(), "Player should be able to load script");
         
         // Create an event channel to capture events
-        let (event_sender, mut event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, mut event_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(event_sender);
         
         // Verify event sender is initialized
@@ -2,20 +2,32 @@
 
 use crate::{
     Result, AutomationError, AutomationConfig, ScriptData, Action, ActionType,
-    platform::{PlatformAutomation, create_platform_automation},
+    platform::{PlatformAutomation, PlatformHandleCache, create_platform_automation, platform_creator},
     logging::{CoreType, OperationType, LogLevel, get_logger},
     error::PlaybackError,
+    events::{event_bus, CoreEvent},
+    resource_profile::{configured_resource_profile, ResourceProfile},
+    click_annotations::{ClickAnnotationConfig, ClickAnnotationReport, ClickAnnotator},
+    clock::{system_clock, Clock, SharedClock},
+    ai_budget::TokenBudget,
+    ai_vision_integration::{persist_cache_update, AIVisionProvider},
+    event_channel::{event_channel, EventSender, DEFAULT_EVENT_CAPACITY, EVENT_SEND_TIMEOUT},
+    performance_assertions::{
+        log_assertion_results, script_assertions, AssertionStatus, MetricAssertion,
+        MetricAssertionMonitor, PerformanceAssertionResult, DEFAULT_SAMPLE_INTERVAL_MS,
+    },
 };
 use std::sync::{Arc, Mutex, atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering}};
 use std::time::{Duration, Instant};
 use std::collections::HashMap;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use serde::{Serialize, Deserialize};
 use serde_json::Value;
 use serde_json::json;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::thread;
+use base64::{Engine as _, engine::general_purpose};
 
 /// Player for executing recorded scripts
 pub struct Player {
@@ -23,6 +35,8 @@ pub struct Player {
     config: AutomationConfig,
     is_playing: Arc<AtomicBool>,
     is_paused: Arc<AtomicBool>,
+    /// Why playback was paused automatically, cleared on resume
+    pause_reason: Arc<Mutex<Option<String>>>,
     current_script: Option<ScriptData>,
     playback_speed: f64,
     loops_remaining: Arc<AtomicU32>,
@@ -30,7 +44,20 @@ pub struct Player {
     current_loop: Arc<AtomicU32>,
     current_action_index: Arc<AtomicUsize>,
     start_time: Option<Instant>,
-    event_sender: Option<mpsc::UnboundedSender<PlaybackEvent>>,
+    event_sender: Option<EventSender<PlaybackEvent>>,
+    /// Assertions checked in addition to the script's own, e.g. from a suite
+    performance_assertions: Vec<MetricAssertion>,
+    /// Time source for action timing and waits
+    clock: SharedClock,
+    /// Locates targets for dynamic AI Vision Capture steps
+    ai_provider: Option<Arc<dyn AIVisionProvider>>,
+    /// Budget dynamic AI Vision Capture steps are charged to
+    token_budget: Option<TokenBudget>,
+    /// Degradations applied to capture and polling during playback
+    resource_profile: ResourceProfile,
+    /// Whether to hook OS input so ESC stops playback
+    #[cfg_attr(target_os = "macos", allow(dead_code))]
+    capture_input: bool,
 }
 
 /// Playback status information
@@ -38,6 +65,8 @@ pub struct Player {
 pub struct PlaybackStatus {
     pub is_playing: bool,
     pub is_paused: bool,
+    /// Set when playback paused itself, e.g. after a display change
+    pub pause_reason: Option<String>,
     pub current_action: usize,
     pub total_actions: usize,
     pub progress: f64,
@@ -75,6 +104,9 @@ pub struct PlaybackStatistics {
     pub playback_speed: f64,
     /// List of errors encountered (limited to first 10)
     pub errors: Vec<String>,
+    /// Results of the run's performance assertions
+    #[serde(default)]
+    pub performance_assertions: Vec<PerformanceAssertionResult>,
 }
 
 impl PlaybackStatistics {
@@ -94,6 +126,7 @@ impl PlaybackStatistics {
             loops_completed: 0,
             playback_speed,
             errors: Vec::new(),
+            performance_assertions: Vec::new(),
         }
     }
     
@@ -222,6 +255,12 @@ pub enum PlaybackEventData {
     VisualAssertResult {
         result: VisualTestResult,
     },
+    ClickAnnotations {
+        report: ClickAnnotationReport,
+    },
+    PerformanceAssertions {
+        results: Vec<PerformanceAssertionResult>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -338,7 +377,8 @@ fn build_visual_assert_comparison_config(
     Some(config)
 }
 
-fn resolve_visual_assert_baseline_path(script: &crate::script::ScriptData, baseline_path: &str) -> String {
+/// Resolve a path stored in a script relative to the script's directory
+fn resolve_script_asset_path(script: &crate::script::ScriptData, asset_path: &str) -> String {
     let script_dir = script
         .metadata
         .additional_data
@@ -346,27 +386,27 @@ fn resolve_visual_assert_baseline_path(script: &crate::script::ScriptData, basel
         .and_then(|v| v.as_str())
         .unwrap_or("");
 
-    if baseline_path.is_empty() {
-        return baseline_path.to_string();
+    if asset_path.is_empty() {
+        return asset_path.to_string();
     }
 
-    let baseline = Path::new(baseline_path);
-    if baseline.is_absolute() {
-        return baseline_path.to_string();
+    let asset = Path::new(asset_path);
+    if asset.is_absolute() {
+        return asset_path.to_string();
     }
 
     if script_dir.is_empty() {
-        return baseline_path.to_string();
+        return asset_path.to_string();
     }
 
     Path::new(script_dir)
-        .join(baseline)
+        .join(asset)
         .to_string_lossy()
         .to_string()
 }
 
 #[cfg(target_os = "macos")]
-fn capture_screenshot_to_temp_png(action_id: &str) -> std::result::Result<PathBuf, String> {
+pub(crate) fn capture_screenshot_to_temp_png(action_id: &str) -> std::result::Result<PathBuf, String> {
     let mut path = std::env::temp_dir();
     let file_name = format!(
         "geniusqa_vrt_actual_{}_{}_{}.png",
@@ -390,10 +430,99 @@ fn capture_screenshot_to_temp_png(action_id: &str) -> std::result::Result<PathBu
 }
 
 #[cfg(not(target_os = "macos"))]
-fn capture_screenshot_to_temp_png(_action_id: &str) -> std::result::Result<PathBuf, String> {
+pub(crate) fn capture_screenshot_to_temp_png(_action_id: &str) -> std::result::Result<PathBuf, String> {
     Err("Visual assert screenshot capture is currently only implemented on macOS".to_string())
 }
 
+/// Capture the current screen for click annotations
+fn capture_review_frame(platform_cache: &PlatformHandleCache) -> Result<image::DynamicImage> {
+    if let Ok(bytes) = platform_cache.get().and_then(|platform| platform.take_screenshot()) {
+        if let Ok(frame) = image::load_from_memory(&bytes) {
+            return Ok(frame);
+        }
+    }
+
+    // Platform capture is not available everywhere yet, so fall back to the system tool
+    let path = capture_screenshot_to_temp_png("click_annotation")
+        .map_err(|message| AutomationError::SystemError { message })?;
+    let frame = image::open(&path).map_err(|e| AutomationError::SystemError {
+        message: format!("Failed to load captured frame: {}", e),
+    });
+    let _ = std::fs::remove_file(&path);
+    frame
+}
+
+/// Create the click annotator for a run when annotations are enabled
+fn create_click_annotator(config: &AutomationConfig, resource_profile: &ResourceProfile) -> Option<ClickAnnotator> {
+    let annotation_config = &config.click_annotations;
+    if !annotation_config.enabled {
+        return None;
+    }
+
+    let output_directory = annotation_config
+        .output_directory
+        .clone()
+        .unwrap_or_else(|| config.scripts_directory.join("reports"));
+    let run_id = format!("run_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f"));
+    // Frames are captured on the playback thread, so take this thread's cache along
+    let platform_cache = crate::platform::platform_cache();
+    let screen_size = platform_cache.screen_size().ok();
+
+    // Markers are scaled to the frame, so downscaled frames still line up
+    let resource_profile = resource_profile.clone();
+    let frame_source = move || {
+        capture_review_frame(&platform_cache).map(|frame| resource_profile.downscale_screenshot(frame))
+    };
+
+    match ClickAnnotator::new(&output_directory, &run_id, annotation_config.marker_radius, screen_size, frame_source) {
+        Ok(annotator) => Some(annotator),
+        Err(e) => {
+            if let Some(logger) = get_logger() {
+                logger.log_operation(
+                    LogLevel::Warn,
+                    CoreType::Rust,
+                    OperationType::Playback,
+                    format!("click_annotations_disabled_{}", chrono::Utc::now().timestamp_millis()),
+                    format!("Click annotations disabled for this run: {}", e),
+                    None,
+                );
+            }
+            None
+        }
+    }
+}
+
+/// Start sampling metrics for the script's and any extra performance assertions
+fn create_assertion_monitor(
+    script: &ScriptData,
+    extra: &[MetricAssertion],
+    resource_profile: &ResourceProfile,
+) -> Option<MetricAssertionMonitor> {
+    let mut assertions = extra.to_vec();
+    match script_assertions(script) {
+        Ok(declared) => assertions.extend(declared),
+        Err(e) => {
+            if let Some(logger) = get_logger() {
+                logger.log_operation(
+                    LogLevel::Warn,
+                    CoreType::Rust,
+                    OperationType::Playback,
+                    format!("performance_assertions_invalid_{}", chrono::Utc::now().timestamp_millis()),
+                    format!("Ignoring script performance assertions: {}", e),
+                    None,
+                );
+            }
+        }
+    }
+
+    if assertions.is_empty() {
+        None
+    } else {
+        let interval = resource_profile.poll_interval(Duration::from_millis(DEFAULT_SAMPLE_INTERVAL_MS));
+        Some(MetricAssertionMonitor::start(assertions, interval))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionPreviewData {
     #[serde(rename = "type")]
@@ -408,14 +537,24 @@ pub struct ActionPreviewData {
 
 impl Player {
     /// Create a new player instance
+    ///
+    /// Uses the profile selected with `init_resource_profile`, or the normal
+    /// profile if none was selected.
     pub fn new(config: AutomationConfig) -> Result<Self> {
+        Self::with_resource_profile(config, &configured_resource_profile())
+    }
+
+    /// Create a player degraded according to `resource_profile`
+    pub fn with_resource_profile(mut config: AutomationConfig, resource_profile: &ResourceProfile) -> Result<Self> {
         let platform = create_platform_automation()?;
+        resource_profile.apply_to_automation(&mut config);
         
         Ok(Self {
             platform,
             config,
             is_playing: Arc::new(AtomicBool::new(false)),
             is_paused: Arc::new(AtomicBool::new(false)),
+            pause_reason: Arc::new(Mutex::new(None)),
             current_script: None,
             playback_speed: 1.0,
             loops_remaining: Arc::new(AtomicU32::new(1)),
@@ -424,6 +563,12 @@ impl Player {
             current_action_index: Arc::new(AtomicUsize::new(0)),
             start_time: None,
             event_sender: None,
+            performance_assertions: Vec::new(),
+            clock: system_clock(),
+            ai_provider: None,
+            token_budget: None,
+            resource_profile: resource_profile.clone(),
+            capture_input: true,
         })
     }
 
@@ -622,7 +767,7 @@ impl Player {
     }
 
     /// Set event sender for real-time UI updates
-    pub fn set_event_sender(&mut self, sender: mpsc::UnboundedSender<PlaybackEvent>) {
+    pub fn set_event_sender(&mut self, sender: EventSender<PlaybackEvent>) {
         self.event_sender = Some(sender);
         
         // Log event sender initialization
@@ -689,6 +834,44 @@ impl Player {
         self.event_sender.is_some()
     }
 
+    /// Set assertions checked during playback in addition to the script's own
+    pub fn set_performance_assertions(&mut self, assertions: Vec<MetricAssertion>) {
+        self.performance_assertions = assertions;
+    }
+
+    /// Replace the time source used for action timing and waits
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Provider asked to locate targets of dynamic AI Vision Capture steps
+    ///
+    /// Without one, dynamic steps only run from cached coordinates.
+    pub fn set_ai_vision_provider(&mut self, provider: Arc<dyn AIVisionProvider>) {
+        self.ai_provider = Some(provider);
+    }
+
+    /// Charge dynamic AI Vision Capture steps to `budget`
+    ///
+    /// Each playback is a budget run unless one (e.g. a suite) is already in
+    /// progress.
+    pub fn set_token_budget(&mut self, budget: TokenBudget) {
+        self.token_budget = Some(budget);
+    }
+
+    /// Set whether to hook OS input events during playback
+    ///
+    /// With capture disabled ESC no longer stops playback, e.g. when a test
+    /// plays against a mock platform.
+    pub fn set_input_capture(&mut self, enabled: bool) {
+        self.capture_input = enabled;
+    }
+
+    /// Choose whether the next playbacks capture annotated click frames
+    pub fn set_click_annotations(&mut self, click_annotations: ClickAnnotationConfig) {
+        self.config.click_annotations = click_annotations;
+    }
+
     /// Load a script for playback
     pub fn load_script(&mut self, script: ScriptData) -> Result<()> {
         // Validate the script first
@@ -803,6 +986,7 @@ impl Player {
 
         self.is_playing.store(true, Ordering::Relaxed);
         self.is_paused.store(false, Ordering::Relaxed);
+        *self.pause_reason.lock().unwrap() = None;
         
         self.playback_speed = clamped_speed;
         self.loops_remaining.store(loops, Ordering::Relaxed);
@@ -1021,6 +1205,9 @@ impl Player {
         // Update atomic state - this is the critical operation for responsiveness
         let atomic_update_start = Instant::now();
         self.is_paused.store(new_paused, Ordering::Relaxed);
+        if !new_paused {
+            *self.pause_reason.lock().unwrap() = None;
+        }
         let atomic_update_duration = atomic_update_start.elapsed();
         
         // Send status event to UI
@@ -1079,6 +1266,7 @@ impl Player {
     fn start_playback_execution(&self) -> Result<()> {
         let is_playing = Arc::clone(&self.is_playing);
         let is_paused = Arc::clone(&self.is_paused);
+        let pause_reason = Arc::clone(&self.pause_reason);
         let current_action_index = Arc::clone(&self.current_action_index);
         let script = self.current_script.clone();
         let playback_speed = self.playback_speed;
@@ -1087,13 +1275,28 @@ impl Player {
         let current_loop = Arc::clone(&self.current_loop);
         let event_sender = self.event_sender.clone();
         let config = self.config.clone();
+        let clock = Arc::clone(&self.clock);
+        let ai_provider = self.ai_provider.clone();
+        let token_budget = self.token_budget.clone();
+        let resource_profile = self.resource_profile.clone();
         
-        // Create platform automation for the background thread
-        let platform = create_platform_automation()?;
+        // Create platform automation for the background thread; recreated after display changes
+        let mut platform = create_platform_automation()?;
+        let create_platform = platform_creator();
+        let platform_cache = crate::platform::platform_cache();
+        
+        // Recorded coordinates assume the layout at start, so watch for display changes
+        let mut core_events = event_bus().subscribe();
+        
+        let mut click_annotator = create_click_annotator(&config, &resource_profile);
+        
+        let mut assertion_monitor = script
+            .as_ref()
+            .and_then(|script| create_assertion_monitor(script, &self.performance_assertions, &resource_profile));
         
         // Spawn ESC key listener thread to stop playback
         #[cfg(not(target_os = "macos"))]
-        {
+        if self.capture_input {
             let is_playing_esc = Arc::clone(&self.is_playing);
             thread::spawn(move || {
                 let is_playing_ref = is_playing_esc;
@@ -1116,18 +1319,29 @@ impl Player {
             });
         }
         
+        // A playback outside a suite is its own budget run
+        let owns_budget_run = match token_budget {
+            Some(ref budget) if script.is_some() && !budget.run_active() => {
+                budget.begin_run(format!("playback_{}", chrono::Utc::now().timestamp_millis()));
+                true
+            }
+            _ => false,
+        };
+        
         thread::spawn(move || {
             if let Some(script) = script {
                 // Initialize loop start time and get first action timestamp for proper timing
-                let mut loop_start_time = Instant::now();
+                let mut loop_start_time = clock.now();
                 let first_action_timestamp = script.actions.first().map(|a| a.timestamp).unwrap_or(0.0);
                 
                 // Initialize playback statistics
                 let mut statistics = PlaybackStatistics::new(script.actions.len(), playback_speed);
-                let playback_start_time = Instant::now();
+                let playback_start_time = clock.now();
                 
                 // Error accumulation for reporting
                 let mut accumulated_errors: Vec<PlaybackError> = Vec::new();
+                // AI Vision Capture results, so later loops reuse what the AI found
+                let mut vision_cache: HashMap<String, CacheUpdate> = HashMap::new();
                 const MAX_RETRY_ATTEMPTS: usize = 3;
                 const RETRY_DELAY_MS: u64 = 100;
                 
@@ -1141,7 +1355,7 @@ impl Player {
                         
                         if remaining > 0 {
                             current_action_index.store(0, Ordering::Relaxed);
-                            loop_start_time = Instant::now();
+                            loop_start_time = clock.now();
                             
                             if let Some(logger) = get_logger() {
                                 logger.log_operation(
@@ -1174,6 +1388,49 @@ impl Player {
                         }
                     }
                     
+                    // Pause with a reason if the display layout changed since the last action
+                    let display_changes = drain_display_changes(&mut core_events);
+                    if !display_changes.is_empty() && !is_paused.load(Ordering::Relaxed) {
+                        let reason = format!("Display configuration changed: {}", display_changes.join("; "));
+                        *pause_reason.lock().unwrap() = Some(reason.clone());
+                        is_paused.store(true, Ordering::Relaxed);
+                        
+                        // Screen bounds and scaling come from the handle, so rebind it to the new layout
+                        // before resuming; the monitor has already invalidated the shared cache
+                        match create_platform() {
+                            Ok(fresh_platform) => platform = fresh_platform,
+                            Err(e) => Self::log_platform_error("create_platform_automation", &e),
+                        }
+                        if let Some(annotator) = click_annotator.as_mut() {
+                            annotator.set_screen_size(platform_cache.screen_size().ok());
+                        }
+                        
+                        if let Some(logger) = get_logger() {
+                            let mut metadata = HashMap::new();
+                            metadata.insert("action_index".to_string(), json!(action_index));
+                            metadata.insert("display_changes".to_string(), json!(display_changes));
+                            
+                            logger.log_operation(
+                                LogLevel::Warn,
+                                CoreType::Rust,
+                                OperationType::Playback,
+                                format!("display_change_pause_{}", chrono::Utc::now().timestamp_millis()),
+                                format!("Playback paused at action {}: {}", action_index, reason),
+                                Some(metadata),
+                            );
+                        }
+                        
+                        if let Some(ref sender) = event_sender {
+                            let _ = sender.send_waiting(PlaybackEvent {
+                                event_type: "status".to_string(),
+                                data: PlaybackEventData::Status {
+                                    status: "paused".to_string(),
+                                    message: Some(reason),
+                                },
+                            }, EVENT_SEND_TIMEOUT);
+                        }
+                    }
+                    
                     // Wait if paused - pause at action boundary
                     let pause_entered = is_paused.load(Ordering::Relaxed);
                     if pause_entered && is_playing.load(Ordering::Relaxed) {
@@ -1326,7 +1583,7 @@ impl Player {
                     
                     // Apply speed scaling to get target time
                     let target_time = Duration::from_secs_f64(relative_timestamp.max(0.0) / playback_speed);
-                    let elapsed = loop_start_time.elapsed();
+                    let elapsed = clock.now().saturating_sub(loop_start_time);
                     
                     // Calculate expected vs actual delay
                     let delay_needed = if target_time > elapsed {
@@ -1367,9 +1624,9 @@ impl Player {
                     }
                     
                     // Handle edge cases: zero or negative delays
-                    let actual_delay_start = Instant::now();
+                    let actual_delay_start = clock.now();
                     if delay_needed > Duration::from_secs(0) {
-                        thread::sleep(delay_needed);
+                        clock.sleep(delay_needed);
                     } else if target_time < elapsed {
                         // We're behind schedule - calculate timing drift
                         let timing_drift = elapsed - target_time;
@@ -1414,7 +1671,7 @@ impl Player {
                             }
                         }
                     }
-                    let actual_delay = actual_delay_start.elapsed();
+                    let actual_delay = clock.now().saturating_sub(actual_delay_start);
 
                     // Execute VisualAssert inside the playback loop so we can emit a dedicated event
                     if action.action_type == ActionType::VisualAssert {
@@ -1432,7 +1689,7 @@ impl Player {
                             .and_then(|v| v.get("baseline_path"))
                             .and_then(|v| v.as_str())
                             .unwrap_or("");
-                        let baseline_path = resolve_visual_assert_baseline_path(&script, baseline_path_rel);
+                        let baseline_path = resolve_script_asset_path(&script, baseline_path_rel);
 
                         let mut passed = false;
                         let mut difference_percentage: f32 = 0.0;
@@ -1520,11 +1777,32 @@ impl Player {
 
                     // Execute the action with retry logic for recoverable errors
                     let action_exec_start = Instant::now();
-                    let mut action_result = Self::execute_action_sync(&*platform, action, action_index, &config);
+                    let is_vision_step = action.action_type == ActionType::AiVisionCapture;
+                    let mut action_result = if is_vision_step {
+                        // AI Vision Capture steps may call the AI provider, so run them here
+                        let vision_result = execute_ai_vision_step(
+                            &*platform,
+                            &script,
+                            action,
+                            action_index,
+                            ai_provider.as_deref(),
+                            token_budget.as_ref(),
+                            &resource_profile,
+                            &mut vision_cache,
+                        );
+                        ai_vision_step_outcome(&vision_result, action_index)
+                    } else {
+                        Self::execute_action_sync(&*platform, &*clock, action, action_index, &config)
+                    };
                     let mut retry_count = 0usize;
                     
                     // Retry logic for transient platform errors
                     while let Err(ref playback_error) = action_result {
+                        // Vision steps are not retried; a retry could spend AI budget again
+                        if is_vision_step {
+                            break;
+                        }
+
                         // Check if we should retry based on error type
                         let should_retry = match &playback_error.underlying_error {
                             AutomationError::SystemError { .. } => true,
@@ -1549,10 +1827,10 @@ impl Player {
                             }
                             
                             // Wait before retrying
-                            thread::sleep(Duration::from_millis(RETRY_DELAY_MS));
+                            clock.sleep(Duration::from_millis(RETRY_DELAY_MS));
                             
                             // Retry the action
-                            action_result = Self::execute_action_sync(&*platform, action, action_index, &config);
+                            action_result = Self::execute_action_sync(&*platform, &*clock, action, action_index, &config);
                         } else {
                             // No more retries or not a retryable error
                             break;
@@ -1634,6 +1912,14 @@ impl Player {
                     } else {
                         // Record successful action in statistics
                         statistics.record_action_success(action_exec_time, actual_delay);
+                        
+                        if let Some(ref mut annotator) = click_annotator {
+                            // Capturing is not part of the script, so shift the schedule rather
+                            // than let it show up as drift on the following actions
+                            let capture_start = clock.now();
+                            annotator.record_click(action, action_index, current_loop.load(Ordering::Relaxed));
+                            loop_start_time += clock.now().saturating_sub(capture_start);
+                        }
                     }
                     
                     // Track timing drift
@@ -1736,16 +2022,36 @@ impl Player {
                     }
                     
                     // Add small delay between actions
-                    thread::sleep(Duration::from_millis(config.platform_config.mouse_delay));
+                    clock.sleep(Duration::from_millis(config.platform_config.mouse_delay));
                 }
                 
                 // Playback completed
                 is_playing.store(false, Ordering::Relaxed);
-                let total_playback_duration = playback_start_time.elapsed();
+                if owns_budget_run {
+                    if let Some(ref budget) = token_budget {
+                        budget.end_run();
+                    }
+                }
+                let total_playback_duration = clock.now().saturating_sub(playback_start_time);
                 
                 // Finalize statistics
                 statistics.finalize(total_playback_duration, loops_total - loops_remaining.load(Ordering::Relaxed));
                 
+                // Evaluate performance assertions over the samples taken during the run
+                if let Some(monitor) = assertion_monitor.take() {
+                    statistics.performance_assertions = monitor.finish();
+                    log_assertion_results(&statistics.performance_assertions);
+                    
+                    if let Some(ref sender) = event_sender {
+                        let _ = sender.send_waiting(PlaybackEvent {
+                            event_type: "performance_assertions".to_string(),
+                            data: PlaybackEventData::PerformanceAssertions {
+                                results: statistics.performance_assertions.clone(),
+                            },
+                        }, EVENT_SEND_TIMEOUT);
+                    }
+                }
+                
                 // Log complete statistics summary
                 if let Some(logger) = get_logger() {
                     let mut metadata = HashMap::new();
@@ -1815,6 +2121,32 @@ impl Player {
                     }
                 }
                 
+                // Store annotated click frames with the run report
+                if let Some(annotator) = click_annotator.take() {
+                    match annotator.finish() {
+                        Ok(report) => {
+                            if let Some(ref sender) = event_sender {
+                                let _ = sender.send_waiting(PlaybackEvent {
+                                    event_type: "click_annotations".to_string(),
+                                    data: PlaybackEventData::ClickAnnotations { report },
+                                }, EVENT_SEND_TIMEOUT);
+                            }
+                        }
+                        Err(e) => {
+                            if let Some(logger) = get_logger() {
+                                logger.log_operation(
+                                    LogLevel::Warn,
+                                    CoreType::Rust,
+                                    OperationType::Playback,
+                                    format!("click_annotations_failed_{}", chrono::Utc::now().timestamp_millis()),
+                                    format!("Failed to write click annotation report: {}", e),
+                                    None,
+                                );
+                            }
+                        }
+                    }
+                }
+                
                 // Log playback completion
                 Self::log_playback_complete(
                     &script,
@@ -1855,8 +2187,8 @@ impl Player {
                         },
                     };
                     
-                    // Send with error handling and logging
-                    if let Err(e) = sender.send(completion_event) {
+                    // Completion must reach the UI, so wait for room in the queue
+                    if let Err(e) = sender.send_waiting(completion_event, EVENT_SEND_TIMEOUT) {
                         if let Some(logger) = get_logger() {
                             let mut metadata = HashMap::new();
                             metadata.insert("error".to_string(), json!(e.to_string()));
@@ -1928,8 +2260,8 @@ impl Player {
             ActionType::Screenshot => false,
             ActionType::Custom => false,
             
-            // AI Vision Capture requires separate handling (not via standard Action struct)
-            ActionType::AiVisionCapture => false,
+            // AI Vision Capture is supported (execution happens in the playback loop)
+            ActionType::AiVisionCapture => true,
         }
     }
     
@@ -1971,7 +2303,7 @@ impl Player {
     }
     
     /// Execute a single action synchronously with comprehensive logging and error handling
-    fn execute_action_sync(platform: &dyn PlatformAutomation, action: &Action, action_index: usize, _config: &AutomationConfig) -> std::result::Result<(), PlaybackError> {
+    fn execute_action_sync(platform: &dyn PlatformAutomation, clock: &dyn Clock, action: &Action, action_index: usize, _config: &AutomationConfig) -> std::result::Result<(), PlaybackError> {
         // Validate action type before execution
         if !Self::is_action_supported(action) {
             let reason = match action.action_type {
@@ -1982,7 +2314,7 @@ impl Player {
                 ActionType::KeyPress | ActionType::KeyRelease => "Missing required key parameter",
                 ActionType::KeyType => "Missing required text parameter",
                 ActionType::Wait => "Wait action has invalid parameters",
-                ActionType::AiVisionCapture => "AI Vision Capture actions require separate handling via AIVisionCaptureAction struct",
+                ActionType::AiVisionCapture => "AI Vision Capture action is not supported",
                 ActionType::VisualAssert => "Visual assert action is not supported",
             };
             
@@ -2216,7 +2548,7 @@ impl Player {
                     .and_then(|data| data.get("duration_ms"))
                     .and_then(|v| v.as_u64()) {
                     Self::log_platform_call("wait", &format!("duration_ms={}", duration_ms));
-                    thread::sleep(Duration::from_millis(duration_ms));
+                    clock.sleep(Duration::from_millis(duration_ms));
                 }
                 Ok(())
             }
@@ -2229,8 +2561,7 @@ impl Player {
                 Ok(())
             }
             ActionType::AiVisionCapture => {
-                // AI Vision Capture actions are handled separately via AIVisionCaptureAction struct
-                // This branch should not be reached as is_action_supported returns false
+                // AI Vision Capture is handled in the playback loop so it can reach the AI provider
                 Ok(())
             }
         };
@@ -2320,6 +2651,17 @@ impl Player {
                         metadata.insert("baseline_path".to_string(), json!(&result.baseline_path));
                         metadata.insert("actual_path".to_string(), json!(&result.actual_path));
                     },
+                    PlaybackEventData::ClickAnnotations { report } => {
+                        metadata.insert("run_id".to_string(), json!(&report.run_id));
+                        metadata.insert("screenshot_count".to_string(), json!(report.screenshots.len()));
+                        metadata.insert("failed_captures".to_string(), json!(report.failed_captures.len()));
+                    },
+                    PlaybackEventData::PerformanceAssertions { results } => {
+                        metadata.insert("assertion_count".to_string(), json!(results.len()));
+                        metadata.insert("failed_count".to_string(), json!(
+                            results.iter().filter(|r| r.status == AssertionStatus::Failed).count()
+                        ));
+                    },
                 }
                 
                 logger.log_operation(
@@ -2389,6 +2731,7 @@ impl Player {
         PlaybackStatus {
             is_playing,
             is_paused,
+            pause_reason: self.pause_reason(),
             current_action: current_action_index,
             total_actions,
             progress,
@@ -2407,12 +2750,37 @@ impl Player {
     pub fn is_paused(&self) -> bool {
         self.is_paused.load(Ordering::Relaxed)
     }
+
+    /// Reason for an automatic pause, if playback paused itself
+    pub fn pause_reason(&self) -> Option<String> {
+        self.pause_reason.lock().unwrap().clone()
+    }
+}
+
+/// Collect descriptions of display changes published since the last check
+fn drain_display_changes(receiver: &mut broadcast::Receiver<CoreEvent>) -> Vec<String> {
+    let mut changes = Vec::new();
+    loop {
+        match receiver.try_recv() {
+            Ok(event) => {
+                if let Some(change) = event.as_display_change() {
+                    changes.push(change.description());
+                }
+            }
+            // Missed events can only make the layout more stale
+            Err(broadcast::error::TryRecvError::Lagged(missed)) => {
+                changes.push(format!("{} display events missed", missed));
+            }
+            Err(_) => break,
+        }
+    }
+    changes
 }
 
 /// Background player that runs playback with event streaming
 pub struct BackgroundPlayer {
     player: Arc<Mutex<Player>>,
-    event_receiver: Option<mpsc::UnboundedReceiver<PlaybackEvent>>,
+    event_receiver: Option<mpsc::Receiver<PlaybackEvent>>,
 }
 
 impl BackgroundPlayer {
@@ -2427,8 +2795,8 @@ impl BackgroundPlayer {
     }
 
     /// Set up event streaming and return receiver
-    pub fn setup_event_streaming(&mut self) -> mpsc::UnboundedReceiver<PlaybackEvent> {
-        let (sender, receiver) = mpsc::unbounded_channel();
+    pub fn setup_event_streaming(&mut self) -> mpsc::Receiver<PlaybackEvent> {
+        let (sender, receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         
         {
             let mut player = self.player.lock().unwrap();
@@ -2444,6 +2812,18 @@ impl BackgroundPlayer {
         player.load_script(script)
     }
 
+    /// Set assertions checked during playback in addition to the script's own
+    pub fn set_performance_assertions(&self, assertions: Vec<MetricAssertion>) {
+        let mut player = self.player.lock().unwrap();
+        player.set_performance_assertions(assertions);
+    }
+
+    /// Replace the time source used for action timing and waits
+    pub fn set_clock(&self, clock: SharedClock) {
+        let mut player = self.player.lock().unwrap();
+        player.set_clock(clock);
+    }
+
     /// Start background playback
     pub fn start_playback(&self, speed: f64, loops: u32) -> Result<()> {
         let mut player = self.player.lock().unwrap();
@@ -3202,6 +3582,261 @@ pub fn execute_dynamic_mode_with_ai(
     }
 }
 
+/// Execute AI Vision Capture in Dynamic Mode, subject to a token budget.
+///
+/// While the budget allows AI calls this behaves like
+/// `execute_dynamic_mode_with_ai` and records the tokens reported by the
+/// provider. Once the budget is exceeded no AI call is made: in degrade mode
+/// the action falls back to its cached or saved static coordinates, otherwise
+/// (or when no coordinates are available) the action is skipped.
+pub fn execute_dynamic_mode_with_budget(
+    platform: &dyn PlatformAutomation,
+    action: &crate::script::AIVisionCaptureAction,
+    action_index: usize,
+    ai_provider: &dyn crate::ai_vision_integration::AIVisionProvider,
+    budget: &crate::ai_budget::TokenBudget,
+    screenshot_base64: String,
+    reference_images_base64: Vec<String>,
+) -> DynamicModeExecutionResult {
+    use crate::ai_budget::BudgetDecision;
+
+    let (reason, degrade) = match budget.check() {
+        BudgetDecision::Allowed => {
+            let metered = MeteredVisionProvider { inner: ai_provider, budget };
+            return execute_dynamic_mode_with_ai(
+                platform,
+                action,
+                action_index,
+                &metered,
+                screenshot_base64,
+                reference_images_base64,
+            );
+        }
+        BudgetDecision::Degraded { reason } => (reason, true),
+        BudgetDecision::Blocked { reason } => (reason, false),
+    };
+
+    let has_saved_coordinates = action.static_data.saved_x.is_some() && action.static_data.saved_y.is_some();
+    let execution_result = if degrade && action.has_cached_coordinates() {
+        log_ai_vision_skip(action_index, action, &format!("{}; using cached coordinates", reason));
+        execute_cache_mode(platform, action, action_index)
+    } else if degrade && has_saved_coordinates {
+        log_ai_vision_skip(action_index, action, &format!("{}; using saved coordinates", reason));
+        execute_static_mode(platform, action, action_index)
+    } else {
+        log_ai_vision_skip(action_index, action, &reason);
+        AIVisionExecutionResult {
+            success: false,
+            mode: AIVisionExecutionMode::Skipped,
+            coordinates: None,
+            ai_called: false,
+            error: Some(reason),
+        }
+    };
+
+    // The AI was not consulted, so the existing cache stays as it is
+    DynamicModeExecutionResult {
+        execution_result,
+        cache_update: None,
+    }
+}
+
+/// Turn the result of an AI Vision Capture step into a playback outcome
+///
+/// A step that did not interact with the screen is a failed action, so it is
+/// counted and reported like any other.
+fn ai_vision_step_outcome(
+    result: &AIVisionExecutionResult,
+    action_index: usize,
+) -> std::result::Result<(), PlaybackError> {
+    if result.success {
+        return Ok(());
+    }
+
+    let message = result
+        .error
+        .clone()
+        .unwrap_or_else(|| "AI Vision Capture step did not find its target".to_string());
+    Err(PlaybackError::new(
+        action_index,
+        "ai_vision_capture".to_string(),
+        result.coordinates,
+        AutomationError::PlaybackError { message },
+    ))
+}
+
+/// Run an AI Vision Capture step of a loaded script
+///
+/// The step's `AIVisionCaptureAction` is carried in
+/// `additional_data.vision_action`. Static and cached steps use their saved
+/// coordinates. Dynamic steps ask `ai_provider`, charged to `budget` when one
+/// is set, with a screenshot downscaled per `resource_profile`. What the AI
+/// finds is kept in `vision_cache` for later loops and written back to the
+/// script file when its path is known.
+fn execute_ai_vision_step(
+    platform: &dyn PlatformAutomation,
+    script: &ScriptData,
+    action: &Action,
+    action_index: usize,
+    ai_provider: Option<&dyn AIVisionProvider>,
+    budget: Option<&TokenBudget>,
+    resource_profile: &ResourceProfile,
+    vision_cache: &mut HashMap<String, CacheUpdate>,
+) -> AIVisionExecutionResult {
+    let vision_value = action.additional_data.as_ref().and_then(|data| data.get("vision_action")).cloned();
+    let parsed = vision_value.map(serde_json::from_value::<crate::script::AIVisionCaptureAction>);
+    let mut vision_action = match parsed {
+        Some(Ok(vision_action)) => vision_action,
+        Some(Err(e)) => {
+            let reason = format!("Invalid AI Vision Capture data: {}", e);
+            Player::log_action_skipped(action_index, action, &reason);
+            return AIVisionExecutionResult {
+                success: false,
+                mode: AIVisionExecutionMode::Skipped,
+                coordinates: None,
+                ai_called: false,
+                error: Some(reason),
+            };
+        }
+        None => {
+            let reason = "Missing AI Vision Capture data".to_string();
+            Player::log_action_skipped(action_index, action, &reason);
+            return AIVisionExecutionResult {
+                success: false,
+                mode: AIVisionExecutionMode::Skipped,
+                coordinates: None,
+                ai_called: false,
+                error: Some(reason),
+            };
+        }
+    };
+
+    if let Some(update) = vision_cache.get(&vision_action.id) {
+        crate::ai_vision_integration::apply_cache_update(&mut vision_action, update);
+    }
+
+    let ai_provider = match ai_provider {
+        Some(provider) if vision_action.is_dynamic && !vision_action.has_cached_coordinates() => provider,
+        _ => return execute_ai_vision_capture(platform, &vision_action, action_index),
+    };
+
+    log_ai_vision_execution_start(action_index, &vision_action);
+    let (screenshot_base64, screenshot_scale) = match platform.take_screenshot() {
+        Ok(bytes) => {
+            let (bytes, scale) = resource_profile.downscale_encoded_screenshot(bytes);
+            (general_purpose::STANDARD.encode(bytes), scale)
+        }
+        Err(e) => {
+            log_ai_vision_error(action_index, &vision_action, &format!("Failed to capture screenshot: {}", e));
+            return AIVisionExecutionResult {
+                success: false,
+                mode: AIVisionExecutionMode::Dynamic,
+                coordinates: None,
+                ai_called: false,
+                error: Some(format!("Failed to capture screenshot: {}", e)),
+            };
+        }
+    };
+
+    let mut reference_images_base64 = Vec::new();
+    for reference in &vision_action.dynamic_config.reference_images {
+        match std::fs::read(resolve_script_asset_path(script, reference)) {
+            Ok(bytes) => reference_images_base64.push(general_purpose::STANDARD.encode(bytes)),
+            Err(e) => log_ai_vision_error(
+                action_index,
+                &vision_action,
+                &format!("Ignoring reference image '{}': {}", reference, e),
+            ),
+        }
+    }
+
+    // The AI sees the downscaled screenshot, so map its findings back to the screen
+    let scaled_provider = ScaledVisionProvider {
+        inner: ai_provider,
+        scale: screenshot_scale,
+    };
+    let ai_provider: &dyn AIVisionProvider = if screenshot_scale != 1.0 { &scaled_provider } else { ai_provider };
+
+    let result = match budget {
+        Some(budget) => execute_dynamic_mode_with_budget(
+            platform,
+            &vision_action,
+            action_index,
+            ai_provider,
+            budget,
+            screenshot_base64,
+            reference_images_base64,
+        ),
+        None => execute_dynamic_mode_with_ai(
+            platform,
+            &vision_action,
+            action_index,
+            ai_provider,
+            screenshot_base64,
+            reference_images_base64,
+        ),
+    };
+
+    if let Some(update) = result.cache_update {
+        let script_path = script.metadata.additional_data.get("script_path").and_then(|v| v.as_str());
+        if let Some(script_path) = script_path {
+            if let Err(e) = persist_cache_update(script_path, &vision_action.id, &update) {
+                log_ai_vision_error(action_index, &vision_action, &format!("Failed to save cache: {}", e));
+            }
+        }
+        vision_cache.insert(vision_action.id.clone(), update);
+    }
+
+    result.execution_result
+}
+
+/// Provider wrapper that records reported token usage against a budget
+struct MeteredVisionProvider<'a> {
+    inner: &'a dyn crate::ai_vision_integration::AIVisionProvider,
+    budget: &'a crate::ai_budget::TokenBudget,
+}
+
+impl crate::ai_vision_integration::AIVisionProvider for MeteredVisionProvider<'_> {
+    fn analyze(
+        &self,
+        request: crate::ai_vision_integration::AIVisionAnalysisRequest,
+    ) -> std::result::Result<crate::ai_vision_integration::AIVisionAnalysisResponse, String> {
+        let response = self.inner.analyze(request)?;
+        if let Some(usage) = response.token_usage {
+            self.budget.record_usage(usage);
+        }
+        Ok(response)
+    }
+}
+
+/// Provider wrapper for screenshots downscaled by `scale`
+///
+/// Scales the region of interest down to the screenshot and the reported
+/// location back up to screen coordinates.
+struct ScaledVisionProvider<'a> {
+    inner: &'a dyn crate::ai_vision_integration::AIVisionProvider,
+    scale: f64,
+}
+
+impl crate::ai_vision_integration::AIVisionProvider for ScaledVisionProvider<'_> {
+    fn analyze(
+        &self,
+        mut request: crate::ai_vision_integration::AIVisionAnalysisRequest,
+    ) -> std::result::Result<crate::ai_vision_integration::AIVisionAnalysisResponse, String> {
+        if let Some(roi) = request.roi.as_mut() {
+            roi.x = (roi.x as f64 / self.scale).round() as i32;
+            roi.y = (roi.y as f64 / self.scale).round() as i32;
+            roi.width = ((roi.width as f64 / self.scale).round() as u32).max(1);
+            roi.height = ((roi.height as f64 / self.scale).round() as u32).max(1);
+        }
+
+        let mut response = self.inner.analyze(request)?;
+        response.x = response.x.map(|x| (x as f64 * self.scale).round() as i32);
+        response.y = response.y.map(|y| (y as f64 * self.scale).round() as i32);
+        Ok(response)
+    }
+}
+
 /// Result of Dynamic Mode execution including cache update information
 #[derive(Debug, Clone)]
 pub struct DynamicModeExecutionResult {
@@ -3462,7 +4097,7 @@ mod tests {
         assert!(!player.has_event_sender());
         
         // Set up event sender
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         // Now has event sender
@@ -3476,7 +4111,7 @@ mod tests {
         let mut player = Player::new(config).unwrap();
         
         // Set up event sender and receiver
-        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let (sender, mut receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         // The set_event_sender should send a test event
@@ -3502,7 +4137,7 @@ mod tests {
         let mut player = Player::new(config).unwrap();
         
         // Set up event sender and receiver
-        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let (sender, mut receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         // Clear the initialization event
@@ -3609,7 +4244,7 @@ mod tests {
         player.load_script(script).unwrap();
         
         // Set up event sender
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         // Cannot pause when not playing
@@ -3648,7 +4283,7 @@ mod tests {
         player.load_script(script).unwrap();
         
         // Set up event sender
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         // Cannot stop when not playing
@@ -3682,7 +4317,7 @@ mod tests {
         player.load_script(script).unwrap();
         
         // Set up event sender
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         // Start playback (will fail due to permissions, but that's ok for this test)
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use crate::{Result, AutomationError};
+use crate::localization::Locale;
+use crate::resource_profile::ResourceMode;
 
 /// Core type selection
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -44,6 +46,10 @@ pub struct UserPreferences {
     
     /// User settings that should be preserved during core switching
     pub user_settings: UserSettings,
+
+    /// Low-resource mode setting, applied on the next start
+    #[serde(default)]
+    pub resource_mode: ResourceMode,
 }
 
 /// User settings that should be preserved during core switching
@@ -60,6 +66,10 @@ pub struct UserSettings {
     
     /// UI state preferences
     pub ui_state: UIState,
+
+    /// Language used for user-facing error messages
+    #[serde(default)]
+    pub locale: Locale,
 }
 
 /// UI state that should be preserved during core switching
@@ -94,6 +104,7 @@ impl Default for UserSettings {
             loop_count: 1,
             selected_script_path: None,
             ui_state: UIState::default(),
+            locale: Locale::default(),
         }
     }
 }
@@ -119,6 +130,7 @@ impl Default for UserPreferences {
             last_working_core: None,
             last_updated: chrono::Utc::now(),
             user_settings: UserSettings::default(),
+            resource_mode: ResourceMode::default(),
         }
     }
 }
@@ -248,6 +260,14 @@ impl PreferenceManager {
         Ok(())
     }
     
+    /// Update the resource mode; takes effect on the next start
+    pub fn set_resource_mode(&mut self, mode: ResourceMode) -> Result<()> {
+        self.current_preferences.resource_mode = mode;
+        self.current_preferences.last_updated = chrono::Utc::now();
+        self.save_to_file()?;
+        Ok(())
+    }
+    
     /// Get the preferred core type
     pub fn get_preferred_core(&self) -> CoreType {
         self.current_preferences.preferred_core
@@ -302,6 +322,14 @@ impl PreferenceManager {
         Ok(())
     }
     
+    /// Update the language used for error messages
+    pub fn set_locale(&mut self, locale: Locale) -> Result<()> {
+        self.current_preferences.user_settings.locale = locale;
+        self.current_preferences.last_updated = chrono::Utc::now();
+        self.save_to_file()?;
+        Ok(())
+    }
+
     /// Update UI state settings
     pub fn set_ui_state(&mut self, ui_state: UIState) -> Result<()> {
         self.current_preferences.user_settings.ui_state = ui_state;
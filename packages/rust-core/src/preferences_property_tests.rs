@@ -4,6 +4,7 @@
 //! **Validates: Requirements 1.2**
 
 use crate::preferences::{PreferenceManager, CoreType, UserSettings, UIState, WindowGeometry};
+use crate::localization::Locale;
 use proptest::prelude::*;
 use tempfile::TempDir;
 
@@ -74,6 +75,7 @@ impl Arbitrary for UserSettings {
                 loop_count,
                 selected_script_path,
                 ui_state,
+                locale: Locale::default(),
             }
         }).boxed()
     }
@@ -3,15 +3,19 @@
 use crate::{
     Result, AutomationError, AutomationConfig, ScriptData, Action, ActionType,
     AIVisionCaptureAction,
-    platform::{PlatformAutomation, create_platform_automation},
-    logging::{CoreType, OperationType, LogLevel, get_logger}
+    platform::{PlatformAutomation, create_platform_automation, platform_cache},
+    logging::{CoreType, OperationType, LogLevel, get_logger},
+    resource_profile::{configured_resource_profile, ResourceProfile},
+    clock::{system_clock, SharedClock},
+    event_channel::{event_channel, EventSender, DEFAULT_EVENT_CAPACITY},
 };
 use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use serde::{Serialize, Deserialize};
 use std::thread;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use uuid::Uuid;
 #[cfg(target_os = "macos")]
 use core_graphics::event::{
@@ -32,6 +36,61 @@ pub struct ModifierState {
     pub alt_held: bool,
 }
 
+/// Writes click screenshots to disk as they are taken
+#[derive(Clone)]
+struct ClickScreenshotWriter {
+    directory: PathBuf,
+    counter: Arc<Mutex<u32>>,
+}
+
+impl ClickScreenshotWriter {
+    /// Take a screenshot, write it to the directory and reference it from the click
+    ///
+    /// Failures are logged and leave the action without a screenshot.
+    fn capture(&self, platform: &dyn PlatformAutomation, action: &mut Action) {
+        let screenshot_data = match capture_click_screen(platform) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("Failed to capture click screenshot: {}", e);
+                return;
+            }
+        };
+
+        let filename = {
+            let mut counter = self.counter.lock().unwrap();
+            *counter += 1;
+            format!("screenshot_{:04}.png", *counter)
+        };
+        let written = std::fs::create_dir_all(&self.directory)
+            .and_then(|_| std::fs::write(self.directory.join(&filename), screenshot_data));
+        if let Err(e) = written {
+            eprintln!("Failed to write click screenshot {}: {}", filename, e);
+            return;
+        }
+
+        // Store screenshot reference in action
+        action
+            .additional_data
+            .get_or_insert_with(HashMap::new)
+            .insert("screenshot".to_string(), serde_json::Value::String(filename));
+    }
+}
+
+/// PNG of the screen at a click
+///
+/// Platform capture is not available everywhere yet, so this falls back to
+/// the system tool the player uses for visual assertions.
+fn capture_click_screen(platform: &dyn PlatformAutomation) -> std::result::Result<Vec<u8>, String> {
+    if let Ok(data) = platform.take_screenshot() {
+        return Ok(data);
+    }
+
+    let path = crate::player::capture_screenshot_to_temp_png("click_screenshot")?;
+    let data = std::fs::read(&path).map_err(|e| format!("Failed to read captured screenshot: {}", e));
+    let _ = std::fs::remove_file(&path);
+    data
+}
+
 /// Event recorder that captures user interactions
 pub struct Recorder {
     platform: Box<dyn PlatformAutomation>,
@@ -39,14 +98,22 @@ pub struct Recorder {
     is_recording: Arc<AtomicBool>,
     start_time: Option<Instant>,
     recorded_actions: Arc<Mutex<Vec<Action>>>,
-    event_sender: Option<mpsc::UnboundedSender<RecordingEvent>>,
+    event_sender: Option<EventSender<RecordingEvent>>,
     screenshot_counter: Arc<Mutex<u32>>,
+    /// Directory click screenshots are written to as they are taken
+    screenshot_directory: Option<PathBuf>,
     /// Modifier key state for hotkey detection
     modifier_state: Arc<Mutex<ModifierState>>,
     /// List of AI Vision Capture actions recorded
     vision_actions: Arc<Mutex<Vec<AIVisionCaptureAction>>>,
     /// Counter for vision capture screenshots
     vision_screenshot_counter: Arc<Mutex<u32>>,
+    /// Time source for the timestamps of actions recorded through `record_*`
+    clock: SharedClock,
+    /// Clock reading when recording started
+    clock_start: Duration,
+    /// Whether to hook OS input events while recording
+    capture_input: bool,
 }
 
 /// Events that can be recorded
@@ -101,8 +168,17 @@ pub struct ActionEventData {
 
 impl Recorder {
     /// Create a new recorder instance
+    ///
+    /// Uses the profile selected with `init_resource_profile`, or the normal
+    /// profile if none was selected.
     pub fn new(config: AutomationConfig) -> Result<Self> {
+        Self::with_resource_profile(config, &configured_resource_profile())
+    }
+
+    /// Create a recorder degraded according to `profile`
+    pub fn with_resource_profile(mut config: AutomationConfig, profile: &ResourceProfile) -> Result<Self> {
         let platform = create_platform_automation()?;
+        profile.apply_to_automation(&mut config);
         
         Ok(Self {
             platform,
@@ -112,22 +188,53 @@ impl Recorder {
             recorded_actions: Arc::new(Mutex::new(Vec::new())),
             event_sender: None,
             screenshot_counter: Arc::new(Mutex::new(0)),
+            screenshot_directory: None,
             modifier_state: Arc::new(Mutex::new(ModifierState::default())),
             vision_actions: Arc::new(Mutex::new(Vec::new())),
             vision_screenshot_counter: Arc::new(Mutex::new(0)),
+            clock: system_clock(),
+            clock_start: Duration::ZERO,
+            capture_input: true,
         })
     }
 
     /// Set event sender for real-time UI updates
-    pub fn set_event_sender(&mut self, sender: mpsc::UnboundedSender<RecordingEvent>) {
+    pub fn set_event_sender(&mut self, sender: EventSender<RecordingEvent>) {
         self.event_sender = Some(sender);
     }
 
+    /// Set the directory click screenshots are written to
+    ///
+    /// Actions refer to the screenshots by file name, so this is normally the
+    /// `<script name>_screenshots` directory next to the script. Without a
+    /// directory no click screenshots are taken.
+    pub fn set_screenshot_directory(&mut self, directory: impl Into<PathBuf>) {
+        self.screenshot_directory = Some(directory.into());
+    }
+
+    /// Number of click screenshots written since recording started
+    pub fn screenshot_count(&self) -> u32 {
+        *self.screenshot_counter.lock().unwrap()
+    }
+
     /// Set whether to capture screenshots on mouse clicks
     pub fn set_capture_screenshot_on_click(&mut self, enabled: bool) {
         self.config.capture_screenshot_on_click = enabled;
     }
 
+    /// Replace the time source used for recorded timestamps
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Set whether to hook OS input events while recording
+    ///
+    /// With capture disabled only actions passed to the `record_*` methods are
+    /// recorded, e.g. when input is synthesized by a test.
+    pub fn set_input_capture(&mut self, enabled: bool) {
+        self.capture_input = enabled;
+    }
+
     /// Start recording user interactions
     pub fn start_recording(&mut self) -> Result<()> {
         let operation_id = format!("start_recording_{}", chrono::Utc::now().timestamp());
@@ -193,6 +300,7 @@ impl Recorder {
         // Initialize recording state
         self.is_recording.store(true, Ordering::SeqCst);
         self.start_time = Some(Instant::now());
+        self.clock_start = self.clock.now();
         {
             let mut actions = self.recorded_actions.lock().unwrap();
             actions.clear();
@@ -224,7 +332,9 @@ impl Recorder {
         });
 
         // Start platform-specific event capture
-        self.start_platform_capture()?;
+        if self.capture_input {
+            self.start_platform_capture()?;
+        }
 
         if let Some(logger) = get_logger() {
             logger.log_operation(
@@ -236,7 +346,7 @@ impl Recorder {
                 Some({
                     let mut metadata = HashMap::new();
                     metadata.insert("platform".to_string(), serde_json::json!(self.platform.platform_name()));
-                    metadata.insert("capture_screenshots".to_string(), serde_json::json!(self.config.capture_screenshots));
+                    metadata.insert("capture_screenshot_on_click".to_string(), serde_json::json!(self.config.capture_screenshot_on_click));
                     metadata
                 }),
             );
@@ -363,9 +473,7 @@ impl Recorder {
 
         self.is_recording.store(false, Ordering::SeqCst);
         
-        let duration = self.start_time
-            .map(|start| start.elapsed().as_secs_f64())
-            .unwrap_or(0.0);
+        let duration = self.get_timestamp();
 
         let mut script = ScriptData::new("rust", self.platform.platform_name());
         script.metadata.duration = duration;
@@ -459,23 +567,8 @@ impl Recorder {
         let mut action = Action::mouse_click(x, y, button, timestamp);
         
         // Capture screenshot if enabled
-        if self.config.capture_screenshots {
-            if let Ok(_screenshot_data) = self.platform.take_screenshot() {
-                let mut counter = self.screenshot_counter.lock().unwrap();
-                *counter += 1;
-                let filename = format!("screenshot_{:04}.png", *counter);
-                
-                // Store screenshot reference in action
-                if let Some(ref mut additional_data) = action.additional_data {
-                    additional_data.insert("screenshot".to_string(), 
-                        serde_json::Value::String(filename));
-                } else {
-                    let mut data = HashMap::new();
-                    data.insert("screenshot".to_string(), 
-                        serde_json::Value::String(filename));
-                    action.additional_data = Some(data);
-                }
-            }
+        if let Some(writer) = self.click_screenshot_writer() {
+            writer.capture(self.platform.as_ref(), &mut action);
         }
         
         // Add to recorded actions
@@ -532,10 +625,21 @@ impl Recorder {
         Ok(())
     }
 
+    /// Writer for click screenshots, if they are enabled for this recording
+    fn click_screenshot_writer(&self) -> Option<ClickScreenshotWriter> {
+        if !self.config.capture_screenshot_on_click {
+            return None;
+        }
+        self.screenshot_directory.as_ref().map(|directory| ClickScreenshotWriter {
+            directory: directory.clone(),
+            counter: Arc::clone(&self.screenshot_counter),
+        })
+    }
+
     /// Get current timestamp relative to recording start
     fn get_timestamp(&self) -> f64 {
         self.start_time
-            .map(|start| start.elapsed().as_secs_f64())
+            .map(|_| self.clock.now().saturating_sub(self.clock_start).as_secs_f64())
             .unwrap_or(0.0)
     }
 
@@ -545,11 +649,15 @@ impl Recorder {
         let recorded_actions = Arc::clone(&self.recorded_actions);
         let start_time = self.start_time;
         let event_sender = self.event_sender.clone();
+        let min_move_interval = self.config.mouse_move_interval_ms as f64 / 1000.0;
+        let screenshots = self.click_screenshot_writer();
+        // Taken on this thread so a test platform installed here is used by the capture threads
+        let platforms = platform_cache();
 
         // macOS: use Core Graphics event tap (safe) to capture keyboard & mouse without rdev crashes
         #[cfg(target_os = "macos")]
         {
-            self.start_macos_event_tap_capture(is_recording, recorded_actions, start_time, event_sender)?;
+            self.start_macos_event_tap_capture(is_recording, recorded_actions, start_time, event_sender, min_move_interval, screenshots, platforms)?;
         }
 
         // Other platforms: use rdev listener
@@ -597,7 +705,6 @@ impl Recorder {
                 let mouse_move_threshold = 10; // Minimum pixels to record a move
                 let max_actions = 50000; // Limit to prevent memory issues
                 let mut last_move_time = 0.0f64;
-                let min_move_interval = 0.05; // Minimum 50ms between mouse moves
 
                 while is_recording.load(Ordering::SeqCst) {
                     // Use recv_timeout to allow checking is_recording periodically
@@ -662,8 +769,15 @@ impl Recorder {
                                         rdev::Button::Middle => "middle",
                                         _ => "unknown",
                                     };
+                                    let mut action = Action::mouse_click(pos.0, pos.1, button_str, timestamp);
+                                    // Capture before taking the lock; the screenshot is slow
+                                    if let Some(ref writer) = screenshots {
+                                        match platforms.get() {
+                                            Ok(platform) => writer.capture(platform.as_ref(), &mut action),
+                                            Err(e) => eprintln!("Failed to get platform for click screenshot: {:?}", e),
+                                        }
+                                    }
                                     if let Ok(mut actions) = recorded_actions.try_lock() {
-                                        let action = Action::mouse_click(pos.0, pos.1, button_str, timestamp);
                                         actions.push(action);
                                     }
                                 }
@@ -706,7 +820,10 @@ impl Recorder {
         is_recording: Arc<AtomicBool>,
         recorded_actions: Arc<Mutex<Vec<Action>>>,
         start_time: Option<Instant>,
-        event_sender: Option<mpsc::UnboundedSender<RecordingEvent>>,
+        event_sender: Option<EventSender<RecordingEvent>>,
+        min_move_interval: f64,
+        screenshots: Option<ClickScreenshotWriter>,
+        platforms: Arc<crate::platform::PlatformHandleCache>,
     ) -> Result<()> {
         // Avoid expensive map allocation on every event: build once
         let keycode_to_name = Self::macos_keycode_map();
@@ -731,7 +848,6 @@ impl Recorder {
         thread::spawn(move || {
             let max_actions = 50000usize;
             let mouse_move_threshold = 10;
-            let min_move_interval = 0.05f64;
             // Use RefCell for interior mutability since CGEventTap expects Fn, not FnMut
             use std::cell::RefCell;
             let last_mouse_pos: RefCell<Option<(i32, i32)>> = RefCell::new(None);
@@ -858,9 +974,16 @@ impl Recorder {
                             CGEventType::RightMouseDown => "right",
                             _ => "middle",
                         };
+                        let mut action = Action::mouse_click(x, y, button_str, timestamp);
+                        // Capture before taking the lock; the screenshot is slow
+                        if let Some(ref writer) = screenshots {
+                            match platforms.get() {
+                                Ok(platform) => writer.capture(platform.as_ref(), &mut action),
+                                Err(e) => eprintln!("Failed to get platform for click screenshot: {:?}", e),
+                            }
+                        }
                         if let Ok(mut actions) = actions_ref.try_lock() {
-                            let action = Action::mouse_click(x, y, button_str, timestamp);
-                            actions.push(action.clone());
+                            actions.push(action);
                             
                             // Send event to UI for real-time feedback
                             if let Some(ref sender) = event_sender_ref {
@@ -1275,7 +1398,7 @@ impl Recorder {
         vision_actions: Arc<Mutex<Vec<AIVisionCaptureAction>>>,
         recorded_actions: Arc<Mutex<Vec<Action>>>,
         start_time: Option<Instant>,
-        event_sender: Option<mpsc::UnboundedSender<RecordingEvent>>,
+        event_sender: Option<EventSender<RecordingEvent>>,
         screen_size_result: Result<(u32, u32)>,
         screenshot_result: Result<Vec<u8>>,
     ) -> Result<(Vec<u8>, AIVisionCaptureAction)> {
@@ -1447,15 +1570,15 @@ impl Recorder {
 /// Background recorder that captures system events
 pub struct BackgroundRecorder {
     recorder: Arc<Mutex<Recorder>>,
-    event_sender: mpsc::UnboundedSender<RecordedEvent>,
-    event_receiver: Option<mpsc::UnboundedReceiver<RecordedEvent>>,
+    event_sender: EventSender<RecordedEvent>,
+    event_receiver: Option<mpsc::Receiver<RecordedEvent>>,
 }
 
 impl BackgroundRecorder {
     /// Create a new background recorder
     pub fn new(config: AutomationConfig) -> Result<Self> {
         let recorder = Arc::new(Mutex::new(Recorder::new(config)?));
-        let (event_sender, event_receiver) = mpsc::unbounded_channel();
+        let (event_sender, event_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
 
         Ok(Self {
             recorder,
@@ -1465,7 +1588,7 @@ impl BackgroundRecorder {
     }
 
     /// Set event sender for real-time UI updates
-    pub fn set_event_sender(&mut self, sender: mpsc::UnboundedSender<RecordingEvent>) {
+    pub fn set_event_sender(&mut self, sender: EventSender<RecordingEvent>) {
         let mut recorder = self.recorder.lock().unwrap();
         recorder.set_event_sender(sender);
     }
@@ -1522,7 +1645,7 @@ impl BackgroundRecorder {
     }
 
     /// Get event sender for external event sources
-    pub fn get_event_sender(&self) -> mpsc::UnboundedSender<RecordedEvent> {
+    pub fn get_event_sender(&self) -> EventSender<RecordedEvent> {
         self.event_sender.clone()
     }
 }
@@ -0,0 +1,503 @@
+//! Recording quality scoring
+//!
+//! After a recording ends, the recorded script is scored for traits that make
+//! scripts slow or brittle to maintain: wandering mouse paths, long idle gaps,
+//! no assertions, and oversized screenshots. The score and findings are
+//! returned with the recording result so users can tidy the script before
+//! saving it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use crate::asset_manager::to_native_path;
+use crate::script::{Action, ActionType, ScriptData};
+use crate::validation::IssueSeverity;
+use crate::Result;
+
+/// Thresholds used when scoring a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingQualityConfig {
+    /// Mouse path length divided by the straight-line distance between two
+    /// interactions above which the movement counts as wandering
+    pub max_mouse_path_ratio: f64,
+    /// Paths shorter than this (in pixels) are never reported as wandering
+    pub min_wandering_distance: f64,
+    /// Gaps between actions longer than this (in seconds) are reported
+    pub max_idle_gap_seconds: f64,
+    /// Screenshots larger than this (in bytes) are reported
+    pub max_screenshot_bytes: u64,
+    /// Report recordings that contain no assertions
+    pub require_assertions: bool,
+}
+
+impl Default for RecordingQualityConfig {
+    fn default() -> Self {
+        Self {
+            max_mouse_path_ratio: 3.0,
+            min_wandering_distance: 400.0,
+            max_idle_gap_seconds: 10.0,
+            max_screenshot_bytes: 2 * 1024 * 1024, // 2MB
+            require_assertions: true,
+        }
+    }
+}
+
+/// Kind of quality finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityFindingKind {
+    MouseWandering,
+    LongIdleGap,
+    MissingAssertions,
+    OversizedScreenshot,
+    UnparsableAction,
+}
+
+impl QualityFindingKind {
+    /// Points deducted from the score per finding
+    fn penalty(&self) -> u32 {
+        match self {
+            QualityFindingKind::MouseWandering => 5,
+            QualityFindingKind::LongIdleGap => 5,
+            QualityFindingKind::MissingAssertions => 20,
+            QualityFindingKind::OversizedScreenshot => 3,
+            QualityFindingKind::UnparsableAction => 10,
+        }
+    }
+
+    /// Maximum points deducted for all findings of this kind
+    fn max_penalty(&self) -> u32 {
+        match self {
+            QualityFindingKind::MouseWandering => 25,
+            QualityFindingKind::LongIdleGap => 20,
+            QualityFindingKind::MissingAssertions => 20,
+            QualityFindingKind::OversizedScreenshot => 15,
+            QualityFindingKind::UnparsableAction => 30,
+        }
+    }
+}
+
+/// A single quality issue found in a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityFinding {
+    pub kind: QualityFindingKind,
+    pub severity: IssueSeverity,
+    /// Index of the action the finding refers to, if any
+    pub action_index: Option<usize>,
+    pub message: String,
+    pub suggestion: String,
+}
+
+/// Quality score (0-100) and findings for a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingQualityReport {
+    pub score: u8,
+    pub findings: Vec<QualityFinding>,
+}
+
+impl RecordingQualityReport {
+    /// Number of findings of a given kind
+    pub fn count(&self, kind: QualityFindingKind) -> usize {
+        self.findings.iter().filter(|f| f.kind == kind).count()
+    }
+}
+
+/// Scores recorded scripts against a quality configuration
+#[derive(Debug, Clone, Default)]
+pub struct RecordingQualityAnalyzer {
+    config: RecordingQualityConfig,
+}
+
+impl RecordingQualityAnalyzer {
+    /// Create an analyzer with the given thresholds
+    pub fn new(config: RecordingQualityConfig) -> Self {
+        Self { config }
+    }
+
+    /// Score a recorded script
+    ///
+    /// Screenshot sizes are only checked when `script_path` is given, since
+    /// screenshot paths are relative to the script's location.
+    pub fn analyze(&self, script: &ScriptData, script_path: Option<&Path>) -> RecordingQualityReport {
+        let raw_actions: Vec<Value> = script
+            .actions
+            .iter()
+            .map(|action| serde_json::to_value(action).unwrap_or(Value::Null))
+            .collect();
+        let actions: Vec<(usize, Action)> = script.actions.iter().cloned().enumerate().collect();
+        self.report(&actions, &raw_actions, script_path, Vec::new())
+    }
+
+    /// Score a saved script file recorded by either core
+    ///
+    /// The file is read as raw JSON, so Python core recordings are scored even
+    /// though their metadata does not match `ScriptData`, and screenshots
+    /// stored outside `additional_data` are found.
+    pub fn analyze_file<P: AsRef<Path>>(&self, script_path: P) -> Result<RecordingQualityReport> {
+        let script_path = script_path.as_ref();
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(script_path)?)?;
+        let raw_actions = json
+            .get("actions")
+            .and_then(|actions| actions.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        // Keep each action's position in the file so findings point at the right one
+        let mut actions = Vec::new();
+        let mut findings = Vec::new();
+        for (index, raw_action) in raw_actions.iter().enumerate() {
+            match serde_json::from_value::<Action>(raw_action.clone()) {
+                Ok(action) => actions.push((index, action)),
+                Err(e) => findings.push(QualityFinding {
+                    kind: QualityFindingKind::UnparsableAction,
+                    severity: IssueSeverity::Warning,
+                    action_index: Some(index),
+                    message: format!("Action {} could not be read and was not checked: {}", index, e),
+                    suggestion: "Re-record the step or fix the action in the script file".to_string(),
+                }),
+            }
+        }
+
+        Ok(self.report(&actions, &raw_actions, Some(script_path), findings))
+    }
+
+    /// Run every check over `actions`, which are paired with their index in the script
+    fn report(
+        &self,
+        actions: &[(usize, Action)],
+        raw_actions: &[Value],
+        script_path: Option<&Path>,
+        mut findings: Vec<QualityFinding>,
+    ) -> RecordingQualityReport {
+
+        self.check_mouse_wandering(actions, &mut findings);
+        self.check_idle_gaps(actions, &mut findings);
+        self.check_assertions(actions, &mut findings);
+        if let Some(script_path) = script_path {
+            self.check_screenshot_sizes(raw_actions, &screenshot_directories(script_path), &mut findings);
+        }
+
+        RecordingQualityReport {
+            score: Self::score(&findings),
+            findings,
+        }
+    }
+
+    /// Compare the mouse path between interactions with the direct distance
+    fn check_mouse_wandering(&self, actions: &[(usize, Action)], findings: &mut Vec<QualityFinding>) {
+        let mut anchor: Option<(f64, f64)> = None;
+        let mut last_position: Option<(f64, f64)> = None;
+        let mut path_length = 0.0;
+
+        for &(index, ref action) in actions {
+            let position = match (action.x, action.y) {
+                (Some(x), Some(y)) => (x as f64, y as f64),
+                _ => continue,
+            };
+
+            if let Some(last) = last_position {
+                path_length += distance(last, position);
+            }
+            last_position = Some(position);
+
+            if action.action_type == ActionType::MouseMove {
+                if anchor.is_none() {
+                    anchor = Some(position);
+                }
+                continue;
+            }
+
+            if let Some(start) = anchor {
+                let direct = distance(start, position).max(1.0);
+                let ratio = path_length / direct;
+                if path_length >= self.config.min_wandering_distance && ratio > self.config.max_mouse_path_ratio {
+                    findings.push(QualityFinding {
+                        kind: QualityFindingKind::MouseWandering,
+                        severity: IssueSeverity::Info,
+                        action_index: Some(index),
+                        message: format!(
+                            "Mouse travelled {:.0}px to reach a target {:.0}px away before action {}",
+                            path_length, direct, index
+                        ),
+                        suggestion: "Remove the intermediate mouse moves so playback goes straight to the target".to_string(),
+                    });
+                }
+            }
+
+            // Every interaction starts a new segment
+            anchor = Some(position);
+            path_length = 0.0;
+        }
+    }
+
+    /// Report long pauses between consecutive actions
+    fn check_idle_gaps(&self, actions: &[(usize, Action)], findings: &mut Vec<QualityFinding>) {
+        for pair in actions.windows(2) {
+            let (previous, (index, current)) = (&pair[0].1, &pair[1]);
+            // Explicit waits are intentional pauses
+            if current.action_type == ActionType::Wait || previous.action_type == ActionType::Wait {
+                continue;
+            }
+
+            let gap = current.timestamp - previous.timestamp;
+            if gap > self.config.max_idle_gap_seconds {
+                findings.push(QualityFinding {
+                    kind: QualityFindingKind::LongIdleGap,
+                    severity: IssueSeverity::Warning,
+                    action_index: Some(*index),
+                    message: format!("Idle for {:.1}s before action {}", gap, index),
+                    suggestion: "Trim the pause or replace it with an explicit wait or visual assertion".to_string(),
+                });
+            }
+        }
+    }
+
+    /// Report recordings that never verify anything
+    fn check_assertions(&self, actions: &[(usize, Action)], findings: &mut Vec<QualityFinding>) {
+        if !self.config.require_assertions || actions.is_empty() {
+            return;
+        }
+
+        if !actions.iter().any(|(_, a)| a.action_type == ActionType::VisualAssert) {
+            findings.push(QualityFinding {
+                kind: QualityFindingKind::MissingAssertions,
+                severity: IssueSeverity::Warning,
+                action_index: None,
+                message: "Recording contains no assertions, so playback can only fail on errors".to_string(),
+                suggestion: "Add a visual assertion after key steps to verify the expected result".to_string(),
+            });
+        }
+    }
+
+    /// Report screenshots whose files exceed the size limit
+    fn check_screenshot_sizes(&self, actions: &[Value], directories: &[PathBuf], findings: &mut Vec<QualityFinding>) {
+        for (index, action) in actions.iter().enumerate() {
+            for screenshot in screenshot_references(action) {
+                let size = match directories
+                    .iter()
+                    .find_map(|dir| std::fs::metadata(dir.join(to_native_path(screenshot))).ok())
+                {
+                    Some(metadata) => metadata.len(),
+                    None => continue,
+                };
+
+                if size > self.config.max_screenshot_bytes {
+                    findings.push(QualityFinding {
+                        kind: QualityFindingKind::OversizedScreenshot,
+                        severity: IssueSeverity::Info,
+                        action_index: Some(index),
+                        message: format!(
+                            "Screenshot '{}' is {:.1}MB",
+                            screenshot,
+                            size as f64 / (1024.0 * 1024.0)
+                        ),
+                        suggestion: "Capture a region instead of the full screen, or lower the capture resolution".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Compute the score from the findings, capping the deduction per kind
+    fn score(findings: &[QualityFinding]) -> u8 {
+        let kinds = [
+            QualityFindingKind::MouseWandering,
+            QualityFindingKind::LongIdleGap,
+            QualityFindingKind::MissingAssertions,
+            QualityFindingKind::OversizedScreenshot,
+            QualityFindingKind::UnparsableAction,
+        ];
+
+        let deduction: u32 = kinds
+            .iter()
+            .map(|kind| {
+                let count = findings.iter().filter(|f| f.kind == *kind).count() as u32;
+                (count * kind.penalty()).min(kind.max_penalty())
+            })
+            .sum();
+
+        100u32.saturating_sub(deduction) as u8
+    }
+}
+
+/// Screenshot paths an action refers to
+///
+/// Click screenshots are stored as `screenshot` (Python core) or
+/// `additional_data.screenshot` (Rust core); AI vision captures keep theirs in
+/// `static_data.original_screenshot`.
+fn screenshot_references(action: &Value) -> Vec<&str> {
+    let mut references: Vec<&str> = [
+        action.get("screenshot"),
+        action.pointer("/additional_data/screenshot"),
+        action.pointer("/static_data/original_screenshot"),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|value| value.as_str())
+    .collect();
+    references.dedup();
+    references
+}
+
+/// Directories screenshot paths may be relative to: the script's own
+/// directory and its `<script name>_screenshots` directory
+fn screenshot_directories(script_path: &Path) -> Vec<PathBuf> {
+    let script_dir = script_path.parent().map(Path::to_path_buf).unwrap_or_default();
+    let mut directories = vec![script_dir.clone()];
+    if let Some(stem) = script_path.file_stem().and_then(|s| s.to_str()) {
+        directories.push(script_dir.join(format!("{}_screenshots", stem)));
+    }
+    directories
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(actions: Vec<Action>) -> ScriptData {
+        let mut script = ScriptData::new("rust", "macos");
+        for action in actions {
+            script.add_action(action);
+        }
+        script
+    }
+
+    fn visual_assert(timestamp: f64) -> Action {
+        Action {
+            action_type: ActionType::VisualAssert,
+            timestamp,
+            x: None,
+            y: None,
+            button: None,
+            key: None,
+            text: None,
+            modifiers: None,
+            additional_data: None,
+        }
+    }
+
+    #[test]
+    fn test_clean_recording_scores_full_marks() {
+        let report = RecordingQualityAnalyzer::default().analyze(
+            &script(vec![
+                Action::mouse_move(100, 100, 0.0),
+                Action::mouse_move(200, 100, 0.1),
+                Action::mouse_click(300, 100, "left", 0.2),
+                visual_assert(1.0),
+            ]),
+            None,
+        );
+
+        assert_eq!(report.score, 100);
+        assert!(report.findings.is_empty());
+    }
+
+    #[test]
+    fn test_detects_wandering_idle_gaps_and_missing_assertions() {
+        let report = RecordingQualityAnalyzer::default().analyze(
+            &script(vec![
+                Action::mouse_move(100, 100, 0.0),
+                Action::mouse_move(900, 100, 0.2),
+                Action::mouse_move(100, 700, 0.4),
+                Action::mouse_move(900, 700, 0.6),
+                Action::mouse_click(150, 100, "left", 0.8),
+                Action::key_type("hello", 30.0),
+            ]),
+            None,
+        );
+
+        assert_eq!(report.count(QualityFindingKind::MouseWandering), 1);
+        assert_eq!(report.count(QualityFindingKind::LongIdleGap), 1);
+        assert_eq!(report.count(QualityFindingKind::MissingAssertions), 1);
+        assert_eq!(report.score, 100 - 5 - 5 - 20);
+    }
+
+    #[test]
+    fn test_detects_oversized_screenshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let screenshots_dir = dir.path().join("script_screenshots");
+        std::fs::create_dir_all(&screenshots_dir).unwrap();
+        std::fs::write(screenshots_dir.join("screenshot_0001.png"), vec![0u8; 2048]).unwrap();
+
+        let mut click = Action::mouse_click(10, 10, "left", 0.0);
+        click.additional_data = Some(
+            [("screenshot".to_string(), serde_json::json!("screenshot_0001.png"))].into_iter().collect(),
+        );
+
+        let analyzer = RecordingQualityAnalyzer::new(RecordingQualityConfig {
+            max_screenshot_bytes: 1024,
+            require_assertions: false,
+            ..Default::default()
+        });
+        let report = analyzer.analyze(&script(vec![click]), Some(&dir.path().join("script.json")));
+
+        assert_eq!(report.count(QualityFindingKind::OversizedScreenshot), 1);
+        assert_eq!(report.score, 97);
+    }
+
+    #[test]
+    fn test_scores_python_core_script_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("screenshots")).unwrap();
+        std::fs::write(dir.path().join("screenshots/vision_1.png"), vec![0u8; 2048]).unwrap();
+
+        let script_path = dir.path().join("script.json");
+        let script = serde_json::json!({
+            "metadata": {"version": "1.0", "created_at": "2024-01-01T00:00:00Z", "duration": 1.0, "action_count": 2, "platform": "darwin"},
+            "actions": [
+                {"type": "mouse_click", "timestamp": 0.0, "x": 10, "y": 10, "button": "left", "screenshot": null},
+                {
+                    "type": "ai_vision_capture",
+                    "id": "1",
+                    "timestamp": 0.5,
+                    "static_data": {"original_screenshot": "screenshots/vision_1.png", "screen_dim": [1920, 1080]}
+                }
+            ]
+        });
+        std::fs::write(&script_path, script.to_string()).unwrap();
+
+        let analyzer = RecordingQualityAnalyzer::new(RecordingQualityConfig {
+            max_screenshot_bytes: 1024,
+            ..Default::default()
+        });
+        let report = analyzer.analyze_file(&script_path).unwrap();
+
+        assert_eq!(report.count(QualityFindingKind::MissingAssertions), 1);
+        let oversized: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.kind == QualityFindingKind::OversizedScreenshot)
+            .collect();
+        assert_eq!(oversized.len(), 1);
+        assert_eq!(oversized[0].action_index, Some(1));
+    }
+
+    #[test]
+    fn test_unparsable_actions_are_reported_without_shifting_indices() {
+        let dir = tempfile::tempdir().unwrap();
+        let script_path = dir.path().join("script.json");
+        let script = serde_json::json!({
+            "actions": [
+                {"type": "mouse_click", "timestamp": 0.0, "x": 10, "y": 10, "button": "left"},
+                {"type": "mouse_wiggle", "timestamp": 0.1},
+                {"type": "key_type", "timestamp": 30.0, "text": "hello"}
+            ]
+        });
+        std::fs::write(&script_path, script.to_string()).unwrap();
+
+        let analyzer = RecordingQualityAnalyzer::new(RecordingQualityConfig {
+            require_assertions: false,
+            ..Default::default()
+        });
+        let report = analyzer.analyze_file(&script_path).unwrap();
+
+        let index_of = |kind| report.findings.iter().find(|f| f.kind == kind).and_then(|f| f.action_index);
+        assert_eq!(index_of(QualityFindingKind::UnparsableAction), Some(1));
+        assert_eq!(index_of(QualityFindingKind::LongIdleGap), Some(2));
+        assert_eq!(report.score, 100 - 10 - 5);
+    }
+}
@@ -0,0 +1,362 @@
+//! Graceful degradation for low-spec machines
+//!
+//! Old QA lab machines struggle with continuous screen capture, frequent
+//! polling and verbose logging. In low-resource mode the recorder stops
+//! continuous capture and samples mouse moves less often, review and AI
+//! Vision screenshots are downscaled, monitoring, assertion and display
+//! polling run less often, logging keeps only warnings and errors, and visual
+//! testing skips its extra stability and parallel work. The mode is set in the
+//! user preferences or detected from the host's memory and CPUs.
+
+use crate::config::AutomationConfig;
+use crate::logging::{LogLevel, LoggingConfig};
+use crate::monitoring::MonitoringConfig;
+use crate::visual_testing::models::PerformanceConfig;
+use crate::visual_testing::screen_capture::CaptureConfig;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Hosts with less memory than this are treated as low-spec
+pub const LOW_RESOURCE_MEMORY_MB: u64 = 4096;
+
+/// Hosts with fewer logical CPUs than this are treated as low-spec
+pub const LOW_RESOURCE_CPU_CORES: usize = 2;
+
+/// Widest screenshot kept for review in low-resource mode
+pub const LOW_RESOURCE_MAX_SCREENSHOT_WIDTH: u32 = 1280;
+
+/// Factor applied to polling intervals in low-resource mode
+pub const LOW_RESOURCE_POLL_MULTIPLIER: u32 = 4;
+
+/// In-memory history limit for logs and metrics in low-resource mode
+const LOW_RESOURCE_HISTORY_ENTRIES: usize = 1000;
+
+/// Requested resource mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceMode {
+    /// Use low-resource mode when the host is below the spec thresholds
+    #[default]
+    Auto,
+    Normal,
+    LowResource,
+}
+
+/// Memory and CPU available on the host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostResources {
+    /// Physical memory, if it could be detected
+    pub total_memory_mb: Option<u64>,
+    pub cpu_cores: usize,
+}
+
+impl HostResources {
+    /// Detect the resources of the current host
+    pub fn detect() -> Self {
+        Self {
+            total_memory_mb: detect_total_memory_mb(),
+            cpu_cores: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        }
+    }
+
+    /// Why the host counts as low-spec, or `None` if it does not
+    pub fn low_spec_reason(&self) -> Option<String> {
+        let mut reasons = Vec::new();
+        if let Some(memory) = self.total_memory_mb.filter(|&mb| mb < LOW_RESOURCE_MEMORY_MB) {
+            reasons.push(format!("{} MB memory (< {} MB)", memory, LOW_RESOURCE_MEMORY_MB));
+        }
+        if self.cpu_cores < LOW_RESOURCE_CPU_CORES {
+            reasons.push(format!("{} CPU core(s) (< {})", self.cpu_cores, LOW_RESOURCE_CPU_CORES));
+        }
+
+        if reasons.is_empty() {
+            None
+        } else {
+            Some(reasons.join(", "))
+        }
+    }
+}
+
+/// Resource profile in effect for this process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceProfile {
+    pub mode: ResourceMode,
+    /// Whether degradations are applied
+    pub low_resource: bool,
+    /// Why the profile was selected
+    pub reason: String,
+    pub host: HostResources,
+}
+
+impl ResourceProfile {
+    /// Resolve the requested mode against the host's resources
+    pub fn resolve(mode: ResourceMode, host: HostResources) -> Self {
+        let (low_resource, reason) = match mode {
+            ResourceMode::Normal => (false, "configured".to_string()),
+            ResourceMode::LowResource => (true, "configured".to_string()),
+            ResourceMode::Auto => match host.low_spec_reason() {
+                Some(reason) => (true, format!("detected low-spec host: {}", reason)),
+                None => (false, "host meets the resource thresholds".to_string()),
+            },
+        };
+
+        Self {
+            mode,
+            low_resource,
+            reason,
+            host,
+        }
+    }
+
+    /// The normal profile, without probing the host
+    ///
+    /// Library code uses this when the application never selected a profile.
+    pub fn normal() -> Self {
+        Self {
+            mode: ResourceMode::Normal,
+            low_resource: false,
+            reason: "not configured".to_string(),
+            host: HostResources {
+                total_memory_mb: None,
+                cpu_cores: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            },
+        }
+    }
+
+    /// Stop capturing screenshots while recording and sample mouse moves less often
+    pub fn apply_to_automation(&self, config: &mut AutomationConfig) {
+        if !self.low_resource {
+            return;
+        }
+        config.capture_screenshots = false;
+        config.mouse_move_interval_ms *= LOW_RESOURCE_POLL_MULTIPLIER as u64;
+    }
+
+    /// Check health less often and keep a shorter history
+    pub fn apply_to_monitoring(&self, config: &mut MonitoringConfig) {
+        if !self.low_resource {
+            return;
+        }
+        config.health_check_interval_seconds *= LOW_RESOURCE_POLL_MULTIPLIER as u64;
+        config.enable_predictive_alerts = false;
+        config.max_metrics_entries = config.max_metrics_entries.min(LOW_RESOURCE_HISTORY_ENTRIES);
+        config.max_alert_history = config.max_alert_history.min(LOW_RESOURCE_HISTORY_ENTRIES / 10);
+    }
+
+    /// Keep only warnings and errors and skip performance logging
+    pub fn apply_to_logging(&self, config: &mut LoggingConfig) {
+        if !self.low_resource {
+            return;
+        }
+        if config.log_level.severity() < LogLevel::Warn.severity() {
+            config.log_level = LogLevel::Warn;
+        }
+        config.drop_below_level = true;
+        config.enable_performance_logging = false;
+        config.buffer_size = config.buffer_size.min(LOW_RESOURCE_HISTORY_ENTRIES / 2);
+    }
+
+    /// Compare single-threaded within a smaller memory budget
+    pub fn apply_to_visual_performance(&self, config: &mut PerformanceConfig) {
+        if !self.low_resource {
+            return;
+        }
+        config.enable_parallel_processing = false;
+        config.max_memory_usage_mb = config.max_memory_usage_mb.min(256);
+    }
+
+    /// Skip the stability wait and back off longer between capture retries
+    pub fn apply_to_capture(&self, config: &mut CaptureConfig) {
+        if !self.low_resource {
+            return;
+        }
+        config.enable_stability_check = false;
+        config.retry_delay_ms *= 2;
+    }
+
+    /// Polling interval to use instead of `base`
+    pub fn poll_interval(&self, base: Duration) -> Duration {
+        if self.low_resource {
+            base * LOW_RESOURCE_POLL_MULTIPLIER
+        } else {
+            base
+        }
+    }
+
+    /// Downscale a screenshot kept for review
+    pub fn downscale_screenshot(&self, image: DynamicImage) -> DynamicImage {
+        if !self.low_resource || image.width() <= LOW_RESOURCE_MAX_SCREENSHOT_WIDTH {
+            return image;
+        }
+        let height = (image.height() as u64 * LOW_RESOURCE_MAX_SCREENSHOT_WIDTH as u64 / image.width() as u64).max(1) as u32;
+        image.resize_exact(LOW_RESOURCE_MAX_SCREENSHOT_WIDTH, height, FilterType::Triangle)
+    }
+
+    /// Downscale an encoded screenshot before it is sent for analysis
+    ///
+    /// Returns the PNG to send and the factor that maps its pixels back to
+    /// screen pixels. Screenshots that need no downscaling, or cannot be
+    /// decoded, are returned unchanged with a factor of 1.
+    pub fn downscale_encoded_screenshot(&self, bytes: Vec<u8>) -> (Vec<u8>, f64) {
+        if !self.low_resource {
+            return (bytes, 1.0);
+        }
+        let image = match image::load_from_memory(&bytes) {
+            Ok(image) if image.width() > LOW_RESOURCE_MAX_SCREENSHOT_WIDTH => image,
+            _ => return (bytes, 1.0),
+        };
+
+        let width = image.width();
+        let downscaled = self.downscale_screenshot(image);
+        let mut encoded = Vec::new();
+        match downscaled.write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png) {
+            Ok(()) => (encoded, width as f64 / downscaled.width() as f64),
+            Err(_) => (bytes, 1.0),
+        }
+    }
+}
+
+static RESOURCE_PROFILE: OnceLock<ResourceProfile> = OnceLock::new();
+
+/// Select the process-wide resource profile
+///
+/// Only the first call takes effect; call it at startup before creating the
+/// logger, monitor, recorder or player.
+pub fn init_resource_profile(mode: ResourceMode) -> &'static ResourceProfile {
+    RESOURCE_PROFILE.get_or_init(|| ResourceProfile::resolve(mode, HostResources::detect()))
+}
+
+/// Get the process-wide resource profile, detecting it if not initialized
+pub fn resource_profile() -> &'static ResourceProfile {
+    init_resource_profile(ResourceMode::Auto)
+}
+
+/// The profile selected with `init_resource_profile`, or the normal profile
+///
+/// Unlike `resource_profile` this never probes the host, so library code
+/// embedded in another application behaves normally unless told otherwise.
+pub fn configured_resource_profile() -> ResourceProfile {
+    RESOURCE_PROFILE.get().cloned().unwrap_or_else(ResourceProfile::normal)
+}
+
+fn detect_total_memory_mb() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix("MemTotal:"))
+            .and_then(|value| value.split_whitespace().next())
+            .and_then(|kb| kb.parse::<u64>().ok())
+            .map(|kb| kb / 1024)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("sysctl").args(["-n", "hw.memsize"]).output().ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|bytes| bytes / (1024 * 1024))
+    }
+
+    #[cfg(windows)]
+    {
+        let output = std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "(Get-CimInstance Win32_ComputerSystem).TotalPhysicalMemory",
+            ])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(|bytes| bytes / (1024 * 1024))
+    }
+
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(total_memory_mb: Option<u64>, cpu_cores: usize) -> HostResources {
+        HostResources { total_memory_mb, cpu_cores }
+    }
+
+    #[test]
+    fn test_mode_resolution() {
+        let old_machine = host(Some(2048), 1);
+        let auto = ResourceProfile::resolve(ResourceMode::Auto, old_machine.clone());
+        assert!(auto.low_resource);
+        assert!(auto.reason.contains("2048 MB memory"));
+        assert!(auto.reason.contains("1 CPU core(s)"));
+
+        assert!(!ResourceProfile::resolve(ResourceMode::Normal, old_machine).low_resource);
+        assert!(!ResourceProfile::resolve(ResourceMode::Auto, host(Some(16384), 8)).low_resource);
+        assert!(!ResourceProfile::resolve(ResourceMode::Auto, host(None, 8)).low_resource);
+        assert!(ResourceProfile::resolve(ResourceMode::LowResource, host(Some(16384), 8)).low_resource);
+    }
+
+    #[test]
+    fn test_low_resource_degrades_subsystem_configs() {
+        let profile = ResourceProfile::resolve(ResourceMode::LowResource, host(Some(16384), 8));
+
+        let mut automation = AutomationConfig { capture_screenshots: true, ..AutomationConfig::default() };
+        profile.apply_to_automation(&mut automation);
+        assert!(!automation.capture_screenshots);
+        assert_eq!(automation.mouse_move_interval_ms, 200);
+
+        let mut monitoring = MonitoringConfig::default();
+        profile.apply_to_monitoring(&mut monitoring);
+        assert_eq!(monitoring.health_check_interval_seconds, 120);
+        assert_eq!(monitoring.max_metrics_entries, 1000);
+
+        let mut logging = LoggingConfig::default();
+        profile.apply_to_logging(&mut logging);
+        assert_eq!(logging.log_level, LogLevel::Warn);
+        assert!(logging.drop_below_level);
+
+        assert_eq!(profile.poll_interval(Duration::from_secs(2)), Duration::from_secs(8));
+
+        let normal = ResourceProfile::resolve(ResourceMode::Normal, host(Some(16384), 8));
+        let mut untouched = MonitoringConfig::default();
+        normal.apply_to_monitoring(&mut untouched);
+        assert_eq!(untouched.health_check_interval_seconds, 30);
+    }
+
+    #[test]
+    fn test_screenshots_are_downscaled_in_low_resource_mode() {
+        let profile = ResourceProfile::resolve(ResourceMode::LowResource, host(None, 8));
+
+        let large = profile.downscale_screenshot(DynamicImage::new_rgba8(2560, 1440));
+        assert_eq!((large.width(), large.height()), (1280, 720));
+
+        let small = profile.downscale_screenshot(DynamicImage::new_rgba8(800, 600));
+        assert_eq!((small.width(), small.height()), (800, 600));
+
+        let mut png = Vec::new();
+        DynamicImage::new_rgba8(2560, 1440)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        let (encoded, factor) = profile.downscale_encoded_screenshot(png.clone());
+        assert_eq!(image::load_from_memory(&encoded).unwrap().width(), 1280);
+        assert_eq!(factor, 2.0);
+
+        let normal = ResourceProfile::normal();
+        assert_eq!(normal.downscale_encoded_screenshot(png.clone()), (png, 1.0));
+    }
+}
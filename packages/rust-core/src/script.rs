@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Complete script data structure compatible with Python core
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,6 +131,83 @@ impl ScriptData {
 
         Ok(())
     }
+
+    /// Load a script file for playback
+    ///
+    /// The file is prepared with `prepare_playback_json` first, so editor-only
+    /// action fields reach the player.
+    pub fn load_for_playback(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| crate::AutomationError::ScriptError {
+            message: format!("Failed to read script file '{}': {}", path.display(), e),
+        })?;
+        let mut script_json: serde_json::Value = serde_json::from_str(&content).map_err(|e| crate::AutomationError::ScriptError {
+            message: format!("Failed to parse script file '{}': {}", path.display(), e),
+        })?;
+
+        prepare_playback_json(&mut script_json, path);
+        serde_json::from_value(script_json).map_err(|e| crate::AutomationError::ScriptError {
+            message: format!("Failed to deserialize script file '{}': {}", path.display(), e),
+        })
+    }
+}
+
+/// Keys of visual assertion actions the player reads from `additional_data`
+const VISUAL_ASSERT_KEYS: [&str; 4] = ["config", "regions", "assets", "context"];
+
+/// Prepare script JSON written by the editor for playback
+///
+/// `Action` only keeps `additional_data`, so visual assertion settings and
+/// whole AI Vision Capture actions are copied there. The script's directory
+/// and path are added to `metadata.additional_data` so relative assets
+/// resolve and AI Vision Capture cache updates are saved back to the file.
+pub fn prepare_playback_json(script_json: &mut serde_json::Value, script_path: &Path) {
+    if let Some(actions) = script_json.get_mut("actions").and_then(|v| v.as_array_mut()) {
+        for action in actions.iter_mut() {
+            let action_type = action.get("type").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let is_vision_action = action_type == "ai_vision_capture" && action.get("id").is_some();
+            if action_type != "visual_assert" && !is_vision_action {
+                continue;
+            }
+
+            // The player reads the whole AI Vision Capture action, so copy it before adding to it
+            let vision_action = if is_vision_action { Some(action.clone()) } else { None };
+            if action.get("additional_data").and_then(|v| v.as_object()).is_none() {
+                action["additional_data"] = serde_json::Value::Object(serde_json::Map::new());
+            }
+
+            match vision_action {
+                Some(vision_action) => action["additional_data"]["vision_action"] = vision_action,
+                None => {
+                    // Provide action_id, since Action has no id field
+                    if let Some(action_id) = action.get("id").cloned().filter(|id| id.is_string()) {
+                        action["additional_data"]["action_id"] = action_id;
+                    }
+                    for key in VISUAL_ASSERT_KEYS {
+                        if let Some(value) = action.get(key).cloned() {
+                            action["additional_data"][key] = value;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let script_dir = script_path.parent().map(|dir| dir.to_string_lossy().to_string()).unwrap_or_default();
+    if let Some(metadata) = script_json.get_mut("metadata").and_then(|v| v.as_object_mut()) {
+        let additional_data = metadata
+            .entry("additional_data")
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        if !additional_data.is_object() {
+            *additional_data = serde_json::Value::Object(serde_json::Map::new());
+        }
+        if let Some(additional_data) = additional_data.as_object_mut() {
+            additional_data.insert("script_dir".to_string(), serde_json::Value::String(script_dir));
+            additional_data.insert(
+                "script_path".to_string(),
+                serde_json::Value::String(script_path.to_string_lossy().to_string()),
+            );
+        }
+    }
 }
 
 impl Action {
@@ -0,0 +1,490 @@
+//! Test suite definitions and run results
+//!
+//! A suite groups scripts that are played back together and carries the
+//! suite-level requirements (such as environment gates) checked before any
+//! script in it runs. `SuiteRunner` plays the scripts in order with the
+//! suite's performance assertions, as one AI token budget run.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use crate::{AutomationConfig, AutomationError, Result, ScriptData};
+use crate::ai_budget::TokenBudget;
+use crate::ai_vision_integration::AIVisionProvider;
+use crate::clock::SharedClock;
+use crate::environment_gates::{EnvironmentGate, EnvironmentGateChecker, EnvironmentGateReport, EnvironmentSnapshot};
+use crate::event_channel::{blocking_receiver, event_channel, DEFAULT_EVENT_CAPACITY};
+use crate::logging::{get_logger, CoreType, LogLevel, OperationType};
+use crate::performance_assertions::{AssertionStatus, MetricAssertion, PerformanceAssertionResult};
+use crate::player::{PlaybackEventData, Player};
+
+/// Default wall-clock limit for one script of a suite to finish
+pub const DEFAULT_SCRIPT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Definition of a suite of scripts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuiteDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Paths of the scripts in execution order
+    pub scripts: Vec<String>,
+    /// Gates checked before any script runs
+    #[serde(default)]
+    pub environment_gates: Vec<EnvironmentGate>,
+    /// Metric limits checked while each script plays, in addition to the
+    /// script's own assertions
+    #[serde(default)]
+    pub performance_assertions: Vec<MetricAssertion>,
+}
+
+/// Final status of a script within a suite run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptRunStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Result of a single script within a suite run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptRunResult {
+    pub script_path: String,
+    pub status: ScriptRunStatus,
+    pub reason: Option<String>,
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub performance_assertions: Vec<PerformanceAssertionResult>,
+}
+
+/// Overall status of a suite run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuiteRunStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Result of running a suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuiteRunResult {
+    pub suite_name: String,
+    pub status: SuiteRunStatus,
+    pub skip_reason: Option<String>,
+    pub gate_report: Option<EnvironmentGateReport>,
+    pub script_results: Vec<ScriptRunResult>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+impl SuiteDefinition {
+    /// Load a suite definition from a JSON file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| AutomationError::ScriptError {
+                message: format!("Failed to read suite file '{}': {}", path.as_ref().display(), e),
+            })?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| AutomationError::ScriptError {
+                message: format!("Failed to parse suite file '{}': {}", path.as_ref().display(), e),
+            })
+    }
+
+    /// Check the suite's environment gates against the current host
+    ///
+    /// Returns `Some(result)` with every script marked as skipped when a gate
+    /// fails, or `None` when the suite may run.
+    pub fn preflight(&self) -> Option<SuiteRunResult> {
+        if self.environment_gates.is_empty() {
+            return None;
+        }
+        self.preflight_with_snapshot(EnvironmentSnapshot::collect())
+    }
+
+    /// Check the suite's environment gates against a given snapshot
+    pub fn preflight_with_snapshot(&self, snapshot: EnvironmentSnapshot) -> Option<SuiteRunResult> {
+        let report = EnvironmentGateChecker::check_with_snapshot(&self.environment_gates, snapshot);
+        if report.passed {
+            None
+        } else {
+            Some(SuiteRunResult::skipped(self, report))
+        }
+    }
+}
+
+impl SuiteRunResult {
+    /// Build a result in which every script is skipped because of failed gates
+    pub fn skipped(suite: &SuiteDefinition, gate_report: EnvironmentGateReport) -> Self {
+        let reason = gate_report.skip_reason();
+        let now = Utc::now();
+
+        let script_results = suite
+            .scripts
+            .iter()
+            .map(|script_path| ScriptRunResult {
+                script_path: script_path.clone(),
+                status: ScriptRunStatus::Skipped,
+                reason: reason.clone(),
+                duration_ms: None,
+                performance_assertions: Vec::new(),
+            })
+            .collect();
+
+        Self {
+            suite_name: suite.name.clone(),
+            status: SuiteRunStatus::Skipped,
+            skip_reason: reason,
+            gate_report: Some(gate_report),
+            script_results,
+            started_at: now,
+            finished_at: now,
+        }
+    }
+}
+
+/// What a script's playback reported
+#[derive(Debug, Default)]
+struct ScriptPlayback {
+    completed: bool,
+    reason: String,
+    actions_failed: usize,
+    duration_ms: u64,
+    performance_assertions: Vec<PerformanceAssertionResult>,
+}
+
+/// Plays the scripts of a suite in order
+///
+/// Each script gets its own player carrying the suite's performance
+/// assertions, and its results are copied into the script's run result. The
+/// whole suite is a single run of the token budget, so per-run limits cover
+/// every script together.
+pub struct SuiteRunner {
+    config: AutomationConfig,
+    clock: Option<SharedClock>,
+    ai_provider: Option<Arc<dyn AIVisionProvider>>,
+    token_budget: Option<TokenBudget>,
+    script_timeout: Duration,
+}
+
+impl SuiteRunner {
+    pub fn new(config: AutomationConfig) -> Self {
+        Self {
+            config,
+            clock: None,
+            ai_provider: None,
+            token_budget: None,
+            script_timeout: DEFAULT_SCRIPT_TIMEOUT,
+        }
+    }
+
+    /// Replace the time source of the players
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = Some(clock);
+    }
+
+    /// Provider for dynamic AI Vision Capture steps
+    pub fn set_ai_vision_provider(&mut self, provider: Arc<dyn AIVisionProvider>) {
+        self.ai_provider = Some(provider);
+    }
+
+    /// Budget the suite's AI calls are charged to
+    pub fn set_token_budget(&mut self, budget: TokenBudget) {
+        self.token_budget = Some(budget);
+    }
+
+    /// Wall-clock limit for one script; playback is stopped when exceeded
+    pub fn set_script_timeout(&mut self, timeout: Duration) {
+        self.script_timeout = timeout;
+    }
+
+    /// Load a suite file and run it, resolving script paths against its directory
+    pub fn run_file<P: AsRef<Path>>(&self, path: P) -> Result<SuiteRunResult> {
+        let suite = SuiteDefinition::load_from_file(path.as_ref())?;
+        let base_dir = path.as_ref().parent().unwrap_or_else(|| Path::new(""));
+        Ok(self.run(&suite, base_dir))
+    }
+
+    /// Run every script of `suite`, or skip them all if a gate fails
+    ///
+    /// Relative script paths are resolved against `base_dir`.
+    pub fn run(&self, suite: &SuiteDefinition, base_dir: &Path) -> SuiteRunResult {
+        if let Some(skipped) = suite.preflight() {
+            return skipped;
+        }
+
+        let started_at = Utc::now();
+        // An enclosing run (e.g. a suite of suites) keeps ownership of the budget run
+        let owns_budget_run = match &self.token_budget {
+            Some(budget) if !budget.run_active() => {
+                budget.begin_run(format!("suite_{}_{}", suite.name, started_at.timestamp_millis()));
+                true
+            }
+            _ => false,
+        };
+
+        let script_results: Vec<ScriptRunResult> = suite
+            .scripts
+            .iter()
+            .map(|script_path| self.run_script(suite, script_path, base_dir))
+            .collect();
+
+        if owns_budget_run {
+            if let Some(budget) = &self.token_budget {
+                budget.end_run();
+            }
+        }
+
+        let status = if script_results.iter().all(|result| result.status == ScriptRunStatus::Passed) {
+            SuiteRunStatus::Passed
+        } else {
+            SuiteRunStatus::Failed
+        };
+        let result = SuiteRunResult {
+            suite_name: suite.name.clone(),
+            status,
+            skip_reason: None,
+            gate_report: None,
+            script_results,
+            started_at,
+            finished_at: Utc::now(),
+        };
+        log_suite_result(&result);
+        result
+    }
+
+    fn run_script(&self, suite: &SuiteDefinition, script_path: &str, base_dir: &Path) -> ScriptRunResult {
+        let playback = ScriptData::load_for_playback(&base_dir.join(script_path))
+            .and_then(|script| self.play(script, &suite.performance_assertions));
+
+        let (status, reason, duration_ms, performance_assertions) = match playback {
+            Ok(playback) => {
+                let failed_assertions = playback
+                    .performance_assertions
+                    .iter()
+                    .filter(|result| result.status == AssertionStatus::Failed)
+                    .count();
+                let (status, reason) = if !playback.completed || playback.actions_failed > 0 {
+                    (ScriptRunStatus::Failed, Some(playback.reason))
+                } else if failed_assertions > 0 {
+                    (ScriptRunStatus::Failed, Some(format!("{} performance assertion(s) failed", failed_assertions)))
+                } else {
+                    (ScriptRunStatus::Passed, None)
+                };
+                (status, reason, Some(playback.duration_ms), playback.performance_assertions)
+            }
+            Err(e) => (ScriptRunStatus::Failed, Some(e.to_string()), None, Vec::new()),
+        };
+
+        ScriptRunResult {
+            script_path: script_path.to_string(),
+            status,
+            reason,
+            duration_ms,
+            performance_assertions,
+        }
+    }
+
+    /// Play one script to completion and collect what the player reported
+    fn play(&self, script: ScriptData, assertions: &[MetricAssertion]) -> Result<ScriptPlayback> {
+        let mut player = Player::new(self.config.clone())?;
+        if let Some(clock) = &self.clock {
+            player.set_clock(Arc::clone(clock));
+        }
+        if let Some(provider) = &self.ai_provider {
+            player.set_ai_vision_provider(Arc::clone(provider));
+        }
+        if let Some(budget) = &self.token_budget {
+            player.set_token_budget(budget.clone());
+        }
+        player.set_performance_assertions(assertions.to_vec());
+
+        let (sender, receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
+        player.set_event_sender(sender);
+        player.load_script(script)?;
+        player.start_playback(1.0, 1)?;
+
+        let receiver = blocking_receiver(receiver);
+        let deadline = Instant::now() + self.script_timeout;
+        let mut playback = ScriptPlayback::default();
+        loop {
+            match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(event) => match event.data {
+                    PlaybackEventData::PerformanceAssertions { results } => playback.performance_assertions = results,
+                    PlaybackEventData::Complete { completed, reason, actions_failed, duration_ms, .. } => {
+                        playback.completed = completed;
+                        playback.reason = reason;
+                        playback.actions_failed = actions_failed;
+                        playback.duration_ms = duration_ms;
+                        return Ok(playback);
+                    }
+                    _ => {}
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = player.stop_playback();
+                    return Err(AutomationError::PlaybackError {
+                        message: format!("Script did not finish within {}s", self.script_timeout.as_secs()),
+                    });
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(AutomationError::PlaybackError {
+                        message: "Playback ended without a completion event".to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn log_suite_result(result: &SuiteRunResult) {
+    if let Some(logger) = get_logger() {
+        let failed = result
+            .script_results
+            .iter()
+            .filter(|script| script.status == ScriptRunStatus::Failed)
+            .count();
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("scripts".to_string(), serde_json::json!(result.script_results.len()));
+        metadata.insert("failed".to_string(), serde_json::json!(failed));
+
+        logger.log_operation(
+            if failed == 0 { LogLevel::Info } else { LogLevel::Warn },
+            CoreType::Rust,
+            OperationType::Playback,
+            format!("suite_run_{}", result.started_at.timestamp_millis()),
+            format!("Suite '{}' finished: {} of {} scripts failed", result.suite_name, failed, result.script_results.len()),
+            Some(metadata),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_budget::{AITokenUsage, BudgetLimits, TokenBudgetConfig};
+    use crate::ai_vision_integration::AIVisionAnalysisResponse;
+    use crate::environment_gates::RequiredPermission;
+    use crate::performance_assertions::{MetricKind, ProcessTarget};
+    use crate::script::{AIVisionCaptureAction, Action, ActionType};
+    use crate::test_harness::TestHarness;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn suite(gates: Vec<EnvironmentGate>) -> SuiteDefinition {
+        SuiteDefinition {
+            name: "checkout".to_string(),
+            description: None,
+            scripts: vec!["login.json".to_string(), "pay.json".to_string()],
+            environment_gates: gates,
+            performance_assertions: Vec::new(),
+        }
+    }
+
+    fn snapshot() -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            os: "linux".to_string(),
+            os_version: Some("22.04".to_string()),
+            screen_resolution: Some((1366, 768)),
+            granted_permissions: vec![RequiredPermission::InputControl],
+            captured_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_preflight_passes_without_failed_gates() {
+        let suite = suite(vec![EnvironmentGate::MinScreenResolution { width: 1280, height: 720 }]);
+        assert!(suite.preflight_with_snapshot(snapshot()).is_none());
+        assert!(suite(Vec::new()).preflight().is_none());
+    }
+
+    #[test]
+    fn test_preflight_skips_all_scripts_with_reason() {
+        let suite = suite(vec![EnvironmentGate::MinScreenResolution { width: 1920, height: 1080 }]);
+        let result = suite.preflight_with_snapshot(snapshot()).unwrap();
+
+        assert_eq!(result.status, SuiteRunStatus::Skipped);
+        assert_eq!(result.script_results.len(), 2);
+        assert!(result.script_results.iter().all(|r| r.status == ScriptRunStatus::Skipped));
+        assert!(result.skip_reason.unwrap().contains("1366x768"));
+    }
+
+    /// Script with one dynamic AI Vision Capture step
+    fn vision_script(id: &str) -> ScriptData {
+        let mut vision = AIVisionCaptureAction::new(id.to_string(), 0.0, String::new(), (1920, 1080));
+        vision.is_dynamic = true;
+        vision.dynamic_config.prompt = format!("{} button", id);
+        let mut additional_data = HashMap::new();
+        additional_data.insert("vision_action".to_string(), serde_json::json!(vision));
+
+        let mut script = ScriptData::new("rust", std::env::consts::OS);
+        script.add_action(Action {
+            action_type: ActionType::AiVisionCapture,
+            timestamp: 0.0,
+            x: None,
+            y: None,
+            button: None,
+            key: None,
+            text: None,
+            modifiers: None,
+            additional_data: Some(additional_data),
+        });
+        script
+    }
+
+    #[test]
+    fn test_runner_applies_suite_assertions_and_one_budget_run() {
+        let temp_dir = TempDir::new().unwrap();
+        for id in ["login", "pay"] {
+            let path = temp_dir.path().join(format!("{}.json", id));
+            std::fs::write(path, serde_json::to_string(&vision_script(id)).unwrap()).unwrap();
+        }
+
+        let harness = TestHarness::new();
+        harness.token_budget.set_config(TokenBudgetConfig {
+            per_run: BudgetLimits { max_tokens: Some(1000), max_cost_usd: None },
+            ..Default::default()
+        });
+        harness
+            .ai
+            .push_response(AIVisionAnalysisResponse::success(40, 60, 0.9).with_token_usage(AITokenUsage::new(800, 400)))
+            .push_response(AIVisionAnalysisResponse::success(40, 60, 0.9).with_token_usage(AITokenUsage::new(800, 400)));
+
+        let mut runner = SuiteRunner::new(harness.config.clone());
+        runner.set_clock(harness.clock.shared());
+        runner.set_ai_vision_provider(Arc::new(harness.ai.clone()));
+        runner.set_token_budget(harness.token_budget.clone());
+
+        let mut suite = suite(Vec::new());
+        suite.performance_assertions = vec![MetricAssertion {
+            name: Some("target memory".to_string()),
+            metric: MetricKind::MemoryMb,
+            process: ProcessTarget { name: Some("geniusqa-no-such-process".to_string()), pid: None },
+            max: 512.0,
+            aggregation: Default::default(),
+        }];
+        suite.scripts = vec!["login.json".to_string(), "pay.json".to_string()];
+        let result = runner.run(&suite, temp_dir.path());
+
+        assert_eq!(result.script_results.len(), 2);
+        for script in &result.script_results {
+            assert_eq!(script.performance_assertions.len(), 1);
+            assert_eq!(script.performance_assertions[0].status, AssertionStatus::Inconclusive);
+        }
+        // Both scripts share the suite's run, so the first script used up its budget
+        assert_eq!(harness.ai.call_count(), 1);
+        assert!(!harness.token_budget.run_active());
+    }
+
+    #[test]
+    fn test_suite_deserialization_defaults() {
+        let suite: SuiteDefinition = serde_json::from_str(r#"{"name": "smoke", "scripts": ["a.json"]}"#).unwrap();
+        assert!(suite.environment_gates.is_empty());
+        assert!(suite.performance_assertions.is_empty());
+        assert!(suite.description.is_none());
+    }
+}
@@ -0,0 +1,82 @@
+//! Scripted AI vision provider
+
+use crate::ai_vision_integration::{AIVisionAnalysisRequest, AIVisionAnalysisResponse, AIVisionProvider};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+struct FakeAIState {
+    responses: VecDeque<Result<AIVisionAnalysisResponse, String>>,
+    /// Returned once the scripted responses run out
+    fallback: AIVisionAnalysisResponse,
+    requests: Vec<AIVisionAnalysisRequest>,
+}
+
+/// AI vision provider that answers from a queue of scripted responses
+///
+/// Clones share state, so a test can keep a handle to inspect the requests
+/// after passing the provider to the code under test.
+#[derive(Clone)]
+pub struct FakeAIProvider {
+    state: Arc<Mutex<FakeAIState>>,
+}
+
+impl Default for FakeAIProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeAIProvider {
+    /// A provider that fails every request until responses are queued
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FakeAIState {
+                responses: VecDeque::new(),
+                fallback: AIVisionAnalysisResponse::failure("No scripted AI response".to_string()),
+                requests: Vec::new(),
+            })),
+        }
+    }
+
+    /// Queue a response for the next request
+    pub fn push_response(&self, response: AIVisionAnalysisResponse) -> &Self {
+        self.state.lock().unwrap().responses.push_back(Ok(response));
+        self
+    }
+
+    /// Queue a provider error, e.g. a network failure, for the next request
+    pub fn push_error(&self, message: &str) -> &Self {
+        self.state.lock().unwrap().responses.push_back(Err(message.to_string()));
+        self
+    }
+
+    /// Queue a successful match at `(x, y)`
+    pub fn find_at(&self, x: i32, y: i32, confidence: f64) -> &Self {
+        self.push_response(AIVisionAnalysisResponse::success(x, y, confidence))
+    }
+
+    /// Response used once the queue is empty
+    pub fn set_fallback(&self, response: AIVisionAnalysisResponse) {
+        self.state.lock().unwrap().fallback = response;
+    }
+
+    /// Requests received so far, in order
+    pub fn requests(&self) -> Vec<AIVisionAnalysisRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn call_count(&self) -> usize {
+        self.state.lock().unwrap().requests.len()
+    }
+}
+
+impl AIVisionProvider for FakeAIProvider {
+    fn analyze(&self, request: AIVisionAnalysisRequest) -> Result<AIVisionAnalysisResponse, String> {
+        let mut state = self.state.lock().unwrap();
+        state.requests.push(request);
+        match state.responses.pop_front() {
+            Some(response) => response,
+            None => Ok(state.fallback.clone()),
+        }
+    }
+}
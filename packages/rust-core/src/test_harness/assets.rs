@@ -0,0 +1,166 @@
+//! In-memory visual testing storage
+
+use crate::visual_testing::{FileMetadata, StorageBackend, StorageUsage, VisualError, VisualResult};
+use image::{DynamicImage, ImageFormat};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone)]
+struct StoredAsset {
+    image: DynamicImage,
+    metadata: FileMetadata,
+}
+
+/// Storage backend that keeps baselines and results in memory
+///
+/// Paths follow `LocalFileStorage`: baselines live under `baselines/` and
+/// results under `results/`. Clones share the same store.
+#[derive(Clone, Default)]
+pub struct InMemoryAssetStore {
+    assets: Arc<Mutex<BTreeMap<String, StoredAsset>>>,
+}
+
+impl InMemoryAssetStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Paths of every stored asset, sorted
+    pub fn paths(&self) -> Vec<String> {
+        self.assets.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Backdate an asset, e.g. to exercise result retention
+    pub fn set_modified_time(&self, path: &str, modified_time: u64) {
+        if let Some(asset) = self.assets.lock().unwrap().get_mut(path) {
+            asset.metadata.modified_time = modified_time;
+        }
+    }
+
+    fn save(&self, category: &str, image: &DynamicImage, path: &str) -> VisualResult<String> {
+        let key = format!("{}/{}", category, path);
+
+        let mut bytes = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(|e| VisualError::ImageSaveError {
+                path: key.clone(),
+                reason: e.to_string(),
+            })?;
+
+        let metadata = FileMetadata {
+            size_bytes: bytes.len() as u64,
+            modified_time: now_secs(),
+            checksum: format!("{:x}", Sha256::digest(&bytes)),
+            file_type: "png".to_string(),
+        };
+        self.assets.lock().unwrap().insert(
+            key.clone(),
+            StoredAsset {
+                image: image.clone(),
+                metadata,
+            },
+        );
+        Ok(key)
+    }
+
+    fn load(&self, category: &str, path: &str) -> VisualResult<DynamicImage> {
+        let key = format!("{}/{}", category, path);
+        self.assets
+            .lock()
+            .unwrap()
+            .get(&key)
+            .map(|asset| asset.image.clone())
+            .ok_or_else(|| VisualError::ImageLoadError {
+                path: key,
+                reason: "not found in memory store".to_string(),
+            })
+    }
+}
+
+impl StorageBackend for InMemoryAssetStore {
+    fn save_baseline(&self, image: &DynamicImage, path: &str) -> VisualResult<String> {
+        self.save("baselines", image, path)
+    }
+
+    fn load_baseline(&self, path: &str) -> VisualResult<DynamicImage> {
+        self.load("baselines", path)
+    }
+
+    fn save_result(&self, image: &DynamicImage, path: &str) -> VisualResult<String> {
+        self.save("results", image, path)
+    }
+
+    fn load_result(&self, path: &str) -> VisualResult<DynamicImage> {
+        self.load("results", path)
+    }
+
+    fn cleanup_old_results(&self, retention_days: u32) -> VisualResult<()> {
+        let cutoff = now_secs().saturating_sub(retention_days as u64 * 24 * 60 * 60);
+        self.assets
+            .lock()
+            .unwrap()
+            .retain(|key, asset| !key.starts_with("results/") || asset.metadata.modified_time >= cutoff);
+        Ok(())
+    }
+
+    fn get_storage_usage(&self) -> VisualResult<StorageUsage> {
+        let mut usage = StorageUsage::new();
+        for (key, asset) in self.assets.lock().unwrap().iter() {
+            let category = key.split('/').next().unwrap_or_default();
+            usage.add_file(category, asset.metadata.size_bytes);
+        }
+        Ok(usage)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.assets.lock().unwrap().contains_key(path)
+    }
+
+    fn delete(&self, path: &str) -> VisualResult<()> {
+        // Like a directory delete, a prefix removes everything beneath it
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        self.assets
+            .lock()
+            .unwrap()
+            .retain(|key, _| key != path && !key.starts_with(&prefix));
+        Ok(())
+    }
+
+    fn list_files(&self, directory: &str) -> VisualResult<Vec<String>> {
+        let prefix = format!("{}/", directory.trim_end_matches('/'));
+        let mut files: Vec<String> = self
+            .assets
+            .lock()
+            .unwrap()
+            .keys()
+            .filter_map(|key| key.strip_prefix(&prefix))
+            .map(|rest| rest.split('/').next().unwrap_or(rest).to_string())
+            .collect();
+        files.dedup();
+        Ok(files)
+    }
+
+    fn get_metadata(&self, path: &str) -> VisualResult<FileMetadata> {
+        self.assets
+            .lock()
+            .unwrap()
+            .get(path)
+            .map(|asset| asset.metadata.clone())
+            .ok_or_else(|| VisualError::FileSystemError {
+                operation: "get_file_metadata".to_string(),
+                path: path.to_string(),
+                reason: "not found in memory store".to_string(),
+            })
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
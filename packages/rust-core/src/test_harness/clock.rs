@@ -0,0 +1,46 @@
+//! Virtual time for playback
+
+use crate::clock::{Clock, SharedClock};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Clock that only moves when slept on or advanced
+///
+/// `sleep` returns immediately after advancing the clock, so a script
+/// recorded over minutes replays in milliseconds while the player still sees
+/// the recorded timing. Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct VirtualClock {
+    now: Arc<Mutex<Duration>>,
+}
+
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move time forward, e.g. between recorded inputs
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Virtual time since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+
+    /// Handle to pass to a recorder or player
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Duration {
+        self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
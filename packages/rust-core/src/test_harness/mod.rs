@@ -0,0 +1,487 @@
+//! Deterministic fakes for integration tests
+//!
+//! Enabled with the `test-harness` feature (and always in this crate's own
+//! tests). Downstream crates add it to their dev-dependencies:
+//!
+//! ```toml
+//! rust-automation-core = { path = "../rust-core", features = ["test-harness"] }
+//! ```
+//!
+//! `TestHarness` wires a mock platform, a virtual clock, a scripted AI vision
+//! provider and an in-memory asset store into real recorders and players, so
+//! a recording → validation → playback → report flow runs in milliseconds
+//! without touching the OS, the network or the disk.
+
+pub mod ai;
+pub mod assets;
+pub mod clock;
+pub mod platform;
+
+pub use ai::FakeAIProvider;
+pub use assets::InMemoryAssetStore;
+pub use clock::VirtualClock;
+pub use platform::{MockPlatform, PlatformCall, PlatformOverrideGuard};
+
+use crate::ai_budget::TokenBudget;
+use crate::performance_assertions::PerformanceAssertionResult;
+use crate::player::{PlaybackEvent, PlaybackEventData, Player};
+use crate::recorder::Recorder;
+use crate::resource_profile::ResourceProfile;
+use crate::visual_testing::AssetManager as VisualAssetManager;
+use crate::{AutomationConfig, AutomationError, Result, ScriptData};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::event_channel::{blocking_receiver, event_channel, DEFAULT_EVENT_CAPACITY};
+use std::sync::mpsc::RecvTimeoutError;
+
+/// Wall-clock limit for a harness playback to report completion
+const PLAYBACK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fakes wired together for one test
+///
+/// The mock platform is installed for the creating thread until the harness
+/// is dropped, so create recorders and players on that thread.
+pub struct TestHarness {
+    pub platform: MockPlatform,
+    pub clock: VirtualClock,
+    pub ai: FakeAIProvider,
+    pub assets: InMemoryAssetStore,
+    /// Config passed to recorders and players created by the harness
+    pub config: AutomationConfig,
+    /// Budget that players charge dynamic AI Vision Capture steps to
+    pub token_budget: TokenBudget,
+    /// Profile recorders and players are degraded by, normal by default
+    pub resource_profile: ResourceProfile,
+    _platform_guard: PlatformOverrideGuard,
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestHarness {
+    /// A harness with a 1920x1080 mock screen
+    pub fn new() -> Self {
+        Self::with_platform(MockPlatform::default())
+    }
+
+    pub fn with_platform(platform: MockPlatform) -> Self {
+        let guard = platform.install();
+        Self {
+            platform,
+            clock: VirtualClock::new(),
+            ai: FakeAIProvider::new(),
+            assets: InMemoryAssetStore::new(),
+            config: AutomationConfig::default(),
+            token_budget: TokenBudget::default(),
+            resource_profile: ResourceProfile::normal(),
+            _platform_guard: guard,
+        }
+    }
+
+    /// A recorder on virtual time that records only the `record_*` calls
+    pub fn recorder(&self) -> Result<Recorder> {
+        let mut recorder = Recorder::with_resource_profile(self.config.clone(), &self.resource_profile)?;
+        recorder.set_clock(self.clock.shared());
+        recorder.set_input_capture(false);
+        Ok(recorder)
+    }
+
+    /// A player on virtual time that asks the fake AI provider
+    pub fn player(&self) -> Result<Player> {
+        let mut player = Player::with_resource_profile(self.config.clone(), &self.resource_profile)?;
+        player.set_clock(self.clock.shared());
+        player.set_input_capture(false);
+        player.set_ai_vision_provider(Arc::new(self.ai.clone()));
+        player.set_token_budget(self.token_budget.clone());
+        Ok(player)
+    }
+
+    /// Visual testing asset manager backed by the in-memory store
+    pub fn visual_assets(&self) -> VisualAssetManager {
+        VisualAssetManager::new(Box::new(self.assets.clone()))
+    }
+
+    /// Play `script` to completion and collect what the player reported
+    pub fn play(&self, script: ScriptData, speed: f64, loops: u32) -> Result<PlaybackRun> {
+        let mut player = self.player()?;
+        let (sender, receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
+        player.set_event_sender(sender);
+        player.load_script(script)?;
+        player.start_playback(speed, loops)?;
+
+        let receiver = blocking_receiver(receiver);
+        let deadline = Instant::now() + PLAYBACK_TIMEOUT;
+        let mut run = PlaybackRun::default();
+        loop {
+            match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(event) => {
+                    if run.record(event) {
+                        return Ok(run);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = player.stop_playback();
+                    return Err(AutomationError::PlaybackError {
+                        message: format!("Playback did not complete within {}s", PLAYBACK_TIMEOUT.as_secs()),
+                    });
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(AutomationError::PlaybackError {
+                        message: "Playback ended without a completion event".to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// What the player reported for one harness playback
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackRun {
+    /// Every event the player sent, in order
+    pub events: Vec<PlaybackEvent>,
+    pub completed: bool,
+    pub reason: String,
+    pub actions_executed: usize,
+    pub actions_failed: usize,
+    pub loops_completed: u32,
+    /// Run time on the harness clock
+    pub duration: Duration,
+    pub errors: Vec<String>,
+    pub performance_assertions: Vec<PerformanceAssertionResult>,
+}
+
+impl PlaybackRun {
+    /// Events of one type, e.g. `"progress"` or `"visual_assert_result"`
+    pub fn events_of_type<'a>(&'a self, event_type: &'a str) -> impl Iterator<Item = &'a PlaybackEvent> {
+        self.events.iter().filter(move |event| event.event_type == event_type)
+    }
+
+    /// Record an event, returning true once playback has completed
+    fn record(&mut self, event: PlaybackEvent) -> bool {
+        let finished = match &event.data {
+            PlaybackEventData::Complete {
+                completed,
+                reason,
+                actions_executed,
+                actions_failed,
+                loops_completed,
+                duration_ms,
+                errors,
+                ..
+            } => {
+                self.completed = *completed;
+                self.reason = reason.clone();
+                self.actions_executed = *actions_executed;
+                self.actions_failed = *actions_failed;
+                self.loops_completed = *loops_completed;
+                self.duration = Duration::from_millis(*duration_ms);
+                self.errors = errors.clone().unwrap_or_default();
+                true
+            }
+            PlaybackEventData::PerformanceAssertions { results } => {
+                self.performance_assertions = results.clone();
+                false
+            }
+            _ => false,
+        };
+        self.events.push(event);
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_budget::{AITokenUsage, BudgetLimits, TokenBudgetConfig};
+    use crate::ai_vision_integration::{AIVisionAnalysisRequest, AIVisionAnalysisResponse, AIVisionProvider};
+    use crate::capabilities::{CapabilityMatrix, CaptureMode};
+    use crate::health::CoreType;
+    use crate::resource_profile::{HostResources, ResourceMode};
+    use crate::script::{AIVisionCaptureAction, SearchScope, VisionROI};
+    use base64::{engine::general_purpose, Engine as _};
+    use crate::{Action, ActionType};
+    use std::collections::HashMap;
+    use std::thread;
+    use crate::validation::ScriptValidator;
+    use crate::visual_testing::StorageBackend;
+    use image::DynamicImage;
+
+    #[test]
+    fn test_record_validate_play_flow_runs_on_virtual_time() {
+        let harness = TestHarness::new();
+
+        let mut recorder = harness.recorder().unwrap();
+        recorder.start_recording().unwrap();
+        harness.clock.advance(Duration::from_secs(1));
+        recorder.record_mouse_move(100, 200).unwrap();
+        harness.clock.advance(Duration::from_millis(500));
+        recorder.record_mouse_click(100, 200, "left").unwrap();
+        harness.clock.advance(Duration::from_secs(2));
+        recorder.record_key_type("hello").unwrap();
+        let script = recorder.stop_recording().unwrap();
+
+        assert_eq!(script.actions.len(), 3);
+        assert_eq!(script.metadata.duration, 3.5);
+        assert_eq!(script.metadata.screen_resolution, Some((1920, 1080)));
+
+        let validation = ScriptValidator::new().validate_script(&script).unwrap();
+        assert!(validation.is_compatible, "{:?}", validation.issues);
+
+        let run = harness.play(script, 1.0, 1).unwrap();
+        assert!(run.completed);
+        assert_eq!(run.actions_executed, 3);
+        assert_eq!(run.actions_failed, 0);
+        // The last action is 2.5s after the first, then the 10ms mouse delay
+        assert_eq!(run.duration, Duration::from_millis(2510));
+        assert_eq!(
+            harness.platform.calls(),
+            vec![
+                PlatformCall::MouseMove { x: 100, y: 200 },
+                PlatformCall::MouseClickAt { x: 100, y: 200, button: "left".to_string() },
+                PlatformCall::KeyType { text: "hello".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_click_screenshots_are_written_while_recording() {
+        let mut harness = TestHarness::new();
+        harness.config.capture_screenshot_on_click = true;
+        let dir = tempfile::tempdir().unwrap();
+        let screenshots = dir.path().join("recording_screenshots");
+
+        let mut recorder = harness.recorder().unwrap();
+        recorder.set_screenshot_directory(screenshots.clone());
+        recorder.start_recording().unwrap();
+        recorder.record_mouse_move(100, 200).unwrap();
+        recorder.record_mouse_click(100, 200, "left").unwrap();
+
+        // On disk before the recording is stopped
+        assert!(screenshots.join("screenshot_0001.png").is_file());
+        assert!(harness.platform.calls().contains(&PlatformCall::Screenshot));
+
+        let script = recorder.stop_recording().unwrap();
+        let click_data = script.actions[1].additional_data.as_ref().unwrap();
+        assert_eq!(click_data["screenshot"], "screenshot_0001.png");
+        assert_eq!(recorder.screenshot_count(), 1);
+        assert!(script.actions[0].additional_data.is_none());
+
+        // The matrix advertises click screenshots wherever the recorder can capture the screen
+        let matrix = CapabilityMatrix::current(&[CoreType::Rust]);
+        let rust = matrix.core(&CoreType::Rust).unwrap();
+        assert_eq!(rust.capture_modes[&CaptureMode::OnClick], rust.capture_modes[&CaptureMode::FullScreen]);
+    }
+
+    #[test]
+    fn test_fake_ai_provider_replays_scripted_responses() {
+        let ai = FakeAIProvider::new();
+        ai.find_at(40, 60, 0.9).push_error("connection reset");
+
+        let request = AIVisionAnalysisRequest {
+            screenshot: String::new(),
+            prompt: "Submit button".to_string(),
+            reference_images: Vec::new(),
+            roi: None,
+            search_scope: SearchScope::Global,
+            timeout_ms: 1000,
+        };
+
+        let found = ai.analyze(request.clone()).unwrap();
+        assert_eq!((found.x, found.y), (Some(40), Some(60)));
+        assert_eq!(ai.analyze(request.clone()).unwrap_err(), "connection reset");
+        assert!(!ai.analyze(request).unwrap().success);
+        assert_eq!(ai.call_count(), 3);
+        assert_eq!(ai.requests()[0].prompt, "Submit button");
+    }
+
+    #[test]
+    fn test_dynamic_vision_steps_are_charged_to_the_budget() {
+        let harness = TestHarness::new();
+        harness.token_budget.set_config(TokenBudgetConfig {
+            per_run: BudgetLimits { max_tokens: Some(1000), max_cost_usd: None },
+            ..Default::default()
+        });
+        harness
+            .ai
+            .push_response(AIVisionAnalysisResponse::success(40, 60, 0.9).with_token_usage(AITokenUsage::new(800, 400)));
+
+        let mut script = ScriptData::new("rust", std::env::consts::OS);
+        for (index, id) in ["submit", "cancel"].iter().enumerate() {
+            let mut vision = AIVisionCaptureAction::new(id.to_string(), index as f64, String::new(), (1920, 1080));
+            vision.is_dynamic = true;
+            vision.dynamic_config.prompt = format!("{} button", id);
+            let mut additional_data = HashMap::new();
+            additional_data.insert("vision_action".to_string(), serde_json::json!(vision));
+            script.add_action(Action {
+                action_type: ActionType::AiVisionCapture,
+                timestamp: index as f64,
+                x: None,
+                y: None,
+                button: None,
+                key: None,
+                text: None,
+                modifiers: None,
+                additional_data: Some(additional_data),
+            });
+        }
+
+        let run = harness.play(script, 1.0, 1).unwrap();
+        assert!(run.completed);
+        // The first step used up the run budget, so the second never reached the AI
+        assert_eq!(harness.ai.call_count(), 1);
+        assert_eq!(run.actions_failed, 1);
+        assert_eq!(
+            harness.platform.calls(),
+            vec![
+                PlatformCall::Screenshot,
+                PlatformCall::MouseClickAt { x: 40, y: 60, button: "left".to_string() },
+            ]
+        );
+        let status = harness.token_budget.status();
+        assert_eq!(status.daily_usage.total_tokens(), 1200);
+        assert_eq!(status.alerts.len(), 1);
+        assert!(status.run_id.is_none());
+    }
+
+    #[test]
+    fn test_vision_step_without_a_target_fails_the_action() {
+        let harness = TestHarness::new();
+        harness.ai.push_response(AIVisionAnalysisResponse::failure("Submit button not found"));
+
+        let mut vision = AIVisionCaptureAction::new("submit".to_string(), 0.0, String::new(), (1920, 1080));
+        vision.is_dynamic = true;
+        vision.dynamic_config.prompt = "submit button".to_string();
+        let mut additional_data = HashMap::new();
+        additional_data.insert("vision_action".to_string(), serde_json::json!(vision));
+        let mut script = ScriptData::new("rust", std::env::consts::OS);
+        script.add_action(Action {
+            action_type: ActionType::AiVisionCapture,
+            timestamp: 0.0,
+            x: None,
+            y: None,
+            button: None,
+            key: None,
+            text: None,
+            modifiers: None,
+            additional_data: Some(additional_data),
+        });
+
+        let run = harness.play(script, 1.0, 1).unwrap();
+        assert!(run.completed);
+        assert_eq!(run.actions_failed, 1);
+        assert_eq!(run.errors.len(), 1);
+        assert!(!harness
+            .platform
+            .calls()
+            .iter()
+            .any(|call| matches!(call, PlatformCall::MouseClickAt { .. })));
+    }
+
+    #[test]
+    fn test_low_resource_vision_screenshots_are_downscaled() {
+        let platform = MockPlatform::new(2560, 1440);
+        platform.set_screenshot(DynamicImage::new_rgba8(2560, 1440));
+        let mut harness = TestHarness::with_platform(platform);
+        harness.resource_profile = ResourceProfile::resolve(
+            ResourceMode::LowResource,
+            HostResources { total_memory_mb: None, cpu_cores: 8 },
+        );
+        harness.ai.find_at(100, 50, 0.9);
+
+        let mut vision = AIVisionCaptureAction::new("submit".to_string(), 0.0, String::new(), (2560, 1440));
+        vision.is_dynamic = true;
+        vision.dynamic_config.prompt = "submit button".to_string();
+        vision.dynamic_config.search_scope = SearchScope::Regional;
+        vision.dynamic_config.roi = Some(VisionROI { x: 400, y: 200, width: 800, height: 600 });
+        let mut additional_data = HashMap::new();
+        additional_data.insert("vision_action".to_string(), serde_json::json!(vision));
+        let mut script = ScriptData::new("rust", std::env::consts::OS);
+        script.add_action(Action {
+            action_type: ActionType::AiVisionCapture,
+            timestamp: 0.0,
+            x: None,
+            y: None,
+            button: None,
+            key: None,
+            text: None,
+            modifiers: None,
+            additional_data: Some(additional_data),
+        });
+
+        assert!(harness.play(script, 1.0, 1).unwrap().completed);
+
+        // The AI sees a half-size screenshot and the click lands on the full screen
+        let request = &harness.ai.requests()[0];
+        let screenshot = image::load_from_memory(&general_purpose::STANDARD.decode(&request.screenshot).unwrap()).unwrap();
+        assert_eq!((screenshot.width(), screenshot.height()), (1280, 720));
+        assert_eq!(request.roi, Some(VisionROI { x: 200, y: 100, width: 400, height: 300 }));
+        assert!(harness
+            .platform
+            .calls()
+            .contains(&PlatformCall::MouseClickAt { x: 200, y: 100, button: "left".to_string() }));
+    }
+
+    #[test]
+    fn test_platforms_recreated_on_player_threads_use_the_mock() {
+        let harness = TestHarness::new();
+        let create_platform = crate::platform::platform_creator();
+
+        // Playback recreates its handle on its own thread after a display change
+        thread::spawn(move || create_platform().unwrap().mouse_move(5, 7).unwrap())
+            .join()
+            .unwrap();
+
+        assert_eq!(harness.platform.calls(), vec![PlatformCall::MouseMove { x: 5, y: 7 }]);
+    }
+
+    #[test]
+    fn test_shared_platform_cache_is_isolated_per_harness() {
+        let harness = TestHarness::new();
+        harness.platform.set_screen_capture_granted(false);
+        let mock_cache = crate::platform::platform_cache();
+
+        // Work handed to other threads carries the harness's cache along
+        let cache = Arc::clone(&mock_cache);
+        thread::spawn(move || cache.get().unwrap().mouse_move(3, 4).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(harness.platform.calls(), vec![PlatformCall::MouseMove { x: 3, y: 4 }]);
+
+        let snapshot = crate::environment_gates::EnvironmentSnapshot::collect();
+        assert_eq!(snapshot.screen_resolution, Some((1920, 1080)));
+        assert!(!snapshot.granted_permissions.contains(&crate::environment_gates::RequiredPermission::ScreenCapture));
+
+        // Threads without a mock, and this thread once the harness is gone, never see it
+        let cache = Arc::clone(&mock_cache);
+        let other_thread_isolated = thread::spawn(move || !Arc::ptr_eq(&crate::platform::platform_cache(), &cache))
+            .join()
+            .unwrap();
+        assert!(other_thread_isolated);
+
+        drop(harness);
+        assert!(!Arc::ptr_eq(&crate::platform::platform_cache(), &mock_cache));
+    }
+
+    #[test]
+    fn test_in_memory_store_backs_visual_assets() {
+        let harness = TestHarness::new();
+        let assets = harness.visual_assets();
+
+        let baseline = DynamicImage::new_rgba8(32, 16);
+        assert_eq!(assets.save_with_compression(&baseline, "login.png", true).unwrap(), "baselines/login.png");
+        assets.save_with_compression(&baseline, "login_actual.png", false).unwrap();
+
+        let loaded = assets.load_image("login.png", true).unwrap();
+        assert_eq!((loaded.width(), loaded.height()), (32, 16));
+        assert_eq!(harness.assets.list_files("baselines").unwrap(), vec!["login.png"]);
+        assert_eq!(harness.assets.get_storage_usage().unwrap().file_count, 2);
+
+        harness.assets.set_modified_time("results/login_actual.png", 0);
+        assets.cleanup_all_backends(7).unwrap();
+        assert_eq!(harness.assets.paths(), vec!["baselines/login.png"]);
+    }
+}
@@ -0,0 +1,265 @@
+//! Scriptable platform backend
+
+use crate::display::DisplayInfo;
+use crate::platform::{set_platform_override, PlatformAutomation, PlatformFactory, PlatformOverride};
+use crate::{AutomationError, Result};
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// A call made to the mock platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlatformCall {
+    MouseMove { x: i32, y: i32 },
+    MouseClick { button: String },
+    MouseClickAt { x: i32, y: i32, button: String },
+    MouseDoubleClick { x: i32, y: i32, button: String },
+    MouseDrag { from_x: i32, from_y: i32, to_x: i32, to_y: i32, button: String },
+    MouseScroll { x: i32, y: i32, delta_x: i32, delta_y: i32 },
+    KeyPress { key: String },
+    KeyRelease { key: String },
+    KeyType { text: String },
+    KeyCombination { key: String, modifiers: Vec<String> },
+    Screenshot,
+}
+
+struct MockState {
+    calls: Vec<PlatformCall>,
+    mouse_position: (i32, i32),
+    screen_size: (u32, u32),
+    displays: Option<Vec<DisplayInfo>>,
+    permissions_granted: bool,
+    screen_capture_granted: bool,
+    screenshot: DynamicImage,
+    /// Input calls still to fail
+    failures_remaining: usize,
+}
+
+/// In-memory platform that records input instead of sending it
+///
+/// Clones share state, so a test keeps one handle for assertions while the
+/// recorder or player owns another.
+#[derive(Clone)]
+pub struct MockPlatform {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Default for MockPlatform {
+    fn default() -> Self {
+        Self::new(1920, 1080)
+    }
+}
+
+impl MockPlatform {
+    /// A mock screen of the given size with permissions granted
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                calls: Vec::new(),
+                mouse_position: (0, 0),
+                screen_size: (width, height),
+                displays: None,
+                permissions_granted: true,
+                screen_capture_granted: true,
+                screenshot: DynamicImage::new_rgba8(width, height),
+                failures_remaining: 0,
+            })),
+        }
+    }
+
+    /// Input calls received so far, in order
+    pub fn calls(&self) -> Vec<PlatformCall> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    pub fn clear_calls(&self) {
+        self.state.lock().unwrap().calls.clear();
+    }
+
+    pub fn mouse_position(&self) -> (i32, i32) {
+        self.state.lock().unwrap().mouse_position
+    }
+
+    pub fn set_screen_size(&self, width: u32, height: u32) {
+        self.state.lock().unwrap().screen_size = (width, height);
+    }
+
+    /// Report these displays instead of a single primary screen
+    pub fn set_displays(&self, displays: Vec<DisplayInfo>) {
+        self.state.lock().unwrap().displays = Some(displays);
+    }
+
+    pub fn set_permissions_granted(&self, granted: bool) {
+        self.state.lock().unwrap().permissions_granted = granted;
+    }
+
+    pub fn set_screen_capture_granted(&self, granted: bool) {
+        self.state.lock().unwrap().screen_capture_granted = granted;
+    }
+
+    /// Frame returned by `take_screenshot`
+    pub fn set_screenshot(&self, image: DynamicImage) {
+        self.state.lock().unwrap().screenshot = image;
+    }
+
+    /// Make the next `count` input calls fail with a system error
+    pub fn fail_next_calls(&self, count: usize) {
+        self.state.lock().unwrap().failures_remaining = count;
+    }
+
+    /// Use this mock for every platform created on the current thread
+    ///
+    /// Recorders and players create their platform when constructed, so
+    /// install the mock before creating them. `platform_cache` and
+    /// `shared_platform` on this thread return a cache of the mock, leaving
+    /// the process-wide cache alone. The previous platform is restored when
+    /// the guard is dropped.
+    pub fn install(&self) -> PlatformOverrideGuard {
+        let mock = self.clone();
+        let factory: PlatformFactory = Arc::new(move || Box::new(mock.clone()) as Box<dyn PlatformAutomation>);
+        let previous = set_platform_override(Some(PlatformOverride::new(factory)));
+        PlatformOverrideGuard {
+            previous,
+            _not_send: PhantomData,
+        }
+    }
+
+    fn record(&self, call: PlatformCall) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.failures_remaining > 0 {
+            state.failures_remaining -= 1;
+            return Err(AutomationError::SystemError {
+                message: format!("Injected failure for {:?}", call),
+            });
+        }
+
+        match &call {
+            PlatformCall::MouseMove { x, y }
+            | PlatformCall::MouseClickAt { x, y, .. }
+            | PlatformCall::MouseDoubleClick { x, y, .. }
+            | PlatformCall::MouseScroll { x, y, .. } => state.mouse_position = (*x, *y),
+            PlatformCall::MouseDrag { to_x, to_y, .. } => state.mouse_position = (*to_x, *to_y),
+            _ => {}
+        }
+        state.calls.push(call);
+        Ok(())
+    }
+}
+
+impl PlatformAutomation for MockPlatform {
+    fn initialize(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn check_permissions(&self) -> Result<bool> {
+        Ok(self.state.lock().unwrap().permissions_granted)
+    }
+
+    fn request_permissions(&self) -> Result<bool> {
+        self.check_permissions()
+    }
+
+    fn check_screen_capture_permission(&self) -> Result<bool> {
+        Ok(self.state.lock().unwrap().screen_capture_granted)
+    }
+
+    fn mouse_move(&self, x: i32, y: i32) -> Result<()> {
+        self.record(PlatformCall::MouseMove { x, y })
+    }
+
+    fn mouse_click(&self, button: &str) -> Result<()> {
+        self.record(PlatformCall::MouseClick { button: button.to_string() })
+    }
+
+    fn mouse_click_at(&self, x: i32, y: i32, button: &str) -> Result<()> {
+        self.record(PlatformCall::MouseClickAt { x, y, button: button.to_string() })
+    }
+
+    fn mouse_double_click(&self, x: i32, y: i32, button: &str) -> Result<()> {
+        self.record(PlatformCall::MouseDoubleClick { x, y, button: button.to_string() })
+    }
+
+    fn mouse_drag(&self, from_x: i32, from_y: i32, to_x: i32, to_y: i32, button: &str) -> Result<()> {
+        self.record(PlatformCall::MouseDrag {
+            from_x,
+            from_y,
+            to_x,
+            to_y,
+            button: button.to_string(),
+        })
+    }
+
+    fn mouse_scroll(&self, x: i32, y: i32, delta_x: i32, delta_y: i32) -> Result<()> {
+        self.record(PlatformCall::MouseScroll { x, y, delta_x, delta_y })
+    }
+
+    fn key_press(&self, key: &str) -> Result<()> {
+        self.record(PlatformCall::KeyPress { key: key.to_string() })
+    }
+
+    fn key_release(&self, key: &str) -> Result<()> {
+        self.record(PlatformCall::KeyRelease { key: key.to_string() })
+    }
+
+    fn key_type(&self, text: &str) -> Result<()> {
+        self.record(PlatformCall::KeyType { text: text.to_string() })
+    }
+
+    fn key_combination(&self, key: &str, modifiers: &[String]) -> Result<()> {
+        self.record(PlatformCall::KeyCombination {
+            key: key.to_string(),
+            modifiers: modifiers.to_vec(),
+        })
+    }
+
+    fn get_mouse_position(&self) -> Result<(i32, i32)> {
+        Ok(self.mouse_position())
+    }
+
+    fn get_screen_size(&self) -> Result<(u32, u32)> {
+        Ok(self.state.lock().unwrap().screen_size)
+    }
+
+    fn get_displays(&self) -> Result<Vec<DisplayInfo>> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .displays
+            .clone()
+            .unwrap_or_else(|| vec![DisplayInfo::primary(state.screen_size.0, state.screen_size.1)]))
+    }
+
+    fn take_screenshot(&self) -> Result<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(PlatformCall::Screenshot);
+
+        let mut bytes = Vec::new();
+        state
+            .screenshot
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(|e| AutomationError::SystemError {
+                message: format!("Failed to encode mock screenshot: {}", e),
+            })?;
+        Ok(bytes)
+    }
+
+    /// The host OS, so recorded scripts validate as on the real platform
+    fn platform_name(&self) -> &'static str {
+        std::env::consts::OS
+    }
+}
+
+/// Restores the previous platform when dropped
+///
+/// The override is per thread, so the guard cannot leave the thread that
+/// installed it.
+pub struct PlatformOverrideGuard {
+    previous: Option<PlatformOverride>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for PlatformOverrideGuard {
+    fn drop(&mut self) {
+        set_platform_override(self.previous.take());
+    }
+}
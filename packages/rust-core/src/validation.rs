@@ -10,6 +10,15 @@ use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use crate::{AutomationError, Result};
 use crate::script::{ScriptData, ScriptMetadata, Action, ActionType};
+use crate::validation_rules::{
+    default_rule_versions_path, default_rules_directory, org_trusted_keys_path, RuleVersionStore, SignedRuleFile,
+    TrustedRuleKeys, ValidationRuleSet,
+};
+use crate::capabilities::CapabilityMatrix;
+use crate::health::CoreType as HealthCoreType;
+use crate::logging::{CoreType, OperationType, LogLevel, get_logger};
+use std::path::Path;
+use std::sync::{OnceLock, RwLock, RwLockReadGuard};
 
 /// JSON schema for script file validation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +34,7 @@ pub struct ScriptSchema {
 pub struct ScriptValidator {
     schema: ScriptSchema,
     supported_versions: Vec<String>,
+    rule_sets: Vec<ValidationRuleSet>,
 }
 
 /// Migration utility for script format upgrades
@@ -94,9 +104,154 @@ impl ScriptValidator {
         Self {
             schema,
             supported_versions: vec!["1.0".to_string()],
+            rule_sets: Vec::new(),
         }
     }
 
+    /// Add a verified rule set, replacing an older or equal version with the same id
+    ///
+    /// Returns an error if a newer version of the rule set is already loaded, so
+    /// reloading an unchanged rule directory succeeds.
+    pub fn add_rule_set(&mut self, rule_set: ValidationRuleSet) -> Result<()> {
+        if let Some(existing) = self.rule_sets.iter_mut().find(|r| r.id == rule_set.id) {
+            if existing.version > rule_set.version {
+                return Err(AutomationError::ConfigError {
+                    message: format!(
+                        "Rule set '{}' v{} is older than loaded v{}",
+                        rule_set.id, rule_set.version, existing.version
+                    ),
+                });
+            }
+            *existing = rule_set;
+        } else {
+            self.rule_sets.push(rule_set);
+        }
+        Ok(())
+    }
+
+    /// Verify and load a signed rule file
+    pub fn load_signed_rule_file<P: AsRef<Path>>(&mut self, path: P, keys: &TrustedRuleKeys) -> Result<()> {
+        self.load_checked_rule_file(path.as_ref(), keys, None)
+    }
+
+    fn load_checked_rule_file(
+        &mut self,
+        path: &Path,
+        keys: &TrustedRuleKeys,
+        versions: Option<&RuleVersionStore>,
+    ) -> Result<()> {
+        let rule_set = SignedRuleFile::load_from_file(path)?.verify(keys)?;
+        if let Some(versions) = versions {
+            versions.check(&rule_set)?;
+        }
+        self.add_rule_set(rule_set)
+    }
+
+    /// Load every signed rule file (`*.json`) in a directory
+    ///
+    /// Files that fail verification are skipped and logged so that a single bad
+    /// file cannot disable the rest. Returns the number of rule sets loaded.
+    pub fn load_rule_directory<P: AsRef<Path>>(&mut self, dir: P, keys: &TrustedRuleKeys) -> Result<usize> {
+        self.load_checked_rule_directory(dir.as_ref(), keys, None)
+    }
+
+    fn load_checked_rule_directory(
+        &mut self,
+        dir: &Path,
+        keys: &TrustedRuleKeys,
+        versions: Option<&RuleVersionStore>,
+    ) -> Result<usize> {
+        let entries = std::fs::read_dir(dir)?;
+        let mut loaded = 0;
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+
+            match self.load_checked_rule_file(&path, keys, versions) {
+                Ok(()) => loaded += 1,
+                Err(e) => log_rule_warning(format!("Skipping rule file {}: {}", path.display(), e)),
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Replace the loaded rule sets with the signed rule files in a directory
+    ///
+    /// Rule sets whose file was removed are dropped. Rule sets older than the
+    /// highest version recorded in `versions` are rejected, and newly accepted
+    /// versions are recorded and saved. Returns the number of rule sets loaded.
+    pub fn reload_rule_directory<P: AsRef<Path>>(
+        &mut self,
+        dir: P,
+        keys: &TrustedRuleKeys,
+        versions: &mut RuleVersionStore,
+    ) -> Result<usize> {
+        self.rule_sets.clear();
+        if !dir.as_ref().is_dir() {
+            return Ok(0);
+        }
+
+        let loaded = self.load_checked_rule_directory(dir.as_ref(), keys, Some(versions))?;
+
+        let mut changed = false;
+        for rule_set in &self.rule_sets {
+            changed |= versions.record(rule_set);
+        }
+        if changed {
+            if let Err(e) = versions.save() {
+                log_rule_warning(format!("Failed to save rule versions: {}", e));
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Create a validator with the signed rule files from the default rules directory
+    ///
+    /// Rule files must be signed by the compiled-in release key or by an
+    /// organization key from `org_trusted_keys_path()`, which only
+    /// administrators can write. Without rule files the validator falls back to
+    /// the compiled-in rules only.
+    pub fn with_default_rules() -> Self {
+        let mut validator = Self::new();
+        validator.reload_default_rules();
+        validator
+    }
+
+    /// Reload the signed rule files from the default rules directory
+    ///
+    /// Rule sets are checked against the versions in
+    /// `default_rule_versions_path()`. If that file cannot be read, only the
+    /// compiled-in rules are used. Returns the number of rule sets loaded.
+    pub fn reload_default_rules(&mut self) -> usize {
+        let mut versions = match RuleVersionStore::load(default_rule_versions_path()) {
+            Ok(versions) => versions,
+            Err(e) => {
+                log_rule_warning(format!("Ignoring rule files, rule versions are unreadable: {}", e));
+                self.rule_sets.clear();
+                return 0;
+            }
+        };
+
+        self.reload_rule_directory(default_rules_directory(), &default_trusted_keys(), &mut versions)
+            .unwrap_or(0)
+    }
+
+    /// Get the loaded rule sets
+    pub fn rule_sets(&self) -> &[ValidationRuleSet] {
+        &self.rule_sets
+    }
+
+    /// Check whether an action type is known to the schema or allowed by a rule set
+    fn is_known_action_type(&self, action_type: &str) -> bool {
+        self.schema.action_types.iter().any(|t| t == action_type)
+            || self.rule_sets.iter().any(|r| r.allowed_action_types().any(|t| t == action_type))
+    }
+
     /// Validate a script file for cross-core compatibility
     pub fn validate_script(&self, script: &ScriptData) -> Result<CompatibilityResult> {
         let mut issues = Vec::new();
@@ -118,6 +273,11 @@ impl ScriptValidator {
         // Validate actions
         self.validate_actions(&script.actions, &mut issues, &mut warnings)?;
 
+        // Apply rules from loaded rule sets
+        for rule_set in &self.rule_sets {
+            rule_set.apply(script, &mut issues);
+        }
+
         // Check action count consistency
         if script.metadata.action_count != script.actions.len() {
             issues.push(CompatibilityIssue {
@@ -232,9 +392,10 @@ impl ScriptValidator {
                 ActionType::Wait => "wait",
                 ActionType::Custom => "custom",
                 ActionType::AiVisionCapture => "ai_vision_capture",
+                ActionType::VisualAssert => "visual_assert",
             };
 
-            if !self.schema.action_types.contains(&action_type_str.to_string()) {
+            if !self.is_known_action_type(action_type_str) {
                 warnings.push(format!("Unknown action type: {}", action_type_str));
             }
 
@@ -312,6 +473,15 @@ impl ScriptValidator {
         self.validate_script(&script)
     }
 
+    /// Validate a script and warn about steps the given core cannot execute
+    /// on the current platform
+    pub fn validate_script_for_core(&self, script: &ScriptData, core: &HealthCoreType) -> Result<CompatibilityResult> {
+        let mut result = self.validate_script(script)?;
+        let matrix = CapabilityMatrix::current(&[core.clone()]);
+        result.issues.extend(matrix.check_script(script, core));
+        Ok(result)
+    }
+
     /// Check if a script is compatible with a specific core type
     pub fn is_compatible_with_core(&self, script: &ScriptData, _core_type: &str) -> Result<bool> {
         let result = self.validate_script(script)?;
@@ -511,6 +681,56 @@ impl CompatibilityTester {
     }
 }
 
+/// Release key plus any organization keys an administrator has installed
+fn default_trusted_keys() -> TrustedRuleKeys {
+    let mut keys = TrustedRuleKeys::with_release_key();
+    let org_keys_path = org_trusted_keys_path();
+    if !org_keys_path.exists() {
+        return keys;
+    }
+
+    match TrustedRuleKeys::load_from_file(&org_keys_path) {
+        Ok(org_keys) => keys.merge(org_keys),
+        Err(e) => log_rule_warning(format!("Ignoring organization rule keys {}: {}", org_keys_path.display(), e)),
+    }
+    keys
+}
+
+fn log_rule_warning(message: String) {
+    if let Some(logger) = get_logger() {
+        logger.log_operation(
+            LogLevel::Warn,
+            CoreType::Rust,
+            OperationType::Configuration,
+            "load_validation_rules".to_string(),
+            message,
+            None,
+        );
+    }
+}
+
+static DEFAULT_VALIDATOR: OnceLock<RwLock<ScriptValidator>> = OnceLock::new();
+
+/// Validator with the default signed rules, shared by playback entry points
+///
+/// Rules are loaded on first use; call `reload_default_validator` to pick up
+/// rule files added later.
+pub fn default_validator() -> RwLockReadGuard<'static, ScriptValidator> {
+    DEFAULT_VALIDATOR
+        .get_or_init(|| RwLock::new(ScriptValidator::with_default_rules()))
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reload the shared validator's rule files, returning the number loaded
+pub fn reload_default_validator() -> usize {
+    DEFAULT_VALIDATOR
+        .get_or_init(|| RwLock::new(ScriptValidator::new()))
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .reload_default_rules()
+}
+
 impl Default for ScriptValidator {
     fn default() -> Self {
         Self::new()
@@ -587,4 +807,37 @@ mod tests {
         assert!(result.is_compatible);
         assert!(!result.warnings.is_empty()); // Should warn about core type mismatch
     }
+
+    #[test]
+    fn test_rule_set_versioning_and_allowed_action_types() {
+        use crate::validation_rules::{ValidationRule, RuleCondition};
+
+        let rule_set = |version: u32| ValidationRuleSet {
+            id: "org".to_string(),
+            version,
+            description: None,
+            issued_at: chrono::Utc::now(),
+            expires_at: None,
+            rules: vec![ValidationRule {
+                id: "ALLOW_VISUAL_ASSERT".to_string(),
+                severity: IssueSeverity::Info,
+                message: "visual_assert is supported".to_string(),
+                suggestion: None,
+                condition: RuleCondition::AllowActionType { action_type: "visual_assert".to_string() },
+            }],
+        };
+
+        let mut validator = ScriptValidator::new();
+        assert!(!validator.is_known_action_type("visual_assert"));
+
+        validator.add_rule_set(rule_set(2)).unwrap();
+        assert!(validator.is_known_action_type("visual_assert"));
+
+        // Older versions must not replace a loaded rule set; reloading the same version is fine
+        assert!(validator.add_rule_set(rule_set(1)).is_err());
+        assert!(validator.add_rule_set(rule_set(2)).is_ok());
+        assert!(validator.add_rule_set(rule_set(3)).is_ok());
+        assert_eq!(validator.rule_sets().len(), 1);
+        assert_eq!(validator.rule_sets()[0].version, 3);
+    }
 }
@@ -0,0 +1,546 @@
+//! Signed validation rule sets for `ScriptValidator`
+//!
+//! Rule sets let new compatibility rules roll out without a full release. They
+//! are distributed as signed JSON rule files, either shipped with app updates or
+//! provided by an organization, and are only applied after the Ed25519
+//! signature has been verified against a trusted key.
+//!
+//! A rule file looks like:
+//!
+//! ```json
+//! {
+//!   "key_id": "geniusqa-release-2024",
+//!   "payload": "<base64 encoded ValidationRuleSet JSON>",
+//!   "signature": "<base64 encoded Ed25519 signature of the decoded payload>"
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use base64::{Engine as _, engine::general_purpose};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use crate::{AutomationError, Result};
+use crate::script::{ScriptData, Action, ActionType};
+use crate::validation::{CompatibilityIssue, IssueSeverity};
+
+/// Signed envelope around a serialized rule set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedRuleFile {
+    pub key_id: String,
+    pub payload: String,
+    pub signature: String,
+}
+
+/// A versioned set of validation rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationRuleSet {
+    pub id: String,
+    pub version: u32,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub issued_at: DateTime<Utc>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    pub rules: Vec<ValidationRule>,
+}
+
+/// A single declarative validation rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationRule {
+    pub id: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+    #[serde(default)]
+    pub suggestion: Option<String>,
+    #[serde(flatten)]
+    pub condition: RuleCondition,
+}
+
+/// Condition checked by a validation rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// Flag every action of a deprecated type
+    DeprecatedActionType { action_type: String },
+    /// Flag an action type on the listed platforms and/or core types
+    UnsupportedActionType {
+        action_type: String,
+        #[serde(default)]
+        platforms: Vec<String>,
+        #[serde(default)]
+        core_types: Vec<String>,
+    },
+    /// Require a key in `additional_data` for an action type
+    RequiredActionData { action_type: String, key: String },
+    /// Flag scripts with more actions than allowed
+    MaxActionCount { max: usize },
+    /// Accept an action type that the built-in schema does not know yet
+    AllowActionType { action_type: String },
+}
+
+/// Id of the key that signs rule files shipped with GeniusQA releases
+pub const RELEASE_RULE_KEY_ID: &str = "geniusqa-release-2024";
+
+/// Base64 Ed25519 public key for `RELEASE_RULE_KEY_ID`
+///
+/// Compiled in so that a user-writable file cannot change which keys are trusted.
+pub const RELEASE_RULE_PUBLIC_KEY: &str = "Pd/lnGssfFqbgxGRXzgrxIeDauh6NEsHzImQf7xjJFk=";
+
+/// Public keys trusted to sign rule files, indexed by key id
+#[derive(Debug, Clone, Default)]
+pub struct TrustedRuleKeys {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl TrustedRuleKeys {
+    /// Create an empty key store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a key store trusting only the compiled-in release key
+    pub fn with_release_key() -> Self {
+        let mut keys = Self::new();
+        keys.add_key(RELEASE_RULE_KEY_ID, RELEASE_RULE_PUBLIC_KEY)
+            .expect("compiled-in release rule key is valid");
+        keys
+    }
+
+    /// Trust every key from another store, keeping existing keys on id clashes
+    pub fn merge(&mut self, other: TrustedRuleKeys) {
+        for (key_id, key) in other.keys {
+            self.keys.entry(key_id).or_insert(key);
+        }
+    }
+
+    /// Trust a base64 encoded Ed25519 public key under the given id
+    pub fn add_key(&mut self, key_id: &str, public_key_base64: &str) -> Result<()> {
+        let bytes = general_purpose::STANDARD
+            .decode(public_key_base64.trim())
+            .map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid base64 for rule key '{}': {}", key_id, e),
+            })?;
+
+        let bytes: [u8; 32] = bytes.as_slice().try_into()
+            .map_err(|_| AutomationError::ConfigError {
+                message: format!("Rule key '{}' must be 32 bytes, got {}", key_id, bytes.len()),
+            })?;
+
+        let key = VerifyingKey::from_bytes(&bytes)
+            .map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid Ed25519 public key '{}': {}", key_id, e),
+            })?;
+
+        self.keys.insert(key_id.to_string(), key);
+        Ok(())
+    }
+
+    /// Load trusted keys from a JSON file of the form `{ "key_id": "base64 public key" }`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        let entries: HashMap<String, String> = serde_json::from_str(&content)?;
+
+        let mut keys = Self::new();
+        for (key_id, public_key) in entries {
+            keys.add_key(&key_id, &public_key)?;
+        }
+        Ok(keys)
+    }
+
+    /// Check whether a key id is trusted
+    pub fn contains(&self, key_id: &str) -> bool {
+        self.keys.contains_key(key_id)
+    }
+
+    /// Number of trusted keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no keys are trusted
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Highest version of each rule set accepted so far
+///
+/// Persisted so that an older, still validly signed rule file cannot roll a
+/// rule set back after a restart.
+#[derive(Debug, Clone, Default)]
+pub struct RuleVersionStore {
+    path: Option<PathBuf>,
+    versions: HashMap<String, u32>,
+}
+
+impl RuleVersionStore {
+    /// Create a store that is not saved to disk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the store from `path`, starting empty if the file does not exist
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let versions = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid rule version file '{}': {}", path.display(), e),
+            })?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            versions,
+        })
+    }
+
+    /// Write the store back to the file it was loaded from
+    pub fn save(&self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.versions)?)?;
+        Ok(())
+    }
+
+    /// Highest accepted version of a rule set, if any was accepted
+    pub fn highest_version(&self, rule_set_id: &str) -> Option<u32> {
+        self.versions.get(rule_set_id).copied()
+    }
+
+    /// Reject a rule set older than the highest accepted version
+    pub fn check(&self, rule_set: &ValidationRuleSet) -> Result<()> {
+        match self.highest_version(&rule_set.id) {
+            Some(highest) if rule_set.version < highest => Err(AutomationError::ConfigError {
+                message: format!(
+                    "Rule set '{}' v{} is older than previously accepted v{}",
+                    rule_set.id, rule_set.version, highest
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record an accepted rule set, returning whether the store changed
+    pub fn record(&mut self, rule_set: &ValidationRuleSet) -> bool {
+        match self.highest_version(&rule_set.id) {
+            Some(highest) if highest >= rule_set.version => false,
+            _ => {
+                self.versions.insert(rule_set.id.clone(), rule_set.version);
+                true
+            }
+        }
+    }
+}
+
+impl SignedRuleFile {
+    /// Load a signed rule file from disk without verifying it
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())?;
+        serde_json::from_str(&content)
+            .map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid rule file '{}': {}", path.as_ref().display(), e),
+            })
+    }
+
+    /// Verify the signature and decode the rule set
+    pub fn verify(&self, keys: &TrustedRuleKeys) -> Result<ValidationRuleSet> {
+        let key = keys.keys.get(&self.key_id)
+            .ok_or_else(|| AutomationError::ConfigError {
+                message: format!("Rule file signed with untrusted key '{}'", self.key_id),
+            })?;
+
+        let payload = general_purpose::STANDARD
+            .decode(self.payload.trim())
+            .map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid base64 rule payload: {}", e),
+            })?;
+
+        let signature_bytes = general_purpose::STANDARD
+            .decode(self.signature.trim())
+            .map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid base64 rule signature: {}", e),
+            })?;
+
+        let signature_bytes: [u8; 64] = signature_bytes.as_slice().try_into()
+            .map_err(|_| AutomationError::ConfigError {
+                message: format!("Rule signature must be 64 bytes, got {}", signature_bytes.len()),
+            })?;
+
+        key.verify(&payload, &Signature::from_bytes(&signature_bytes))
+            .map_err(|_| AutomationError::ConfigError {
+                message: format!("Rule file signature verification failed for key '{}'", self.key_id),
+            })?;
+
+        let rule_set: ValidationRuleSet = serde_json::from_slice(&payload)
+            .map_err(|e| AutomationError::ConfigError {
+                message: format!("Invalid rule set payload: {}", e),
+            })?;
+
+        if let Some(expires_at) = rule_set.expires_at {
+            if expires_at < Utc::now() {
+                return Err(AutomationError::ConfigError {
+                    message: format!("Rule set '{}' v{} expired at {}", rule_set.id, rule_set.version, expires_at),
+                });
+            }
+        }
+
+        Ok(rule_set)
+    }
+}
+
+/// Default directory for rule files
+pub fn default_rules_directory() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("GeniusQA")
+        .join("validation_rules")
+}
+
+/// File recording the highest accepted version of each rule set
+pub fn default_rule_versions_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("GeniusQA")
+        .join("rule_versions.json")
+}
+
+/// Organization key file, in a location only administrators can write
+///
+/// Organizations deploying their own rule files list their signing keys here,
+/// in the same `{ "key_id": "base64 public key" }` form as `load_from_file`.
+pub fn org_trusted_keys_path() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let base = std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+        .join("GeniusQA");
+    #[cfg(target_os = "macos")]
+    let base = PathBuf::from("/Library/Application Support/GeniusQA");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let base = PathBuf::from("/etc/geniusqa");
+
+    base.join("trusted_rule_keys.json")
+}
+
+/// Snake case name of an action type as used in script files
+pub(crate) fn action_type_name(action_type: &ActionType) -> String {
+    serde_json::to_value(action_type)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_else(|| format!("{:?}", action_type).to_lowercase())
+}
+
+impl ValidationRuleSet {
+    /// Action types this rule set allows in addition to the built-in schema
+    pub fn allowed_action_types(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().filter_map(|rule| match &rule.condition {
+            RuleCondition::AllowActionType { action_type } => Some(action_type.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Apply this rule set to a script, appending any issues found
+    pub fn apply(&self, script: &ScriptData, issues: &mut Vec<CompatibilityIssue>) {
+        for rule in &self.rules {
+            match &rule.condition {
+                RuleCondition::DeprecatedActionType { action_type } => {
+                    self.for_each_action(script, action_type, |index, _| {
+                        issues.push(rule.issue(format!("actions[{}]", index)));
+                    });
+                }
+                RuleCondition::UnsupportedActionType { action_type, platforms, core_types } => {
+                    let platform_match = platforms.is_empty()
+                        || platforms.iter().any(|p| p.eq_ignore_ascii_case(&script.metadata.platform));
+                    let core_match = core_types.is_empty()
+                        || core_types.iter().any(|c| c.eq_ignore_ascii_case(&script.metadata.core_type));
+
+                    if platform_match && core_match {
+                        self.for_each_action(script, action_type, |index, _| {
+                            issues.push(rule.issue(format!("actions[{}]", index)));
+                        });
+                    }
+                }
+                RuleCondition::RequiredActionData { action_type, key } => {
+                    self.for_each_action(script, action_type, |index, action| {
+                        let has_key = action.additional_data
+                            .as_ref()
+                            .map(|data| data.contains_key(key))
+                            .unwrap_or(false);
+                        if !has_key {
+                            issues.push(rule.issue(format!("actions[{}].additional_data.{}", index, key)));
+                        }
+                    });
+                }
+                RuleCondition::MaxActionCount { max } => {
+                    if script.actions.len() > *max {
+                        issues.push(rule.issue("actions".to_string()));
+                    }
+                }
+                RuleCondition::AllowActionType { .. } => {}
+            }
+        }
+    }
+
+    fn for_each_action<F: FnMut(usize, &Action)>(&self, script: &ScriptData, action_type: &str, mut f: F) {
+        for (index, action) in script.actions.iter().enumerate() {
+            if action_type_name(&action.action_type) == action_type {
+                f(index, action);
+            }
+        }
+    }
+}
+
+impl ValidationRule {
+    fn issue(&self, field: String) -> CompatibilityIssue {
+        CompatibilityIssue {
+            severity: self.severity.clone(),
+            field,
+            message: format!("[{}] {}", self.id, self.message),
+            suggestion: self.suggestion.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn trusted_keys() -> TrustedRuleKeys {
+        let mut keys = TrustedRuleKeys::new();
+        let public = general_purpose::STANDARD.encode(signing_key().verifying_key().to_bytes());
+        keys.add_key("test", &public).unwrap();
+        keys
+    }
+
+    #[test]
+    fn test_release_key_is_compiled_in() {
+        let mut keys = TrustedRuleKeys::with_release_key();
+        assert!(keys.contains(RELEASE_RULE_KEY_ID));
+
+        keys.merge(trusted_keys());
+        assert_eq!(keys.len(), 2);
+    }
+
+    fn sign(rule_set_json: &str) -> SignedRuleFile {
+        let signature = signing_key().sign(rule_set_json.as_bytes());
+        SignedRuleFile {
+            key_id: "test".to_string(),
+            payload: general_purpose::STANDARD.encode(rule_set_json),
+            signature: general_purpose::STANDARD.encode(signature.to_bytes()),
+        }
+    }
+
+    const RULE_SET: &str = r#"{
+        "id": "compat",
+        "version": 2,
+        "issued_at": "2024-01-01T00:00:00Z",
+        "rules": [
+            {
+                "id": "R001",
+                "kind": "unsupported_action_type",
+                "action_type": "mouse_drag",
+                "platforms": ["linux"],
+                "severity": "Error",
+                "message": "Drag is unreliable on Linux"
+            },
+            {
+                "id": "R002",
+                "kind": "max_action_count",
+                "max": 1,
+                "severity": "Warning",
+                "message": "Script is too long"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_reload_drops_removed_files_and_rejects_rollback() {
+        use crate::validation::ScriptValidator;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let rules_dir = dir.path().join("rules");
+        std::fs::create_dir_all(&rules_dir).unwrap();
+        let versions_path = dir.path().join("rule_versions.json");
+        let write_rule_file = |version: u32| {
+            let json = RULE_SET.replace("\"version\": 2", &format!("\"version\": {}", version));
+            std::fs::write(rules_dir.join("compat.json"), serde_json::to_string(&sign(&json)).unwrap()).unwrap();
+        };
+
+        write_rule_file(3);
+        let mut validator = ScriptValidator::new();
+        let mut versions = RuleVersionStore::load(&versions_path).unwrap();
+        assert_eq!(validator.reload_rule_directory(&rules_dir, &trusted_keys(), &mut versions).unwrap(), 1);
+        assert_eq!(validator.rule_sets()[0].version, 3);
+
+        // After a restart, an older signed file is still refused
+        write_rule_file(2);
+        let mut validator = ScriptValidator::new();
+        let mut versions = RuleVersionStore::load(&versions_path).unwrap();
+        assert_eq!(versions.highest_version("compat"), Some(3));
+        assert_eq!(validator.reload_rule_directory(&rules_dir, &trusted_keys(), &mut versions).unwrap(), 0);
+        assert!(validator.rule_sets().is_empty());
+
+        // Removing the file removes its rules on the next reload
+        write_rule_file(4);
+        validator.reload_rule_directory(&rules_dir, &trusted_keys(), &mut versions).unwrap();
+        assert_eq!(validator.rule_sets().len(), 1);
+        std::fs::remove_file(rules_dir.join("compat.json")).unwrap();
+        validator.reload_rule_directory(&rules_dir, &trusted_keys(), &mut versions).unwrap();
+        assert!(validator.rule_sets().is_empty());
+    }
+
+    #[test]
+    fn test_verify_valid_rule_file() {
+        let rule_set = sign(RULE_SET).verify(&trusted_keys()).unwrap();
+        assert_eq!(rule_set.id, "compat");
+        assert_eq!(rule_set.rules.len(), 2);
+    }
+
+    #[test]
+    fn test_reject_tampered_payload() {
+        let mut file = sign(RULE_SET);
+        file.payload = general_purpose::STANDARD.encode(RULE_SET.replace("\"max\": 1", "\"max\": 100"));
+        assert!(file.verify(&trusted_keys()).is_err());
+    }
+
+    #[test]
+    fn test_reject_untrusted_key() {
+        let mut file = sign(RULE_SET);
+        file.key_id = "unknown".to_string();
+        assert!(file.verify(&trusted_keys()).is_err());
+    }
+
+    #[test]
+    fn test_apply_rules() {
+        let rule_set = sign(RULE_SET).verify(&trusted_keys()).unwrap();
+
+        let mut script = ScriptData::new("rust", "linux");
+        script.add_action(Action::mouse_move(10, 10, 0.1));
+        let mut drag = Action::mouse_move(20, 20, 0.2);
+        drag.action_type = ActionType::MouseDrag;
+        script.add_action(drag);
+
+        let mut issues = Vec::new();
+        rule_set.apply(&script, &mut issues);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].field, "actions[1]");
+        assert!(issues[0].message.starts_with("[R001]"));
+        assert_eq!(issues[1].field, "actions");
+    }
+}
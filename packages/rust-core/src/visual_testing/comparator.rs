@@ -707,8 +707,12 @@ impl ImageComparator {
     ) -> VisualResult<ComparisonResult> {
         use crate::visual_testing::ScreenCapture;
         
-        // Use default capture config if none provided
-        let capture_config = capture_config.unwrap_or_default();
+        // Use default capture config if none provided, degraded on low-spec hosts
+        let capture_config = capture_config.unwrap_or_else(|| {
+            let mut config = crate::visual_testing::CaptureConfig::default();
+            crate::resource_profile::configured_resource_profile().apply_to_capture(&mut config);
+            config
+        });
         
         // Capture screenshot with retry logic
         let capture_result = ScreenCapture::capture_with_retry(capture_config)
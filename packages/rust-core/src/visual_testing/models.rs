@@ -335,11 +335,14 @@ pub struct VisualTestConfig {
 impl VisualTestConfig {
     /// Create a new visual test configuration
     pub fn new(action_id: String, baseline_path: String) -> Self {
+        let mut performance = PerformanceConfig::default();
+        crate::resource_profile::configured_resource_profile().apply_to_visual_performance(&mut performance);
+
         Self {
             action_id,
             baseline_path,
             comparison: ComparisonConfig::default(),
-            performance: PerformanceConfig::default(),
+            performance,
         }
     }
 
@@ -7,7 +7,7 @@ use rust_automation_core::{
     player::Player,
     recorder::Recorder,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 use std::time::Duration;
 use std::thread;
 
@@ -23,7 +23,7 @@ fn test_complete_record_playback_workflow() {
     let mut recorder = Recorder::new(config.clone()).expect("Failed to create recorder");
     
     // Set up event sender for recording feedback
-    let (rec_sender, mut rec_receiver) = mpsc::unbounded_channel();
+    let (rec_sender, mut rec_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     recorder.set_event_sender(rec_sender);
     
     println!("✓ Recorder created successfully");
@@ -72,7 +72,7 @@ fn test_complete_record_playback_workflow() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender for playback feedback
-    let (play_sender, mut play_receiver) = mpsc::unbounded_channel();
+    let (play_sender, mut play_receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(play_sender);
     
     assert!(player.has_event_sender(), "Event sender should be initialized");
@@ -264,7 +264,7 @@ fn test_playback_speed_variations() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -294,7 +294,7 @@ fn test_playback_loop_configuration() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
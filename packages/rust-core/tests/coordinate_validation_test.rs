@@ -4,7 +4,7 @@ use rust_automation_core::{
     AutomationConfig, ScriptData, Action, ActionType,
     player::Player,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 
 #[test]
 fn test_coordinate_clamping_with_out_of_bounds() {
@@ -20,7 +20,7 @@ fn test_coordinate_clamping_with_out_of_bounds() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Load script
@@ -86,7 +86,7 @@ fn test_unsupported_action_handling() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Load script
@@ -107,7 +107,7 @@ fn test_concurrent_playback_prevention() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Load script
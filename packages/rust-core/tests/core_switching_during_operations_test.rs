@@ -7,7 +7,7 @@ use rust_automation_core::{
     player::Player,
     recorder::Recorder,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
 use std::thread;
 use std::time::Duration;
@@ -21,7 +21,7 @@ fn test_cannot_switch_cores_during_recording() {
     let mut recorder = Recorder::new(config).expect("Failed to create recorder");
     
     // Set up event sender
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     recorder.set_event_sender(sender);
     
     // Verify recorder is not recording initially
@@ -64,7 +64,7 @@ fn test_cannot_switch_cores_during_playback() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Load script
@@ -206,7 +206,7 @@ fn test_concurrent_playback_prevention() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Load script
@@ -258,7 +258,7 @@ fn test_operation_state_management_integration() {
     script.add_action(Action::mouse_move(100, 100, 0.0));
     script.add_action(Action::mouse_move(200, 200, 0.5));
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     player.load_script(script).expect("Failed to load script");
     println!("✓ Script loaded successfully");
@@ -5,7 +5,7 @@ use rust_automation_core::{
     AutomationConfig, ScriptData, Action, ActionType,
     player::Player,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 use std::time::Duration;
 
 /// Test complete playback flow with various action types
@@ -35,7 +35,7 @@ fn test_complete_playback_flow_end_to_end() {
     let mut player = Player::new(config).expect("Failed to create player");
     
     // Set up event sender to capture UI updates
-    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let (sender, mut receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Verify event sender is initialized
@@ -115,7 +115,7 @@ fn test_realistic_user_interaction_sequence() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script.clone());
@@ -149,7 +149,7 @@ fn test_rapid_action_sequence() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script.clone());
@@ -169,7 +169,7 @@ fn test_playback_status_tracking() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -217,7 +217,7 @@ fn test_event_streaming_setup() {
     assert!(!player.has_event_sender(), "Should not have event sender initially");
     
     // Set up event sender
-    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let (sender, mut receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Verify event sender is set
@@ -244,7 +244,7 @@ fn test_multiple_loops_configuration() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -269,7 +269,7 @@ fn test_playback_speed_configuration() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -5,7 +5,7 @@ use rust_automation_core::{
     AutomationConfig, ScriptData, Action, ActionType,
     player::Player,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 use std::collections::HashMap;
 
 /// Test playback with invalid coordinates (out of bounds)
@@ -25,7 +25,7 @@ fn test_invalid_coordinates_handling() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     // Script should load successfully even with invalid coordinates
@@ -79,7 +79,7 @@ fn test_unsupported_actions_handling() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
@@ -144,7 +144,7 @@ fn test_missing_action_parameters() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
@@ -172,7 +172,7 @@ fn test_stop_during_playback() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -208,7 +208,7 @@ fn test_pause_resume_during_playback() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -241,7 +241,7 @@ fn test_concurrent_playback_prevention() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -313,7 +313,7 @@ fn test_error_messages_and_recovery() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
@@ -339,7 +339,7 @@ fn test_missing_permissions_handling() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -367,7 +367,7 @@ fn test_empty_script_handling() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
@@ -387,7 +387,7 @@ fn test_single_action_script() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
@@ -409,7 +409,7 @@ fn test_zero_timestamp_actions() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
@@ -438,7 +438,7 @@ fn test_error_context_information() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -464,7 +464,7 @@ fn test_system_under_load() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script);
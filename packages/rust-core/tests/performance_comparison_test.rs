@@ -7,7 +7,7 @@ use rust_automation_core::{
     player::Player,
     recorder::Recorder,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 use std::time::{Duration, Instant};
 
 /// Test 18.3.1: Record same actions with both cores
@@ -112,7 +112,7 @@ fn test_playback_timing_comparison() {
     let config = AutomationConfig::default();
     let mut rust_player = Player::new(config.clone()).expect("Failed to create Rust player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     rust_player.set_event_sender(sender);
     rust_player.load_script(script.clone()).expect("Failed to load script");
     
@@ -122,7 +122,7 @@ fn test_playback_timing_comparison() {
     println!("\nSimulating Python core playback timing...");
     let mut python_player = Player::new(config).expect("Failed to create Python player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     python_player.set_event_sender(sender);
     python_player.load_script(script.clone()).expect("Failed to load script");
     
@@ -172,7 +172,7 @@ fn test_success_rate_comparison() {
     
     for (i, script) in test_scripts.iter().enumerate() {
         let mut player = Player::new(config.clone()).expect("Failed to create Rust player");
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         match player.load_script(script.clone()) {
@@ -198,7 +198,7 @@ fn test_success_rate_comparison() {
     
     for (i, script) in test_scripts.iter().enumerate() {
         let mut player = Player::new(config.clone()).expect("Failed to create Python player");
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         
         match player.load_script(script.clone()) {
@@ -254,7 +254,7 @@ fn test_performance_metrics_display() {
     println!("Collecting Rust core metrics...");
     let rust_start = Instant::now();
     let mut rust_player = Player::new(config.clone()).expect("Failed to create Rust player");
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     rust_player.set_event_sender(sender);
     rust_player.load_script(script.clone()).expect("Failed to load script");
     let rust_load_time = rust_start.elapsed();
@@ -276,7 +276,7 @@ fn test_performance_metrics_display() {
     println!("\nCollecting Python core metrics...");
     let python_start = Instant::now();
     let mut python_player = Player::new(config).expect("Failed to create Python player");
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     python_player.set_event_sender(sender);
     python_player.load_script(script.clone()).expect("Failed to load script");
     let python_load_time = python_start.elapsed();
@@ -355,7 +355,7 @@ fn test_complete_performance_comparison_workflow() {
         // Test Rust core
         let rust_start = Instant::now();
         let mut rust_player = Player::new(config.clone()).expect("Failed to create Rust player");
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         rust_player.set_event_sender(sender);
         let rust_result = rust_player.load_script(script.clone());
         let rust_time = rust_start.elapsed();
@@ -363,7 +363,7 @@ fn test_complete_performance_comparison_workflow() {
         // Test Python core (simulated)
         let python_start = Instant::now();
         let mut python_player = Player::new(config.clone()).expect("Failed to create Python player");
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         python_player.set_event_sender(sender);
         let python_result = python_player.load_script(script.clone());
         let python_time = python_start.elapsed();
@@ -5,7 +5,7 @@ use rust_automation_core::{
     AutomationConfig, ScriptData, Action,
     player::Player,
 };
-use tokio::sync::mpsc;
+use rust_automation_core::{event_channel, DEFAULT_EVENT_CAPACITY};
 use std::time::{Duration, Instant};
 
 /// Test timestamp delay respect
@@ -24,7 +24,7 @@ fn test_timestamp_delay_respect() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -60,7 +60,7 @@ fn test_1x_speed_timing_accuracy() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -95,7 +95,7 @@ fn test_speed_scaling_proportionality() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -142,7 +142,7 @@ fn test_zero_and_minimal_delays() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     let result = player.load_script(script.clone());
@@ -172,7 +172,7 @@ fn test_timing_error_logging() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -216,7 +216,7 @@ fn test_playback_at_various_speeds() {
     
     for (requested_speed, expected_speed) in test_cases {
         let mut player = Player::new(config.clone()).expect("Failed to create player");
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         player.load_script(script.clone()).expect("Failed to load script");
         
@@ -247,7 +247,7 @@ fn test_timing_accuracy_measurement() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -286,7 +286,7 @@ fn test_timing_with_different_action_types() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -314,7 +314,7 @@ fn test_timing_drift_accumulation() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -345,7 +345,7 @@ fn test_timing_statistics_collection() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");
@@ -387,7 +387,7 @@ fn test_speed_parameter_validation() {
     
     for speed in test_speeds {
         let mut player = Player::new(config.clone()).expect("Failed to create player");
-        let (sender, _receiver) = mpsc::unbounded_channel();
+        let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
         player.set_event_sender(sender);
         player.load_script(script.clone()).expect("Failed to load script");
         
@@ -413,7 +413,7 @@ fn test_timing_with_loops() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script.clone()).expect("Failed to load script");
@@ -445,7 +445,7 @@ fn test_timing_tolerance_verification() {
     let config = AutomationConfig::default();
     let mut player = Player::new(config).expect("Failed to create player");
     
-    let (sender, _receiver) = mpsc::unbounded_channel();
+    let (sender, _receiver) = event_channel(DEFAULT_EVENT_CAPACITY);
     player.set_event_sender(sender);
     
     player.load_script(script).expect("Failed to load script");